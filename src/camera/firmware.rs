@@ -0,0 +1,100 @@
+use super::{Error, InvalidFirmwareVersionSnafu};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize, Serializer};
+use snafu::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Version of the camera firmware, e.g. `3.00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct FirmwareVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl FirmwareVersion {
+    pub fn new(major: u32, minor: u32) -> Self {
+        FirmwareVersion { major, minor }
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{:02}", self.major, self.minor)
+    }
+}
+
+impl FromStr for FirmwareVersion {
+    type Err = Error;
+
+    /// Reads `3.00`, or the version in the `Software` tag written by the
+    /// camera, e.g. `Digital Camera X-T3 Ver3.00`. The version of other
+    /// software, e.g. `Adobe Lightroom 13.1`, is not read.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        static RE: Lazy<Regex> =
+            Lazy::new(|| Regex::new(r"(?i)^(?:.*\bver\.?\s*)?([0-9]+)\.([0-9]+)$").unwrap());
+
+        let captures = RE
+            .captures(s.trim())
+            .context(InvalidFirmwareVersionSnafu { value: s })?;
+
+        let number = |i: usize| {
+            captures[i]
+                .parse::<u32>()
+                .ok()
+                .context(InvalidFirmwareVersionSnafu { value: s })
+        };
+
+        Ok(FirmwareVersion {
+            major: number(1)?,
+            minor: number(2)?,
+        })
+    }
+}
+
+impl TryFrom<String> for FirmwareVersion {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Serialize for FirmwareVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_versions() {
+        assert_eq!(
+            "3.00".parse::<FirmwareVersion>().unwrap(),
+            FirmwareVersion::new(3, 0)
+        );
+        assert_eq!(
+            "Digital Camera X100VI Ver1.20"
+                .parse::<FirmwareVersion>()
+                .unwrap(),
+            FirmwareVersion::new(1, 20)
+        );
+        assert!("Digital Camera".parse::<FirmwareVersion>().is_err());
+        assert!("Adobe Lightroom 13.1".parse::<FirmwareVersion>().is_err());
+    }
+
+    #[test]
+    fn it_compares_versions() {
+        assert!(FirmwareVersion::new(1, 20) < FirmwareVersion::new(2, 0));
+        assert!(FirmwareVersion::new(3, 10) > FirmwareVersion::new(3, 1));
+        assert_eq!(FirmwareVersion::new(3, 1).to_string(), "3.01");
+    }
+}
@@ -0,0 +1,373 @@
+use crate::recipe::report::Field;
+use crate::recipe::{FilmSimulation, TransSensor};
+use log::trace;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+pub mod firmware;
+
+pub use firmware::FirmwareVersion;
+
+/// Version of the database format, `version` in the JSON.
+pub const DATABASE_VERSION: u32 = 1;
+
+const EMBEDDED: &str = include_str!("models.json");
+
+static DATABASE: Lazy<RwLock<CameraDatabase>> =
+    Lazy::new(|| RwLock::new(CameraDatabase::embedded()));
+
+/// Inclusive range of the values a setting can take on a camera.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ValueRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ValueRange {
+    pub fn contains(&self, value: f64) -> bool {
+        self.min <= value && value <= self.max
+    }
+}
+
+/// What a camera body supports, from the camera database.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CameraModel {
+    /// Name as written by the camera in the EXIF `Model` tag.
+    pub name: String,
+    /// Other names the model is known by.
+    pub aliases: Vec<String>,
+    pub sensor: TransSensor,
+    /// Film simulation names, filters excluded, e.g. `Acros` or `Classic Chrome`.
+    pub film_simulations: Vec<String>,
+    pub settings: Vec<Field>,
+    pub ranges: HashMap<Field, ValueRange>,
+    /// Film simulations & settings added by firmware updates, oldest first.
+    pub firmware: Vec<FirmwareUpdate>,
+}
+
+/// Film simulations & settings a firmware update added to a camera.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FirmwareUpdate {
+    pub version: FirmwareVersion,
+    #[serde(default)]
+    pub film_simulations: Vec<String>,
+    #[serde(default)]
+    pub settings: Vec<Field>,
+}
+
+impl CameraModel {
+    /// Finds a model by its name or one of its aliases, ignoring case and a
+    /// leading `FUJIFILM`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use fuji::camera::CameraModel;
+    /// use fuji::recipe::TransSensor;
+    ///
+    /// let model = CameraModel::lookup("X100VI").unwrap();
+    /// assert_eq!(model.sensor, TransSensor::TransV);
+    /// ```
+    pub fn lookup(name: &str) -> Option<CameraModel> {
+        DATABASE.read().ok()?.lookup(name).cloned()
+    }
+
+    /// Adds a model to the database used by [`CameraModel::lookup`], replacing
+    /// a model with the same name.
+    pub fn register(model: CameraModel) {
+        if let Ok(mut database) = DATABASE.write() {
+            database.register(model);
+        }
+    }
+
+    /// What the camera supports with `firmware` installed. Without a
+    /// version, every update is expected to be installed.
+    pub fn at_firmware(&self, firmware: Option<&FirmwareVersion>) -> CameraModel {
+        let mut model = self.clone();
+
+        for update in &self.firmware {
+            if firmware.is_some_and(|firmware| *firmware < update.version) {
+                continue;
+            }
+
+            for film_simulation in &update.film_simulations {
+                if !model.film_simulations.contains(film_simulation) {
+                    model.film_simulations.push(film_simulation.clone());
+                }
+            }
+
+            for field in &update.settings {
+                if !model.settings.contains(field) {
+                    model.settings.push(*field);
+                }
+            }
+        }
+
+        model
+    }
+
+    pub fn supports(&self, field: Field) -> bool {
+        self.settings.contains(&field)
+    }
+
+    pub fn supports_film_simulation(&self, film_simulation: &FilmSimulation) -> bool {
+        let name = match film_simulation {
+            FilmSimulation::Acros { .. } => "Acros".to_string(),
+            FilmSimulation::Monochrome { .. } => "Monochrome".to_string(),
+            film_simulation => film_simulation.to_string(),
+        };
+
+        self.film_simulations.contains(&name)
+    }
+
+    pub fn range(&self, field: Field) -> Option<ValueRange> {
+        self.ranges.get(&field).copied()
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        normalize(&self.name) == name || self.aliases.iter().any(|a| normalize(a) == name)
+    }
+}
+
+/// Adds the models of a JSON database, in the format of the embedded one, to
+/// the database used by [`CameraModel::lookup`].
+pub fn extend_from_json(json: &str) -> Result<(), Error> {
+    let extension = CameraDatabase::from_json(json)?;
+
+    if let Ok(mut database) = DATABASE.write() {
+        database.extend(extension);
+    }
+
+    Ok(())
+}
+
+/// Camera models and what they support. The one compiled into the crate is
+/// used by [`CameraModel::lookup`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraDatabase {
+    models: Vec<CameraModel>,
+}
+
+impl CameraDatabase {
+    /// The database compiled into the crate, without the models added at
+    /// runtime.
+    pub fn embedded() -> Self {
+        CameraDatabase::from_json(EMBEDDED).expect("embedded camera database is valid")
+    }
+
+    /// Reads a database, each model getting the film simulations, settings &
+    /// ranges of its sensor unless it lists its own.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let file: DatabaseFile = serde_json::from_str(json).context(InvalidJsonSnafu)?;
+
+        ensure!(
+            file.version == DATABASE_VERSION,
+            UnsupportedVersionSnafu {
+                version: file.version
+            }
+        );
+
+        let models = file
+            .models
+            .into_iter()
+            .map(|entry| {
+                let sensor = file
+                    .sensors
+                    .iter()
+                    .find(|sensor| sensor.sensor == entry.sensor);
+
+                CameraModel {
+                    film_simulations: entry
+                        .film_simulations
+                        .or_else(|| sensor.map(|s| s.film_simulations.clone()))
+                        .unwrap_or_default(),
+                    settings: entry
+                        .settings
+                        .or_else(|| sensor.map(|s| s.settings.clone()))
+                        .unwrap_or_else(|| entry.sensor.fields().to_vec()),
+                    ranges: entry
+                        .ranges
+                        .or_else(|| sensor.map(|s| s.ranges.clone()))
+                        .unwrap_or_default(),
+                    name: entry.name,
+                    aliases: entry.aliases,
+                    sensor: entry.sensor,
+                    firmware: entry.firmware,
+                }
+            })
+            .collect();
+
+        Ok(CameraDatabase { models })
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<&CameraModel> {
+        let name = normalize(name);
+        let model = self.models.iter().find(|model| model.matches(&name));
+
+        trace!(
+            "CameraDatabase::lookup: {} {:?}",
+            name,
+            model.map(|m| &m.name)
+        );
+
+        model
+    }
+
+    pub fn register(&mut self, model: CameraModel) {
+        let name = normalize(&model.name);
+        self.models.retain(|m| normalize(&m.name) != name);
+        self.models.push(model);
+    }
+
+    /// Registers every model of `other`, replacing the ones with the same name.
+    pub fn extend(&mut self, other: CameraDatabase) {
+        for model in other.models {
+            self.register(model);
+        }
+    }
+
+    pub fn models(&self) -> &[CameraModel] {
+        &self.models
+    }
+}
+
+fn normalize(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    let name = name.strip_prefix("fujifilm").unwrap_or(&name);
+
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[derive(Deserialize)]
+struct DatabaseFile {
+    version: u32,
+    #[serde(default)]
+    sensors: Vec<SensorEntry>,
+    models: Vec<ModelEntry>,
+}
+
+#[derive(Deserialize)]
+struct SensorEntry {
+    sensor: TransSensor,
+    film_simulations: Vec<String>,
+    settings: Vec<Field>,
+    #[serde(default)]
+    ranges: HashMap<Field, ValueRange>,
+}
+
+#[derive(Deserialize)]
+struct ModelEntry {
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    sensor: TransSensor,
+    film_simulations: Option<Vec<String>>,
+    settings: Option<Vec<Field>>,
+    ranges: Option<HashMap<Field, ValueRange>>,
+    #[serde(default)]
+    firmware: Vec<FirmwareUpdate>,
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid camera database: {}", source))]
+    InvalidJson { source: serde_json::Error },
+
+    #[snafu(display(
+        "Unsupported camera database version {}, expected {}",
+        version,
+        DATABASE_VERSION
+    ))]
+    UnsupportedVersion { version: u32 },
+
+    #[snafu(display("Invalid firmware version: {}", value))]
+    InvalidFirmwareVersion { value: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recipe::MonochromaticFilter;
+
+    #[test]
+    fn it_reads_the_embedded_database() {
+        let database = CameraDatabase::embedded();
+
+        assert!(database.models().len() >= 35);
+        assert!(database
+            .models()
+            .iter()
+            .all(|model| !model.film_simulations.is_empty() && !model.settings.is_empty()));
+    }
+
+    #[test]
+    fn it_looks_up_aliases() {
+        let model = CameraModel::lookup("FUJIFILM x-h2 s").unwrap();
+
+        assert_eq!(model.name, "X-H2S");
+        assert_eq!(model.sensor, TransSensor::TransV);
+        assert_eq!(CameraModel::lookup("X-T30II").unwrap().name, "X-T30 II");
+        assert_eq!(CameraModel::lookup("FinePix S1"), None);
+    }
+
+    #[test]
+    fn it_knows_model_capabilities() {
+        let model = CameraModel::lookup("X-T3").unwrap();
+
+        assert!(!model.supports(Field::Clarity));
+        assert!(model.supports_film_simulation(&FilmSimulation::Acros {
+            filter: MonochromaticFilter::Red
+        }));
+        assert!(!model.supports_film_simulation(&FilmSimulation::ClassicNeg));
+        assert_eq!(
+            model.range(Field::Color),
+            Some(ValueRange {
+                min: -4.0,
+                max: 4.0
+            })
+        );
+    }
+
+    #[test]
+    fn it_registers_models() {
+        let json = r#"{
+            "version": 1,
+            "models": [{ "name": "X-Test1", "aliases": ["XT1 Test"], "sensor": "TransIII" }]
+        }"#;
+
+        let mut database = CameraDatabase::embedded();
+        database.extend(CameraDatabase::from_json(json).unwrap());
+
+        let model = database.lookup("xt1 test").unwrap();
+        assert_eq!(model.sensor, TransSensor::TransIII);
+        assert_eq!(model.settings, TransSensor::TransIII.fields());
+    }
+
+    #[test]
+    fn it_adds_firmware_updates() {
+        let model = CameraModel::lookup("X-T3").unwrap();
+
+        let launch = model.at_firmware(Some(&FirmwareVersion::new(1, 0)));
+        assert!(!launch.supports(Field::ColorChromeFxBlue));
+        assert!(!launch.supports_film_simulation(&FilmSimulation::ClassicNeg));
+
+        let updated = model.at_firmware(Some(&FirmwareVersion::new(3, 0)));
+        assert!(updated.supports(Field::ColorChromeFxBlue));
+        assert!(!updated.supports_film_simulation(&FilmSimulation::ClassicNeg));
+
+        let latest = model.at_firmware(None);
+        assert!(latest.supports(Field::ColorChromeFxBlue));
+        assert!(latest.supports_film_simulation(&FilmSimulation::ClassicNeg));
+    }
+
+    #[test]
+    fn it_rejects_other_versions() {
+        assert!(matches!(
+            CameraDatabase::from_json(r#"{ "version": 2, "models": [] }"#),
+            Err(Error::UnsupportedVersion { version: 2 })
+        ));
+    }
+}
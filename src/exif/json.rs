@@ -1,4 +1,4 @@
-use super::ExifData;
+use super::{split_group, ExifData, ExifValue};
 use log::trace;
 use snafu::prelude::*;
 
@@ -13,32 +13,17 @@ impl TryFrom<JsonValue> for Vec<ExifData> {
             .as_object()
             .context(EmptySnafu)?
             .into_iter()
-            .filter_map(|(tag, value)| {
+            .map(|(tag, value)| {
                 trace!("{:?}: {:?}", tag, value);
 
-                if let Some(value) = value.as_str() {
-                    let value = value.to_string().trim().to_string();
-
-                    Some(ExifData(tag.clone(), value))
-                } else if let Some(value) = value.as_number() {
-                    Some(ExifData(tag.clone(), value.to_string()))
-                } else if let Some(values) = value.as_array() {
-                    let value: String = values.iter().fold(String::from(""), |acc, value| {
-                        let value: String = if let Some(value) = value.as_str() {
-                            let value = value.to_string().trim().to_string();
-
-                            format!("{}, {}", acc, value)
-                        } else {
-                            acc
-                        };
-
-                        value
-                    });
-
-                    Some(ExifData(tag.clone(), value))
-                } else {
-                    None
-                }
+                // Named `Group:Tag` when read with `-G1`
+                let (group, tag) = split_group(tag);
+
+                ExifData(
+                    tag.to_string(),
+                    to_exif_value(value),
+                    group.map(|g| g.to_string()),
+                )
             })
             .collect();
 
@@ -46,8 +31,100 @@ impl TryFrom<JsonValue> for Vec<ExifData> {
     }
 }
 
+/// Keeps the JSON type of the value, so `true` and `"true"` stay apart.
+fn to_exif_value(value: &serde_json::Value) -> ExifValue {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => ExifValue::Null,
+        Value::Bool(value) => ExifValue::Bool(*value),
+        Value::String(value) => ExifValue::parse(value),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => ExifValue::Int(value),
+            None => number
+                .as_f64()
+                .map_or_else(|| ExifValue::Str(number.to_string()), ExifValue::Float),
+        },
+        Value::Array(values) => ExifValue::List(values.iter().map(to_exif_value).collect()),
+        Value::Object(fields) => ExifValue::Map(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), to_exif_value(value)))
+                .collect(),
+        ),
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display("No Object found"))]
     Empty,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(value: serde_json::Value) -> Vec<ExifData> {
+        JsonValue(value).try_into().unwrap()
+    }
+
+    #[test]
+    fn it_keeps_json_types() {
+        let fields = parse(json!({
+            "Make": " FUJIFILM ",
+            "ISO": 400,
+            "FNumber": 5.6,
+            "ExposureTime": "1/250",
+            "Flag": true,
+            "Text": "true",
+            "Missing": null,
+        }));
+
+        // serde_json sorts the keys
+        assert_eq!(
+            fields,
+            vec![
+                ExifData::with_value("ExposureTime", ExifValue::Rational(1, 250)),
+                ExifData::with_value("FNumber", ExifValue::Float(5.6)),
+                ExifData::with_value("Flag", ExifValue::Bool(true)),
+                ExifData::with_value("ISO", ExifValue::Int(400)),
+                ExifData::with_value("Make", ExifValue::from("FUJIFILM")),
+                ExifData::with_value("Missing", ExifValue::Null),
+                ExifData::with_value("Text", ExifValue::from("true")),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_keeps_every_array_element() {
+        let fields = parse(json!({ "Keywords": ["a", 2, 3.5] }));
+
+        assert_eq!(
+            fields[0].value(),
+            &ExifValue::List(vec![
+                ExifValue::from("a"),
+                ExifValue::Int(2),
+                ExifValue::Float(3.5),
+            ])
+        );
+        assert_eq!(fields[0].value().to_string(), "a, 2, 3.5");
+    }
+
+    #[test]
+    fn it_keeps_objects() {
+        let fields = parse(json!({ "Region": { "Name": "Face", "Area": [1, 2] } }));
+
+        assert_eq!(
+            fields[0].value(),
+            &ExifValue::Map(vec![
+                (
+                    "Area".to_string(),
+                    ExifValue::List(vec![ExifValue::Int(1), ExifValue::Int(2)])
+                ),
+                ("Name".to_string(), ExifValue::from("Face")),
+            ])
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use super::{split_group, ExifData, FindExifData};
+use std::collections::HashMap;
+
+/// Family 1 groups of the Fujifilm MakerNote, exiftool's `MakerNotes` family 0
+/// group.
+const MAKER_NOTES_GROUPS: &[&str] = &["FujiFilm"];
+
+/// Family 1 groups of the TIFF structure, exiftool's `EXIF` family 0 group.
+const EXIF_GROUPS: &[&str] = &["IFD0", "IFD1", "ExifIFD", "GPS", "InteropIFD", "SubIFD"];
+
+/// Whether `group`, the family 1 group of a tag, is the `wanted` group. The
+/// `MakerNotes` and `EXIF` family 0 groups are accepted as well.
+pub(crate) fn group_matches(group: Option<&str>, wanted: &str) -> bool {
+    let Some(group) = group else {
+        return false;
+    };
+
+    if group.eq_ignore_ascii_case(wanted) {
+        return true;
+    }
+
+    let family = if wanted.eq_ignore_ascii_case("MakerNotes") {
+        MAKER_NOTES_GROUPS
+    } else if wanted.eq_ignore_ascii_case("EXIF") {
+        EXIF_GROUPS
+    } else {
+        return false;
+    };
+
+    family.iter().any(|g| g.eq_ignore_ascii_case(group))
+}
+
+/// MakerNote values take precedence over their generic EXIF counterparts when
+/// looked up without a group, e.g. the MakerNote `WhiteBalance` ("Kelvin")
+/// over the EXIF one ("Manual").
+pub(crate) fn is_preferred(exif: &ExifData) -> bool {
+    group_matches(exif.group(), "MakerNotes")
+}
+
+/// Collection of tags indexed by name, read from exiftool's `-G1` output or
+/// the native reader.
+///
+/// Tags read with `-a` can repeat in several groups, [`ExifMap::get`] returns
+/// the MakerNote one first and [`ExifMap::get_all`] every one of them. A tag
+/// can be qualified with its group, e.g. `FujiFilm:WhiteBalance`, or with the
+/// family 0 `MakerNotes` and `EXIF` groups, e.g. `MakerNotes:WhiteBalance`.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exif::{ExifMap, FindExifData};
+/// use fuji::native;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::path::Path::new("tests/img/DSCF5230.JPG");
+/// let map = ExifMap::from(native::read_metadata(&path)?);
+///
+/// println!("{:?}", map.get("MakerNotes:WhiteBalance"));
+/// println!("{:?}", map.get("EXIF:WhiteBalance"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExifMap {
+    fields: Vec<ExifData>,
+    /// Positions of the fields by tag, the preferred one first.
+    index: HashMap<String, Vec<usize>>,
+}
+
+impl ExifMap {
+    pub fn new() -> Self {
+        ExifMap::default()
+    }
+
+    pub fn insert(&mut self, exif: ExifData) {
+        let position = self.fields.len();
+        let positions = self.index.entry(exif.tag().to_string()).or_default();
+
+        let first_preferred = positions
+            .first()
+            .is_some_and(|p| is_preferred(&self.fields[*p]));
+
+        if is_preferred(&exif) && !first_preferred {
+            positions.insert(0, position);
+        } else {
+            positions.push(position);
+        }
+
+        self.fields.push(exif);
+    }
+
+    /// The field of `exif_tag`, which may be qualified with its group.
+    pub fn get(&self, exif_tag: &str) -> Option<&ExifData> {
+        self.get_all(exif_tag).next()
+    }
+
+    /// Every field of `exif_tag` across groups, the preferred one first.
+    pub fn get_all<'a>(&'a self, exif_tag: &str) -> impl Iterator<Item = &'a ExifData> + 'a {
+        let (group, tag) = split_group(exif_tag);
+        let group = group.map(|g| g.to_string());
+
+        self.index
+            .get(tag)
+            .into_iter()
+            .flatten()
+            .map(|p| &self.fields[*p])
+            .filter(move |exif| {
+                group
+                    .as_deref()
+                    .is_none_or(|g| group_matches(exif.group(), g))
+            })
+    }
+
+    /// Fields in the order they were read.
+    pub fn iter(&self) -> std::slice::Iter<'_, ExifData> {
+        self.fields.iter()
+    }
+
+    pub fn as_slice(&self) -> &[ExifData] {
+        &self.fields
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl FindExifData for ExifMap {
+    fn find(&self, exif_tag: &str) -> Option<ExifData> {
+        self.get(exif_tag).cloned()
+    }
+}
+
+impl From<Vec<ExifData>> for ExifMap {
+    fn from(fields: Vec<ExifData>) -> Self {
+        fields.into_iter().collect()
+    }
+}
+
+impl FromIterator<ExifData> for ExifMap {
+    fn from_iter<I: IntoIterator<Item = ExifData>>(iter: I) -> Self {
+        let mut map = ExifMap::new();
+        iter.into_iter().for_each(|exif| map.insert(exif));
+
+        map
+    }
+}
+
+impl<'a> IntoIterator for &'a ExifMap {
+    type Item = &'a ExifData;
+    type IntoIter = std::slice::Iter<'a, ExifData>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifValue;
+
+    fn white_balance() -> ExifMap {
+        ExifMap::from(vec![
+            ExifData::grouped("IFD0", "Make", ExifValue::from("FUJIFILM")),
+            ExifData::grouped("ExifIFD", "WhiteBalance", ExifValue::from("Manual")),
+            ExifData::grouped("FujiFilm", "WhiteBalance", ExifValue::from("Kelvin")),
+        ])
+    }
+
+    #[test]
+    fn it_prefers_maker_notes() {
+        let map = white_balance();
+
+        assert_eq!(
+            map.get("WhiteBalance").map(|e| e.value().to_string()),
+            Some("Kelvin".to_string())
+        );
+        assert_eq!(map.get("Make").and_then(|e| e.group()), Some("IFD0"));
+    }
+
+    #[test]
+    fn it_finds_group_qualified_tags() {
+        let map = white_balance();
+
+        for (tag, value) in [
+            ("ExifIFD:WhiteBalance", "Manual"),
+            ("EXIF:WhiteBalance", "Manual"),
+            ("FujiFilm:WhiteBalance", "Kelvin"),
+            ("MakerNotes:WhiteBalance", "Kelvin"),
+        ] {
+            assert_eq!(
+                map.find(tag).map(|e| e.value().to_string()),
+                Some(value.to_string()),
+                "{}",
+                tag
+            );
+        }
+
+        assert_eq!(map.get("IFD1:WhiteBalance"), None);
+    }
+
+    #[test]
+    fn it_iterates_over_duplicates() {
+        let map = white_balance();
+
+        let groups: Vec<Option<&str>> = map.get_all("WhiteBalance").map(|e| e.group()).collect();
+
+        assert_eq!(groups, vec![Some("FujiFilm"), Some("ExifIFD")]);
+        assert_eq!(map.len(), 3);
+    }
+}
@@ -4,48 +4,204 @@ use snafu::prelude::*;
 use std::num::{ParseFloatError, ParseIntError};
 
 pub mod json;
+pub mod map;
+pub mod source;
+pub mod value;
+
+pub use map::ExifMap;
+pub use value::ExifValue;
 
 pub type Tag = String;
-pub type Value = String;
+/// exiftool family 1 group of a tag, e.g. `IFD0`, `ExifIFD` or `FujiFilm`.
+pub type Group = String;
+
+/// Suffix exiftool appends to a tag name when its value is extracted without
+/// print conversion, e.g. `FilmMode#`.
+pub const NUMERIC_SUFFIX: &str = "#";
+
+/// Fujifilm MakerNote tags also read as raw numbers.
+///
+/// The printed values are English and change between exiftool versions, the
+/// numbers do not, so the `FromExifData` impls try these first.
+pub const NUMERIC_TAGS: &[&str] = &[
+    "AdvancedFilter",
+    "BWAdjustment",
+    "BWMagentaGreen",
+    "Clarity",
+    "ColorChromeEffect",
+    "ColorChromeFXBlue",
+    "ColorTemperature",
+    "CropMode",
+    "DRangePriority",
+    "DRangePriorityAuto",
+    "DRangePriorityFixed",
+    "DevelopmentDynamicRange",
+    "DynamicRangeSetting",
+    "FilmMode",
+    "GrainEffectRoughness",
+    "GrainEffectSize",
+    "HighlightTone",
+    "NoiseReduction",
+    "PanoramaDirection",
+    "PictureMode",
+    "Saturation",
+    "ShadowTone",
+    "Sharpness",
+    "VideoRecordingMode",
+    "WhiteBalance",
+    "WhiteBalanceFineTune",
+];
+
+/// Name under which the raw number of `tag` is stored.
+pub fn numeric_tag(tag: &str) -> String {
+    format!("{}{}", tag, NUMERIC_SUFFIX)
+}
+
+/// Splits `Group:Tag` into its group and tag, like exiftool's `-G1` names.
+pub fn split_group(exif_tag: &str) -> (Option<&str>, &str) {
+    match exif_tag.split_once(':') {
+        Some((group, tag)) => (Some(group), tag),
+        None => (None, exif_tag),
+    }
+}
 
 #[derive(Debug, Clone, Default, PartialEq)]
-pub struct ExifData(Tag, Value);
+pub struct ExifData(Tag, ExifValue, Option<Group>);
 
 impl ExifData {
     pub fn new(tag: &str, value: &str) -> Self {
-        ExifData(tag.to_string(), value.to_string())
+        ExifData(tag.to_string(), ExifValue::from(value), None)
+    }
+
+    pub fn with_value(tag: &str, value: ExifValue) -> Self {
+        ExifData(tag.to_string(), value, None)
     }
 
+    pub fn grouped(group: &str, tag: &str, value: ExifValue) -> Self {
+        ExifData(tag.to_string(), value, Some(group.to_string()))
+    }
+
+    /// Tag name without its group.
     pub fn tag(&self) -> &str {
         &self.0
     }
 
-    pub fn value(&self) -> &str {
+    pub fn value(&self) -> &ExifValue {
         &self.1
     }
+
+    /// Group the tag was read from, `None` when read without `-G1`.
+    pub fn group(&self) -> Option<&str> {
+        self.2.as_deref()
+    }
 }
 
 pub trait FromExifData {
-    fn from_exif(data: &[ExifData]) -> Option<Self>
+    fn from_exif<D>(data: &D) -> Option<Self>
     where
+        D: FindExifData + ?Sized,
         Self: Sized;
 }
 
+/// Same as [`FromExifData`], telling why the value could not be read.
+pub trait TryFromExifData: Sized {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized;
+}
+
+/// Why a value could not be read, see [`TryFromExifData`].
+#[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+pub enum FieldError {
+    #[snafu(display("{} is missing", tag))]
+    TagMissing { tag: String },
+
+    #[snafu(display("{} has an unknown value {:?}", tag, value))]
+    UnknownValue { tag: String, value: String },
+
+    #[snafu(display("unsupported camera model {}", model))]
+    UnsupportedModel { model: String },
+}
+
+impl FieldError {
+    pub fn missing(tag: &str) -> Self {
+        FieldError::TagMissing {
+            tag: tag.to_string(),
+        }
+    }
+
+    pub fn unknown<V: ToString>(tag: &str, value: V) -> Self {
+        FieldError::UnknownValue {
+            tag: tag.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    /// `tag` could not be read: it is either missing, or has a value that is
+    /// not known. The printed value is reported, else the raw number.
+    pub fn not_read<D>(data: &D, tag: &str) -> Self
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(exif) = data.find(tag) {
+            return FieldError::unknown(tag, exif.value());
+        }
+
+        match data.find_number(tag) {
+            Some(number) => FieldError::unknown(tag, number),
+            None => FieldError::missing(tag),
+        }
+    }
+}
+
 pub trait FindExifData {
     fn find(&self, exif_tag: &str) -> Option<ExifData>;
+
+    /// Raw numbers of `exif_tag`, see [`NUMERIC_TAGS`].
+    fn find_numbers(&self, exif_tag: &str) -> Option<Vec<i64>> {
+        self.find(&numeric_tag(exif_tag))?.value().numbers()
+    }
+
+    /// First raw number of `exif_tag`, see [`NUMERIC_TAGS`].
+    fn find_number(&self, exif_tag: &str) -> Option<i64> {
+        self.find_numbers(exif_tag)?.first().copied()
+    }
 }
 
-impl FindExifData for &[ExifData] {
+/// Linear scan, use [`ExifMap`] to look up many tags. Tags can be qualified
+/// with their group, see [`ExifMap::get`].
+impl FindExifData for [ExifData] {
     fn find(&self, exif_tag: &str) -> Option<ExifData> {
-        let found = self.iter().find(|exif| exif.tag() == exif_tag);
+        let (group, tag) = split_group(exif_tag);
+
+        let mut found = self.iter().filter(|exif| {
+            exif.tag() == tag && group.is_none_or(|g| map::group_matches(exif.group(), g))
+        });
 
-        if let Some(exif) = found {
-            let exif = exif.clone();
+        let first = found.next()?;
 
-            return Some(exif);
+        if map::is_preferred(first) {
+            return Some(first.clone());
         }
 
-        None
+        let exif = found.find(|exif| map::is_preferred(exif)).unwrap_or(first);
+
+        Some(exif.clone())
+    }
+}
+
+impl FindExifData for Vec<ExifData> {
+    fn find(&self, exif_tag: &str) -> Option<ExifData> {
+        self.as_slice().find(exif_tag)
+    }
+}
+
+impl<T> FindExifData for &T
+where
+    T: FindExifData + ?Sized,
+{
+    fn find(&self, exif_tag: &str) -> Option<ExifData> {
+        (**self).find(exif_tag)
     }
 }
 
@@ -64,10 +220,17 @@ impl From<ExifData> for Option<i64> {
 impl TryFrom<ExifData> for i64 {
     type Error = Error;
 
+    /// Typed values are used as-is, the number is only searched for in
+    /// printed strings, e.g. `+2 (hard)`.
     fn try_from(exif: ExifData) -> Result<i64, Error> {
         static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i:([+\-0-9]+))").unwrap());
 
-        let captures = RE.captures(exif.value()).context(NotFoundSnafu)?;
+        if let Some(value) = exif.value().as_i64() {
+            return Ok(value);
+        }
+
+        let text = exif.value().as_str().context(NotFoundSnafu)?;
+        let captures = RE.captures(text).context(NotFoundSnafu)?;
         let value = get_first_match(captures)?;
 
         let value = value.parse::<i64>().context(ParseIntSnafu)?;
@@ -91,10 +254,16 @@ impl From<ExifData> for Option<f64> {
 impl TryFrom<ExifData> for f64 {
     type Error = Error;
 
+    /// Same as the `i64` conversion, e.g. `-1.5 (medium soft)`.
     fn try_from(exif: ExifData) -> Result<f64, Error> {
         static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i:([+\-0-9.]+))").unwrap());
 
-        let captures = RE.captures(exif.value()).context(NotFoundSnafu)?;
+        if let Some(value) = exif.value().as_f64() {
+            return Ok(value);
+        }
+
+        let text = exif.value().as_str().context(NotFoundSnafu)?;
+        let captures = RE.captures(text).context(NotFoundSnafu)?;
         let value = get_first_match(captures)?;
 
         let value = value.parse::<f64>().context(ParseFloatSnafu)?;
@@ -0,0 +1,327 @@
+use super::json::{Error as JsonError, JsonValue};
+use super::{ExifData, NUMERIC_TAGS};
+use log::{debug, warn};
+use snafu::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// A backend that reads [`ExifData`] from an image.
+///
+/// Implemented by [`crate::exiftool::spawn::ExiftoolSpawn`],
+/// [`crate::exiftool::session::ExiftoolSession`], [`crate::native::NativeReader`]
+/// and [`JsonFixture`], so the backend can be chosen at runtime.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exif::source::{Fallback, MetadataSource};
+/// use fuji::exiftool::spawn::ExiftoolSpawn;
+/// use fuji::native::NativeReader;
+/// use fuji::recipe::read;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // Use the native reader, and exiftool for what it cannot decode
+/// let mut source: Box<dyn MetadataSource> =
+///     Box::new(Fallback::new(NativeReader, ExiftoolSpawn::new(None)));
+///
+/// let path = std::path::Path::new("tests/img/DSCF5230.JPG");
+/// let metadata = source.read_metadata(path)?;
+/// let recipe = read::from_exif(&metadata)?;
+///
+/// println!("Recipe: {:?}", recipe);
+/// # Ok(())
+/// # }
+/// ```
+pub trait MetadataSource {
+    fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, Error>;
+}
+
+impl<T> MetadataSource for Box<T>
+where
+    T: MetadataSource + ?Sized,
+{
+    fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, Error> {
+        (**self).read_metadata(img_path)
+    }
+}
+
+/// Reads with `primary` and falls back to `fallback` when it fails, or when it
+/// returns a recipe setting it could not decode (printed as `Unknown (...)`).
+///
+/// When only the fallback for an undecoded setting fails, e.g. because
+/// exiftool is not installed, what `primary` read is kept.
+pub struct Fallback<A, B> {
+    primary: A,
+    fallback: B,
+}
+
+impl<A, B> Fallback<A, B>
+where
+    A: MetadataSource,
+    B: MetadataSource,
+{
+    pub fn new(primary: A, fallback: B) -> Self {
+        Fallback { primary, fallback }
+    }
+}
+
+impl<A, B> MetadataSource for Fallback<A, B>
+where
+    A: MetadataSource,
+    B: MetadataSource,
+{
+    fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, Error> {
+        let fields = match self.primary.read_metadata(img_path) {
+            Ok(fields) => fields,
+            Err(primary) => {
+                debug!("Primary source failed, falling back: {}", primary);
+
+                return self
+                    .fallback
+                    .read_metadata(img_path)
+                    .map_err(|fallback| Error::Fallback {
+                        primary: Box::new(primary),
+                        fallback: Box::new(fallback),
+                    });
+            }
+        };
+
+        let Some(unknown) = fields.iter().find(|e| is_undecoded_setting(e)) else {
+            return Ok(fields);
+        };
+
+        debug!(
+            "Could not decode {}: {:?}, falling back",
+            unknown.tag(),
+            unknown.value()
+        );
+
+        match self.fallback.read_metadata(img_path) {
+            Ok(fallback) => Ok(fallback),
+            Err(e) => {
+                warn!("Fallback failed, keeping the primary source: {}", e);
+
+                Ok(fields)
+            }
+        }
+    }
+}
+
+/// A value the recipe reads, the film simulation & the [`NUMERIC_TAGS`], that
+/// was printed as `Unknown (...)`. Other tags, e.g. of the lens, do not matter.
+fn is_undecoded_setting(exif: &ExifData) -> bool {
+    NUMERIC_TAGS.contains(&exif.tag())
+        && exif
+            .value()
+            .as_str()
+            .is_some_and(|v| v.starts_with("Unknown ("))
+}
+
+/// Pre-recorded exiftool JSON output (`exiftool -a -j`), useful for tests and
+/// for images that are no longer around.
+///
+/// Entries are matched by their `SourceFile`, or by the file name when the
+/// fixture was recorded in another directory.
+#[derive(Debug, Clone)]
+pub struct JsonFixture {
+    entries: Vec<(PathBuf, serde_json::Value)>,
+}
+
+impl JsonFixture {
+    pub fn open<P>(fixture_path: &P) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+    {
+        let json = std::fs::read_to_string(fixture_path).context(FixtureReadSnafu)?;
+
+        Self::parse(&json)
+    }
+
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        let value: serde_json::Value = serde_json::from_str(json).context(FixtureJsonSnafu)?;
+
+        let entries = match value {
+            serde_json::Value::Array(entries) => entries,
+            entry => vec![entry],
+        };
+
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let source_file = entry
+                    .get("SourceFile")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .replace('\\', "/");
+
+                (PathBuf::from(source_file), entry)
+            })
+            .collect();
+
+        Ok(JsonFixture { entries })
+    }
+}
+
+impl MetadataSource for JsonFixture {
+    fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, Error> {
+        let wanted = PathBuf::from(img_path.to_string_lossy().replace('\\', "/"));
+
+        let entry = self
+            .entries
+            .iter()
+            .find(|(path, _)| *path == wanted)
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .find(|(path, _)| path.file_name() == wanted.file_name())
+            })
+            .context(FixtureMissingSnafu {
+                path: img_path.to_string_lossy().to_string(),
+            })?;
+
+        JsonValue(entry.1.clone())
+            .try_into()
+            .context(FixtureEntrySnafu)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum Error {
+    #[snafu(display("Exiftool failed: {}", source))]
+    Exiftool {
+        source: crate::exiftool::spawn::Error,
+    },
+
+    #[snafu(display("Native reader failed: {}", source))]
+    Native { source: crate::native::Error },
+
+    #[snafu(display("Failed to read fixture: {:?}", source))]
+    FixtureRead { source: std::io::Error },
+
+    #[snafu(display("Invalid fixture JSON: {:?}", source))]
+    FixtureJson { source: serde_json::Error },
+
+    #[snafu(display("Invalid fixture entry: {}", source))]
+    FixtureEntry { source: JsonError },
+
+    #[snafu(display("No fixture entry for {:?}", path))]
+    FixtureMissing { path: String },
+
+    #[snafu(display("{}, and the fallback failed too: {}", primary, fallback))]
+    Fallback {
+        primary: Box<Error>,
+        fallback: Box<Error>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::FindExifData;
+
+    const FIXTURE: &str = r#"[
+        {"SourceFile": "tests/img/DSCF5230.JPG", "Make": "FUJIFILM", "FilmMode": "Classic Negative"},
+        {"SourceFile": "tests/img/DSCF5358.JPG", "Make": "FUJIFILM", "FilmMode": "Unknown (0x1200)"},
+        {"SourceFile": "tests/img/DSCF0002.JPG", "Make": "FUJIFILM", "LensID": "Unknown (12 34)"}
+    ]"#;
+
+    struct Failing;
+
+    impl MetadataSource for Failing {
+        fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, Error> {
+            Err(Error::FixtureMissing {
+                path: img_path.to_string_lossy().to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn it_reads_fixture_entries() {
+        let mut fixture = JsonFixture::parse(FIXTURE).unwrap();
+        let fields = fixture
+            .read_metadata(Path::new("tests/img/DSCF5230.JPG"))
+            .unwrap();
+
+        assert_eq!(
+            fields.as_slice().find("FilmMode"),
+            Some(ExifData::new("FilmMode", "Classic Negative"))
+        );
+    }
+
+    #[test]
+    fn it_matches_fixture_entries_by_file_name() {
+        let mut fixture = JsonFixture::parse(FIXTURE).unwrap();
+
+        assert!(fixture
+            .read_metadata(Path::new("/elsewhere/DSCF5230.JPG"))
+            .is_ok());
+        assert!(matches!(
+            fixture.read_metadata(Path::new("DSCF0001.JPG")),
+            Err(Error::FixtureMissing { .. })
+        ));
+    }
+
+    #[test]
+    fn it_falls_back_when_the_primary_fails() {
+        let fixture = JsonFixture::parse(FIXTURE).unwrap();
+        let mut source = Fallback::new(Failing, fixture);
+
+        assert!(source
+            .read_metadata(Path::new("tests/img/DSCF5230.JPG"))
+            .is_ok());
+    }
+
+    #[test]
+    fn it_falls_back_on_undecoded_settings() {
+        let primary = JsonFixture::parse(FIXTURE).unwrap();
+        let fallback = JsonFixture::parse(
+            r#"{"SourceFile": "tests/img/DSCF5358.JPG", "FilmMode": "Classic Chrome"}"#,
+        )
+        .unwrap();
+        let mut source: Box<dyn MetadataSource> = Box::new(Fallback::new(primary, fallback));
+
+        let fields = source
+            .read_metadata(Path::new("tests/img/DSCF5358.JPG"))
+            .unwrap();
+
+        assert_eq!(
+            fields.as_slice().find("FilmMode"),
+            Some(ExifData::new("FilmMode", "Classic Chrome"))
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_primary_when_the_fallback_fails() {
+        let fixture = JsonFixture::parse(FIXTURE).unwrap();
+        let mut source = Fallback::new(fixture, Failing);
+
+        let fields = source
+            .read_metadata(Path::new("tests/img/DSCF5358.JPG"))
+            .unwrap();
+
+        assert_eq!(
+            fields.as_slice().find("FilmMode"),
+            Some(ExifData::new("FilmMode", "Unknown (0x1200)"))
+        );
+    }
+
+    #[test]
+    fn it_ignores_undecoded_tags_the_recipe_does_not_read() {
+        let fixture = JsonFixture::parse(FIXTURE).unwrap();
+        let mut source = Fallback::new(fixture, Failing);
+
+        assert!(source
+            .read_metadata(Path::new("tests/img/DSCF0002.JPG"))
+            .is_ok());
+    }
+
+    #[test]
+    fn it_fails_when_both_sources_fail() {
+        let mut source = Fallback::new(Failing, Failing);
+
+        assert!(matches!(
+            source.read_metadata(Path::new("tests/img/DSCF5230.JPG")),
+            Err(Error::Fallback { .. })
+        ));
+    }
+}
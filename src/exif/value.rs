@@ -0,0 +1,198 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+
+/// Value of a tag, keeping the type exiftool's JSON or the native reader gave
+/// it instead of flattening everything into a string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExifValue {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    /// Numerator & denominator, e.g. an exposure time of `1/250`.
+    Rational(i64, i64),
+    List(Vec<ExifValue>),
+    Binary(Vec<u8>),
+    Bool(bool),
+    /// Fields of a JSON object, in the order exiftool wrote them.
+    Map(Vec<(String, ExifValue)>),
+    /// A JSON `null`, which tells the tag is there without a value.
+    Null,
+}
+
+impl ExifValue {
+    /// Reads a string value, turning `1/250` into a [`ExifValue::Rational`].
+    pub fn parse(value: &str) -> Self {
+        static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(-?[0-9]+)/([0-9]+)$").unwrap());
+
+        let value = value.trim();
+
+        if let Some(captures) = RE.captures(value) {
+            if let (Ok(n), Ok(d)) = (captures[1].parse(), captures[2].parse()) {
+                return ExifValue::Rational(n, d);
+            }
+        }
+
+        ExifValue::Str(value.to_string())
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ExifValue::Str(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Value as an integer, floats & rationals only when they are whole and
+    /// strings only when they hold nothing but the number.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            ExifValue::Int(v) => Some(v),
+            ExifValue::Float(v) if v.fract() == 0.0 => Some(v as i64),
+            ExifValue::Rational(n, d) if d != 0 && n % d == 0 => Some(n / d),
+            ExifValue::Str(ref v) => v.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Value as a float, strings only when they hold nothing but the number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            ExifValue::Int(v) => Some(v as f64),
+            ExifValue::Float(v) => Some(v),
+            ExifValue::Rational(n, d) if d != 0 => Some(n as f64 / d as f64),
+            ExifValue::Str(ref v) => v.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Every number of the value: the items of a list, the space separated
+    /// numbers of a string (like exiftool prints them with `#`) or the value
+    /// itself. `None` when any of them is not an integer.
+    pub fn numbers(&self) -> Option<Vec<i64>> {
+        let numbers: Vec<i64> = match self {
+            ExifValue::List(values) => values.iter().map(|v| v.as_i64()).collect::<Option<_>>()?,
+            ExifValue::Str(v) => v
+                .split_whitespace()
+                .map(|v| v.parse().ok())
+                .collect::<Option<_>>()?,
+            value => vec![value.as_i64()?],
+        };
+
+        if numbers.is_empty() {
+            return None;
+        }
+
+        Some(numbers)
+    }
+}
+
+impl Default for ExifValue {
+    fn default() -> Self {
+        ExifValue::Str(String::new())
+    }
+}
+
+impl fmt::Display for ExifValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExifValue::Str(v) => write!(f, "{}", v),
+            ExifValue::Int(v) => write!(f, "{}", v),
+            ExifValue::Float(v) => write!(f, "{}", v),
+            ExifValue::Rational(n, d) => write!(f, "{}/{}", n, d),
+            ExifValue::List(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+
+                Ok(())
+            }
+            ExifValue::Binary(data) => write!(f, "(Binary data {} bytes)", data.len()),
+            ExifValue::Bool(v) => write!(f, "{}", v),
+            ExifValue::Map(fields) => {
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+
+                Ok(())
+            }
+            ExifValue::Null => Ok(()),
+        }
+    }
+}
+
+impl From<&str> for ExifValue {
+    fn from(value: &str) -> Self {
+        ExifValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for ExifValue {
+    fn from(value: String) -> Self {
+        ExifValue::Str(value)
+    }
+}
+
+impl From<i64> for ExifValue {
+    fn from(value: i64) -> Self {
+        ExifValue::Int(value)
+    }
+}
+
+impl From<f64> for ExifValue {
+    fn from(value: f64) -> Self {
+        ExifValue::Float(value)
+    }
+}
+
+impl From<bool> for ExifValue {
+    fn from(value: bool) -> Self {
+        ExifValue::Bool(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_rationals() {
+        assert_eq!(ExifValue::parse("1/250"), ExifValue::Rational(1, 250));
+        assert_eq!(
+            ExifValue::parse(" f/2.0 "),
+            ExifValue::Str("f/2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn it_converts_whole_numbers_only() {
+        assert_eq!(ExifValue::Float(400.0).as_i64(), Some(400));
+        assert_eq!(ExifValue::Float(5.6).as_i64(), None);
+        assert_eq!(ExifValue::Rational(10, 5).as_i64(), Some(2));
+        assert_eq!(ExifValue::Rational(1, 250).as_f64(), Some(0.004));
+        assert_eq!(ExifValue::from("+2 (hard)").as_i64(), None);
+    }
+
+    #[test]
+    fn it_reads_numbers_of_lists_and_strings() {
+        let list = ExifValue::List(vec![ExifValue::Int(20), ExifValue::Int(-40)]);
+
+        assert_eq!(list.numbers(), Some(vec![20, -40]));
+        assert_eq!(ExifValue::from("20 -40").numbers(), Some(vec![20, -40]));
+        assert_eq!(ExifValue::from("Red +1").numbers(), None);
+        assert_eq!(ExifValue::from("").numbers(), None);
+    }
+
+    #[test]
+    fn it_displays_lists_without_leading_separator() {
+        let list = ExifValue::List(vec![ExifValue::from("A"), ExifValue::Int(1)]);
+
+        assert_eq!(list.to_string(), "A, 1");
+    }
+}
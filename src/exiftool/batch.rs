@@ -0,0 +1,360 @@
+use super::diagnostic::split_stdout;
+use super::spawn::{
+    json_kind, parse_entry, read_args, Error, ExiftoolWarningSnafu, InvalidJsonSnafu,
+    EXTRACT_EMBEDDED,
+};
+use crate::exif::ExifData;
+use crate::utils::extension::{is_valid_extension, is_video_extension};
+use log::{debug, warn};
+use snafu::prelude::*;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub type BatchResult = Vec<(PathBuf, Result<Vec<ExifData>, Error>)>;
+
+/// Splits the paths that can be sent to exiftool from the ones with an
+/// invalid extension, which already get their error.
+pub(crate) fn split_paths<P>(img_paths: &[P]) -> (Vec<PathBuf>, BatchResult)
+where
+    P: AsRef<Path>,
+{
+    let (valid, invalid): (Vec<PathBuf>, Vec<PathBuf>) = img_paths
+        .iter()
+        .map(|p| p.as_ref().to_path_buf())
+        .partition(|p| is_valid_extension(p));
+
+    let invalid = invalid
+        .into_iter()
+        .map(|path| {
+            let error = Error::Path {
+                path: path.to_string_lossy().to_string(),
+            };
+
+            (path, Err(error))
+        })
+        .collect();
+
+    (valid, invalid)
+}
+
+/// Splits `paths` into the requests sent to exiftool: images and movies are
+/// read apart, as only movies are read with `-ee`, see [`batch_args`].
+pub(crate) fn requests(paths: Vec<PathBuf>) -> Vec<Vec<PathBuf>> {
+    let (videos, images): (Vec<PathBuf>, Vec<PathBuf>) =
+        paths.into_iter().partition(|p| is_video_extension(p));
+
+    [images, videos]
+        .into_iter()
+        .filter(|paths| !paths.is_empty())
+        .collect()
+}
+
+/// Arguments to read every file in `paths` with one exiftool run. Movies
+/// need `-ee` for their recipe, so `paths` are either all images or all
+/// movies, see [`requests`].
+pub(crate) fn batch_args(paths: &[PathBuf]) -> Vec<String> {
+    let mut args = read_args();
+    if paths.iter().any(|p| is_video_extension(p)) {
+        args.push(EXTRACT_EMBEDDED.to_string());
+    }
+    args.extend(paths.iter().map(|p| p.to_string_lossy().to_string()));
+
+    args
+}
+
+/// Sorts `results` in the order in which the paths were given.
+pub(crate) fn in_order<P>(img_paths: &[P], mut results: BatchResult) -> BatchResult
+where
+    P: AsRef<Path>,
+{
+    let mut ordered: BatchResult = Vec::with_capacity(results.len());
+
+    for path in img_paths {
+        if let Some(i) = results.iter().position(|(p, _)| p == path.as_ref()) {
+            ordered.push(results.swap_remove(i));
+        }
+    }
+
+    ordered
+}
+
+/// Splits the output of a single exiftool run over many files back into one
+/// result per file, matching each JSON entry by its `SourceFile`.
+///
+/// Files exiftool did not return an entry for get the `stderr` lines that
+/// mention them, so one unreadable image does not fail the others.
+pub(crate) fn split_output(paths: &[PathBuf], stdout: &str, stderr: &str) -> BatchResult {
+    let (printed, json) = split_stdout(stdout);
+    for diagnostic in &printed {
+        warn!("exiftool: {}", diagnostic);
+    }
+
+    let entries: Vec<serde_json::Value> = if json.trim().is_empty() {
+        if !printed.is_empty() {
+            let message: Vec<String> = printed.iter().map(|d| d.to_string()).collect();
+            let error = ExiftoolWarningSnafu {
+                message: message.join("\n"),
+            }
+            .build();
+
+            return fail_all(paths, error);
+        }
+
+        vec![]
+    } else {
+        match serde_json::from_str::<serde_json::Value>(json).context(InvalidJsonSnafu) {
+            Ok(serde_json::Value::Array(entries)) => entries,
+            Ok(json) => {
+                let error = Error::NonObjectEntry {
+                    found: json_kind(&json),
+                };
+
+                return fail_all(paths, error);
+            }
+            Err(error) => return fail_all(paths, error),
+        }
+    };
+
+    paths
+        .iter()
+        .map(|path| {
+            let source_file = normalize(path);
+
+            let entry = entries.iter().find(|entry| {
+                entry
+                    .get("SourceFile")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|v| normalize(Path::new(v)) == source_file)
+            });
+
+            let result = match entry {
+                Some(entry) => parse_entry(entry),
+                None => {
+                    debug!("No metadata returned for {:?}", path);
+
+                    Err(missing(&source_file, stderr))
+                }
+            };
+
+            (path.clone(), result)
+        })
+        .collect()
+}
+
+/// Gives every file in `paths` the error of the exiftool run that read them.
+/// Timeouts and cancellations are reported as such for each file.
+pub(crate) fn fail_with(paths: &[PathBuf], error: Error) -> BatchResult {
+    match error {
+        Error::Timeout { elapsed, .. } => paths
+            .iter()
+            .map(|path| {
+                let error = Error::Timeout {
+                    path: path.to_string_lossy().to_string(),
+                    elapsed,
+                };
+
+                (path.clone(), Err(error))
+            })
+            .collect(),
+        Error::Cancelled { .. } => paths
+            .iter()
+            .map(|path| {
+                let error = Error::Cancelled {
+                    path: path.to_string_lossy().to_string(),
+                };
+
+                (path.clone(), Err(error))
+            })
+            .collect(),
+        e => fail_all(paths, e),
+    }
+}
+
+/// Gives every file in `paths` an [`Error::Batch`] sharing `error`.
+fn fail_all(paths: &[PathBuf], error: Error) -> BatchResult {
+    let error = Arc::new(error);
+
+    paths
+        .iter()
+        .map(|path| {
+            let error = Error::Batch {
+                source: Arc::clone(&error),
+            };
+
+            (path.clone(), Err(error))
+        })
+        .collect()
+}
+
+fn missing(source_file: &str, stderr: &str) -> Error {
+    let lines: Vec<&str> = stderr
+        .lines()
+        .filter(|line| mentions(&line.replace('\\', "/"), source_file))
+        .collect();
+
+    if lines.is_empty() {
+        Error::MissingResult {
+            path: source_file.to_string(),
+        }
+    } else {
+        Error::Stderr {
+            stderr: lines.join("\n"),
+        }
+    }
+}
+
+/// Whether `line` has `path` as a whole word, so `one.jpg` is not found in a
+/// line about `zone.jpg`.
+fn mentions(line: &str, path: &str) -> bool {
+    line.match_indices(path).any(|(start, _)| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + path.len()..].chars().next();
+
+        before.is_none_or(char::is_whitespace) && after.is_none_or(char::is_whitespace)
+    })
+}
+
+/// exiftool always prints paths with forward slashes.
+fn normalize(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::FindExifData;
+
+    #[test]
+    fn it_reads_images_apart_from_movies() {
+        let paths = vec![
+            PathBuf::from("one.jpg"),
+            PathBuf::from("DSCF0001.MOV"),
+            PathBuf::from("two.raf"),
+        ];
+
+        let requests = requests(paths);
+
+        assert_eq!(
+            requests,
+            vec![
+                vec![PathBuf::from("one.jpg"), PathBuf::from("two.raf")],
+                vec![PathBuf::from("DSCF0001.MOV")],
+            ]
+        );
+        assert!(!batch_args(&requests[0]).contains(&EXTRACT_EMBEDDED.to_string()));
+        assert!(batch_args(&requests[1]).contains(&EXTRACT_EMBEDDED.to_string()));
+    }
+
+    #[test]
+    fn it_splits_results_per_source_file() {
+        let paths = vec![PathBuf::from("a/one.jpg"), PathBuf::from("b/two.raf")];
+        let stdout = r#"[
+            {"SourceFile": "b/two.raf", "FilmMode": "Classic Chrome"},
+            {"SourceFile": "a/one.jpg", "FilmMode": "Eterna"}
+        ]"#;
+
+        let result = split_output(&paths, stdout, "");
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, PathBuf::from("a/one.jpg"));
+
+        let one = result[0].1.as_ref().unwrap();
+        assert_eq!(
+            one.as_slice().find("FilmMode"),
+            Some(ExifData::new("FilmMode", "Eterna"))
+        );
+
+        let two = result[1].1.as_ref().unwrap();
+        assert_eq!(
+            two.as_slice().find("FilmMode"),
+            Some(ExifData::new("FilmMode", "Classic Chrome"))
+        );
+    }
+
+    #[test]
+    fn it_reports_errors_per_file() {
+        let paths = vec![PathBuf::from("one.jpg"), PathBuf::from("broken.jpg")];
+        let stdout = r#"[{"SourceFile": "one.jpg", "FilmMode": "Eterna"}]"#;
+        let stderr = "Error: File format error - broken.jpg\n";
+
+        let result = split_output(&paths, stdout, stderr);
+
+        assert!(result[0].1.is_ok());
+        assert!(matches!(
+            &result[1].1,
+            Err(Error::Stderr { stderr }) if stderr == "Error: File format error - broken.jpg"
+        ));
+    }
+
+    #[test]
+    fn it_matches_stderr_lines_by_the_whole_path() {
+        let paths = vec![PathBuf::from("one.jpg"), PathBuf::from("zone.jpg")];
+        let stderr = "Error: File not found - zone.jpg\n";
+
+        let result = split_output(&paths, "", stderr);
+
+        assert!(matches!(&result[0].1, Err(Error::MissingResult { .. })));
+        assert!(matches!(&result[1].1, Err(Error::Stderr { .. })));
+    }
+
+    #[test]
+    fn it_reports_files_without_results() {
+        let paths = vec![PathBuf::from("one.jpg")];
+
+        let result = split_output(&paths, "", "");
+
+        assert!(matches!(&result[0].1, Err(Error::MissingResult { .. })));
+    }
+
+    #[test]
+    fn it_fails_every_file_with_invalid_json() {
+        let paths = vec![PathBuf::from("one.jpg"), PathBuf::from("two.jpg")];
+
+        let result = split_output(&paths, r#"[{"SourceFile": "one.jpg""#, "");
+
+        assert!(result.iter().all(|(_, r)| matches!(
+            r,
+            Err(Error::Batch { source }) if matches!(**source, Error::InvalidJson { .. })
+        )));
+    }
+
+    #[test]
+    fn it_keeps_the_error_of_the_run() {
+        let paths = vec![PathBuf::from("one.jpg"), PathBuf::from("two.jpg")];
+
+        let result = fail_with(&paths, Error::NotFound { tried: vec![] });
+
+        assert!(result.iter().all(|(_, r)| matches!(
+            r,
+            Err(Error::Batch { source }) if matches!(**source, Error::NotFound { .. })
+        )));
+    }
+
+    #[test]
+    fn it_reads_json_after_a_warning() {
+        let paths = vec![PathBuf::from("one.jpg"), PathBuf::from("broken.jpg")];
+        let stdout = "Warning: Bad MakerNotes offset\n".to_string()
+            + r#"[
+                {"SourceFile": "one.jpg", "FilmMode": "Eterna"},
+                {"SourceFile": "broken.jpg", "Error": "File format error"}
+            ]"#;
+
+        let result = split_output(&paths, &stdout, "");
+
+        assert!(result[0].1.is_ok());
+        assert!(matches!(
+            &result[1].1,
+            Err(Error::ExiftoolWarning { message }) if message == "Error: File format error"
+        ));
+    }
+
+    #[test]
+    fn it_matches_windows_paths() {
+        let paths = vec![PathBuf::from("C:\\img\\one.jpg")];
+        let stdout = r#"[{"SourceFile": "C:/img/one.jpg", "FilmMode": "Eterna"}]"#;
+
+        let result = split_output(&paths, stdout, "");
+
+        assert!(result[0].1.is_ok());
+    }
+}
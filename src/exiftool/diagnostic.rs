@@ -0,0 +1,187 @@
+use crate::exif::{ExifData, ExifValue};
+use std::fmt;
+
+/// Group of the `Warning` & `Error` tags written by exiftool with `-G1`.
+pub const GROUP: &str = "ExifTool";
+
+const WARNING: &str = "Warning";
+const ERROR: &str = "Error";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "{}", WARNING),
+            Severity::Error => write!(f, "{}", ERROR),
+        }
+    }
+}
+
+/// A warning or error exiftool reported while reading a file. They are kept
+/// next to the metadata as `ExifTool:Warning` & `ExifTool:Error` tags, see
+/// [`diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+impl Diagnostic {
+    /// Parses a line exiftool printed, e.g. `Warning: Bad MakerNotes offset`.
+    /// Lines without a `Warning:` or `Error:` prefix are kept as warnings.
+    pub(crate) fn from_line(line: &str) -> Self {
+        let line = line.trim();
+
+        let (severity, message) = if let Some(message) = line.strip_prefix("Error:") {
+            (Severity::Error, message)
+        } else if let Some(message) = line.strip_prefix("Warning:") {
+            (Severity::Warning, message)
+        } else {
+            (Severity::Warning, line)
+        };
+
+        Diagnostic {
+            severity,
+            message: message.trim().to_string(),
+        }
+    }
+
+    /// The diagnostic as a tag, as exiftool writes it in its JSON output.
+    pub(crate) fn to_exif(&self) -> ExifData {
+        let tag = match self.severity {
+            Severity::Warning => WARNING,
+            Severity::Error => ERROR,
+        };
+
+        ExifData::grouped(GROUP, tag, self.message.as_str().into())
+    }
+}
+
+/// Warnings & errors exiftool reported for the file `fields` were read from.
+/// The `-a` option makes exiftool list every one of them, not only the first.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exif::ExifData;
+/// use fuji::exiftool::diagnostic::{diagnostics, Severity};
+///
+/// let fields = vec![
+///     ExifData::new("FilmMode", "Classic Chrome"),
+///     ExifData::grouped("ExifTool", "Warning", "Bad MakerNotes offset".into()),
+/// ];
+///
+/// let diagnostics = diagnostics(&fields);
+/// assert_eq!(diagnostics[0].severity, Severity::Warning);
+/// assert_eq!(diagnostics[0].message, "Bad MakerNotes offset");
+/// ```
+pub fn diagnostics(fields: &[ExifData]) -> Vec<Diagnostic> {
+    fields
+        .iter()
+        .filter(|field| field.group().is_none_or(|group| group == GROUP))
+        .filter_map(|field| {
+            let severity = match field.tag() {
+                WARNING => Severity::Warning,
+                ERROR => Severity::Error,
+                _ => return None,
+            };
+
+            let messages = match field.value() {
+                ExifValue::List(values) => values.iter().map(|v| v.to_string()).collect(),
+                value => vec![value.to_string()],
+            };
+
+            Some(
+                messages
+                    .into_iter()
+                    .map(move |message| Diagnostic { severity, message }),
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+/// Splits the lines exiftool printed before its JSON output, when a warning
+/// ends up on stdout, from the JSON itself.
+pub(crate) fn split_stdout(stdout: &str) -> (Vec<Diagnostic>, &str) {
+    let mut diagnostics = vec![];
+    let mut rest = stdout;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() || rest.starts_with(['[', '{']) {
+            break;
+        }
+
+        let (line, next) = rest.split_once('\n').unwrap_or((rest, ""));
+        diagnostics.push(Diagnostic::from_line(line));
+        rest = next;
+    }
+
+    (diagnostics, rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reads_every_warning() {
+        let fields = vec![
+            ExifData::grouped(
+                GROUP,
+                WARNING,
+                ExifValue::List(vec!["[minor] One".into(), "Two".into()]),
+            ),
+            ExifData::grouped(GROUP, ERROR, "File format error".into()),
+            ExifData::grouped("FujiFilm", WARNING, "Not exiftool's".into()),
+        ];
+
+        assert_eq!(
+            diagnostics(&fields)
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "Warning: [minor] One",
+                "Warning: Two",
+                "Error: File format error"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_splits_warnings_from_json() {
+        let stdout = "Warning: Bad MakerNotes offset\n\n[{\"SourceFile\": \"a.jpg\"}]\n";
+
+        let (diagnostics, json) = split_stdout(stdout);
+
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                severity: Severity::Warning,
+                message: "Bad MakerNotes offset".to_string()
+            }]
+        );
+        assert_eq!(json, "[{\"SourceFile\": \"a.jpg\"}]\n");
+    }
+
+    #[test]
+    fn it_keeps_output_without_json() {
+        let (diagnostics, json) = split_stdout("Error: File not found - a.jpg");
+
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(json, "");
+    }
+}
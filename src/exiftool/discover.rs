@@ -0,0 +1,306 @@
+use super::spawn::{Error, NotFoundSnafu};
+use log::debug;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::Read;
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+#[cfg(target_os = "windows")]
+use winapi::um::winbase::CREATE_NO_WINDOW;
+
+/// Environment variable pointing to the exiftool to use, either the script or
+/// executable itself, or a directory with the `deps/exiftool` layout.
+pub const ENV_VAR: &str = "FUJI_EXIFTOOL";
+
+#[cfg(target_os = "windows")]
+const DEPS_NAMES: &[&str] = &["exiftool(-k).exe", "exiftool.exe"];
+#[cfg(not(target_os = "windows"))]
+const DEPS_NAMES: &[&str] = &["exiftool"];
+
+#[cfg(target_os = "windows")]
+const PATH_NAMES: &[&str] = &["exiftool.exe", "exiftool(-k).exe"];
+#[cfg(not(target_os = "windows"))]
+const PATH_NAMES: &[&str] = &["exiftool"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExiftoolKind {
+    /// The Perl distribution of exiftool, run with `perl`.
+    PerlScript,
+    /// A standalone executable (or a script the OS can run on its own).
+    Executable,
+}
+
+/// An exiftool found on disk, and how to run it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exiftool {
+    pub path: PathBuf,
+    pub kind: ExiftoolKind,
+}
+
+impl Exiftool {
+    pub fn new(path: PathBuf) -> Self {
+        let kind = detect_kind(&path);
+
+        Exiftool { path, kind }
+    }
+
+    /// Command running this exiftool, arguments can be appended to it.
+    pub fn command(&self) -> Command {
+        let mut cmd = match self.kind {
+            ExiftoolKind::PerlScript => Command::new("perl"),
+            ExiftoolKind::Executable => Command::new(&self.path),
+        };
+
+        if self.kind == ExiftoolKind::PerlScript {
+            cmd.arg(&self.path);
+        }
+
+        #[cfg(target_os = "windows")]
+        cmd.creation_flags(CREATE_NO_WINDOW);
+
+        cmd
+    }
+}
+
+/// Finds exiftool at `exiftool_path`, either exiftool itself or a directory
+/// containing `deps/exiftool`, and fails if it is not there. Without a path,
+/// tries in order:
+///
+/// 1. The [`ENV_VAR`] environment variable, with the same rules.
+/// 2. `deps/exiftool` in the current directory, then next to the running
+///    executable.
+/// 3. `exiftool` on the `PATH`.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::discover;
+///
+/// match discover::find_exiftool(None) {
+///     Ok(exiftool) => println!("Using {:?}", exiftool.path),
+///     Err(e) => println!("{}", e),
+/// }
+/// ```
+pub fn find_exiftool(exiftool_path: Option<&Path>) -> Result<Exiftool, Error> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|p| p.to_path_buf()));
+
+    let candidates = candidates(
+        exiftool_path,
+        std::env::var_os(ENV_VAR),
+        std::env::current_dir().ok(),
+        exe_dir,
+        std::env::var_os("PATH"),
+    );
+
+    find_in(candidates)
+}
+
+fn find_in(candidates: Vec<PathBuf>) -> Result<Exiftool, Error> {
+    let mut tried = vec![];
+
+    for candidate in candidates {
+        if candidate.is_file() {
+            let exiftool = Exiftool::new(candidate);
+            debug!("Found exiftool: {:?}", exiftool);
+
+            return Ok(exiftool);
+        }
+
+        if !tried.contains(&candidate) {
+            tried.push(candidate);
+        }
+    }
+
+    NotFoundSnafu { tried }.fail()
+}
+
+fn candidates(
+    explicit: Option<&Path>,
+    env: Option<OsString>,
+    current_dir: Option<PathBuf>,
+    exe_dir: Option<PathBuf>,
+    path_var: Option<OsString>,
+) -> Vec<PathBuf> {
+    if let Some(path) = explicit {
+        return configured(path);
+    }
+
+    let mut candidates = vec![];
+
+    if let Some(env) = env.filter(|v| !v.is_empty()) {
+        candidates.extend(configured(Path::new(&env)));
+    }
+
+    for dir in current_dir.into_iter().chain(exe_dir) {
+        candidates.extend(deps_layout(&dir));
+    }
+
+    if let Some(path_var) = path_var {
+        for dir in std::env::split_paths(&path_var) {
+            candidates.extend(PATH_NAMES.iter().map(|name| dir.join(name)));
+        }
+    }
+
+    candidates
+}
+
+fn configured(path: &Path) -> Vec<PathBuf> {
+    if path.is_dir() {
+        deps_layout(path)
+    } else {
+        vec![path.to_path_buf()]
+    }
+}
+
+fn deps_layout(root: &Path) -> Vec<PathBuf> {
+    DEPS_NAMES
+        .iter()
+        .map(|name| root.join("deps").join("exiftool").join(name))
+        .collect()
+}
+
+/// The Perl distribution ships as `exiftool` (or `exiftool.pl`) starting with
+/// a `perl` shebang, anything else is run directly.
+fn detect_kind(path: &Path) -> ExiftoolKind {
+    if path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("pl"))
+    {
+        return ExiftoolKind::PerlScript;
+    }
+
+    let mut head = [0u8; 128];
+    let read = File::open(path)
+        .and_then(|mut f| f.read(&mut head))
+        .unwrap_or(0);
+
+    let first_line = head[..read].split(|b| *b == b'\n').next().unwrap_or(&[]);
+
+    if first_line.starts_with(b"#!") && String::from_utf8_lossy(first_line).contains("perl") {
+        ExiftoolKind::PerlScript
+    } else {
+        ExiftoolKind::Executable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("fuji-discover-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn write(path: &Path, content: &[u8]) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn it_detects_perl_scripts() {
+        let dir = temp_dir("kind");
+        let script = dir.join("exiftool");
+        let binary = dir.join("exiftool-bin");
+        write(&script, b"#!/usr/bin/perl -w\nuse strict;\n");
+        write(&binary, b"\x7fELF\x02\x01\x01");
+
+        assert_eq!(detect_kind(&script), ExiftoolKind::PerlScript);
+        assert_eq!(detect_kind(&binary), ExiftoolKind::Executable);
+        assert_eq!(
+            detect_kind(&dir.join("exiftool.pl")),
+            ExiftoolKind::PerlScript
+        );
+    }
+
+    #[test]
+    fn it_follows_the_discovery_order() {
+        let dir = temp_dir("order");
+        let env = dir.join("env").join("exiftool");
+        let deps = deps_layout(&dir.join("cwd"))[0].clone();
+        let on_path = dir.join("bin").join(PATH_NAMES[0]);
+        write(&env, b"#!/usr/bin/perl\n");
+        write(&deps, b"#!/usr/bin/perl\n");
+        write(&on_path, b"binary");
+
+        let find = |env: Option<&Path>, cwd: Option<&Path>| {
+            find_in(candidates(
+                None,
+                env.map(|p| p.as_os_str().to_os_string()),
+                cwd.map(|p| p.to_path_buf()),
+                None,
+                Some(dir.join("bin").into_os_string()),
+            ))
+            .unwrap()
+            .path
+        };
+
+        assert_eq!(find(Some(&env), Some(&dir.join("cwd"))), env);
+        assert_eq!(find(None, Some(&dir.join("cwd"))), deps);
+        assert_eq!(find(None, None), on_path);
+    }
+
+    #[test]
+    fn it_accepts_a_directory_with_the_deps_layout() {
+        let dir = temp_dir("explicit");
+        let deps = deps_layout(&dir)[0].clone();
+        write(&deps, b"#!/usr/bin/perl\n");
+
+        let exiftool = find_in(candidates(Some(&dir), None, None, None, None)).unwrap();
+
+        assert_eq!(exiftool.path, deps);
+        assert_eq!(exiftool.kind, ExiftoolKind::PerlScript);
+    }
+
+    #[test]
+    fn it_does_not_fall_back_from_an_explicit_path() {
+        let dir = temp_dir("fallback");
+        let explicit = dir.join("nope").join("exiftool");
+        let on_path = dir.join("bin").join(PATH_NAMES[0]);
+        write(&on_path, b"binary");
+
+        let result = find_in(candidates(
+            Some(&explicit),
+            None,
+            None,
+            None,
+            Some(dir.join("bin").into_os_string()),
+        ));
+
+        let Err(Error::NotFound { tried }) = result else {
+            panic!("exiftool should not be found");
+        };
+
+        assert_eq!(tried, vec![explicit]);
+    }
+
+    #[test]
+    fn it_lists_every_location_tried() {
+        let dir = temp_dir("missing");
+        let env = dir.join("nope").join("exiftool");
+
+        let result = find_in(candidates(
+            None,
+            Some(env.clone().into_os_string()),
+            Some(dir.clone()),
+            None,
+            Some(dir.join("bin").into_os_string()),
+        ));
+
+        let Err(Error::NotFound { tried }) = result else {
+            panic!("exiftool should not be found");
+        };
+
+        assert_eq!(tried[0], env);
+        assert!(tried.contains(&deps_layout(&dir)[0]));
+        assert!(tried.contains(&dir.join("bin").join(PATH_NAMES[0])));
+    }
+}
@@ -1 +1,8 @@
+pub mod batch;
+pub mod diagnostic;
+pub mod discover;
+pub mod options;
+pub mod session;
 pub mod spawn;
+#[cfg(feature = "tokio")]
+pub mod tokio;
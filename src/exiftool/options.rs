@@ -0,0 +1,168 @@
+use super::spawn::Error;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How exiftool is found and how long it may run.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::options::{CancelHandle, ExiftoolOptions};
+/// use std::time::Duration;
+///
+/// let cancel = CancelHandle::new();
+/// let options = ExiftoolOptions::new()
+///     .timeout(Duration::from_secs(10))
+///     .cancel_handle(cancel.clone());
+///
+/// // From another thread, stops the running request
+/// cancel.cancel();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ExiftoolOptions {
+    /// See [`super::discover::find_exiftool`].
+    pub exiftool_path: Option<PathBuf>,
+    /// Time a single request may take, exiftool is killed after it.
+    pub timeout: Option<Duration>,
+    pub cancel: Option<CancelHandle>,
+}
+
+impl ExiftoolOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn exiftool_path(mut self, exiftool_path: Option<&Path>) -> Self {
+        self.exiftool_path = exiftool_path.map(|p| p.to_path_buf());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn cancel_handle(mut self, cancel: CancelHandle) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub(crate) fn deadline(&self) -> Deadline {
+        Deadline {
+            started: Instant::now(),
+            timeout: self.timeout,
+            cancel: self.cancel.clone(),
+        }
+    }
+}
+
+/// Cancels the running and any later exiftool request it was given to. Clones
+/// share the same state, so it can be cancelled from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Why waiting on exiftool stopped early.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Interrupt {
+    TimedOut(Duration),
+    Cancelled,
+}
+
+impl Interrupt {
+    pub(crate) fn into_error(self, path: &str) -> Error {
+        let path = path.to_string();
+
+        match self {
+            Interrupt::TimedOut(elapsed) => Error::Timeout { path, elapsed },
+            Interrupt::Cancelled => Error::Cancelled { path },
+        }
+    }
+}
+
+/// Limits of a single request.
+#[derive(Debug, Clone)]
+pub(crate) struct Deadline {
+    started: Instant,
+    timeout: Option<Duration>,
+    cancel: Option<CancelHandle>,
+}
+
+impl Deadline {
+    /// How often the cancel handle is checked while waiting.
+    const POLL: Duration = Duration::from_millis(50);
+
+    pub(crate) fn check(&self) -> Result<(), Interrupt> {
+        if self.cancel.as_ref().is_some_and(|c| c.is_cancelled()) {
+            return Err(Interrupt::Cancelled);
+        }
+
+        let elapsed = self.started.elapsed();
+        match self.timeout {
+            Some(timeout) if elapsed >= timeout => Err(Interrupt::TimedOut(elapsed)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Time to wait before calling [`Deadline::check`] again, `None` when
+    /// there is no limit at all.
+    pub(crate) fn next_wait(&self) -> Option<Duration> {
+        let remaining = self
+            .timeout
+            .map(|t| t.saturating_sub(self.started.elapsed()));
+
+        match (remaining, &self.cancel) {
+            (Some(remaining), Some(_)) => Some(remaining.min(Self::POLL)),
+            (Some(remaining), None) => Some(remaining),
+            (None, Some(_)) => Some(Self::POLL),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_times_out() {
+        let deadline = ExiftoolOptions::new().timeout(Duration::ZERO).deadline();
+
+        assert!(matches!(deadline.check(), Err(Interrupt::TimedOut(_))));
+    }
+
+    #[test]
+    fn it_is_cancelled_from_a_clone() {
+        let cancel = CancelHandle::new();
+        let deadline = ExiftoolOptions::new()
+            .cancel_handle(cancel.clone())
+            .deadline();
+
+        assert_eq!(deadline.check(), Ok(()));
+        cancel.clone().cancel();
+        assert_eq!(deadline.check(), Err(Interrupt::Cancelled));
+    }
+
+    #[test]
+    fn it_waits_forever_without_limits() {
+        let deadline = ExiftoolOptions::new().deadline();
+
+        assert_eq!(deadline.check(), Ok(()));
+        assert_eq!(deadline.next_wait(), None);
+    }
+}
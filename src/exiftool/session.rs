@@ -0,0 +1,416 @@
+use super::batch::{
+    batch_args, fail_with, in_order, requests, split_output, split_paths, BatchResult,
+};
+use super::discover::{find_exiftool, Exiftool};
+#[cfg(test)]
+use super::options::CancelHandle;
+use super::options::{Deadline, ExiftoolOptions};
+use super::spawn::{file_args, parse_output, Error, ExiftoolSnafu, IoSnafu, SessionSnafu};
+use crate::exif::{
+    source::{self, MetadataSource},
+    ExifData,
+};
+use crate::utils::extension::is_valid_extension;
+use log::{debug, warn};
+use snafu::prelude::*;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// A long-lived exiftool process, started with `-stay_open True` so Perl and
+/// exiftool are only loaded once for any number of files.
+///
+/// The process is shut down when the session is dropped.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::session::ExiftoolSession;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut session = ExiftoolSession::new(None)?;
+///
+/// for path in ["tests/img/DSCF5230.JPG", "tests/img/DSCF5358.JPG"] {
+///     let metadata = session.read_metadata(&path)?;
+///     println!("Metadata: {:?}", metadata);
+/// }
+///
+/// # Ok(())
+/// # }
+/// ```
+pub struct ExiftoolSession {
+    exiftool: Exiftool,
+    options: ExiftoolOptions,
+    process: Option<Process>,
+    counter: u64,
+}
+
+impl ExiftoolSession {
+    pub fn new(exiftool_path: Option<&Path>) -> Result<Self, Error> {
+        Self::with_options(ExiftoolOptions::new().exiftool_path(exiftool_path))
+    }
+
+    /// Starts a session with a timeout and/or cancel handle. When a request
+    /// times out or is cancelled exiftool is killed, and started again on the
+    /// next request.
+    pub fn with_options(options: ExiftoolOptions) -> Result<Self, Error> {
+        let exiftool = find_exiftool(options.exiftool_path.as_deref())?;
+        debug!("Exiftool {:?}", exiftool);
+
+        let process = Process::spawn(&exiftool)?;
+
+        Ok(ExiftoolSession {
+            exiftool,
+            options,
+            process: Some(process),
+            counter: 0,
+        })
+    }
+
+    /// Reads Metadata from a JPEG, HEIF, RAF or MOV/MP4 File using the running
+    /// exiftool process.
+    pub fn read_metadata<P>(&mut self, img_path: &P) -> Result<Vec<ExifData>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        if !is_valid_extension(img_path.as_ref()) {
+            return Err(Error::Path {
+                path: img_path.as_ref().to_string_lossy().to_string(),
+            });
+        }
+
+        let path = img_path.as_ref().to_string_lossy();
+        let args = file_args(img_path.as_ref());
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+        let (stdout, stderr) = self.execute(&args, &path)?;
+
+        if stdout.trim().is_empty() {
+            return Err(Error::Stderr { stderr });
+        }
+
+        parse_output(&stdout)
+    }
+
+    /// Reads Metadata from many JPEG, HEIF, RAF or MOV/MP4 Files with a single
+    /// request, see [`super::spawn::read_metadata_batch`].
+    pub fn read_metadata_batch<P>(&mut self, img_paths: &[P]) -> BatchResult
+    where
+        P: AsRef<Path>,
+    {
+        let (valid, mut results) = split_paths(img_paths);
+
+        for valid in requests(valid) {
+            let args = batch_args(&valid);
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+            let files = args[args.len() - valid.len()..].join(", ");
+
+            let batch = match self.execute(&args, &files) {
+                Ok((stdout, stderr)) => split_output(&valid, &stdout, &stderr),
+                Err(e) => fail_with(&valid, e),
+            };
+
+            results.extend(batch);
+        }
+
+        in_order(img_paths, results)
+    }
+
+    /// Sends one request (a list of arguments) and waits for its response.
+    /// Returns what exiftool wrote to stdout and stderr for this request.
+    ///
+    /// `path` is only used to report timeouts and cancellations.
+    pub(crate) fn execute(&mut self, args: &[&str], path: &str) -> Result<(String, String), Error> {
+        let deadline = self.options.deadline();
+        deadline.check().map_err(|i| i.into_error(path))?;
+
+        self.counter += 1;
+        let id = self.counter;
+
+        let mut process = match self.process.take() {
+            Some(process) => process,
+            None => Process::spawn(&self.exiftool)?,
+        };
+
+        match process.execute(&request(args, id), &ready_marker(id), &deadline, path) {
+            Ok(output) => {
+                self.process = Some(process);
+
+                Ok(output)
+            }
+            Err(e) => {
+                warn!("Stopping exiftool: {}", e);
+                process.kill();
+
+                Err(e)
+            }
+        }
+    }
+}
+
+/// A running `-stay_open` exiftool. Its output is forwarded line by line
+/// from other threads, so waiting on it can be given up on.
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Receiver<String>,
+    stderr: Receiver<String>,
+}
+
+impl Process {
+    fn spawn(exiftool: &Exiftool) -> Result<Self, Error> {
+        let mut child = exiftool
+            .command()
+            .arg("-stay_open")
+            .arg("True")
+            .arg("-@")
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(ExiftoolSnafu)?;
+
+        let stdin = child.stdin.take().context(SessionSnafu {
+            reason: "stdin not available",
+        })?;
+        let stdout = child.stdout.take().context(SessionSnafu {
+            reason: "stdout not available",
+        })?;
+        let stderr = child.stderr.take().context(SessionSnafu {
+            reason: "stderr not available",
+        })?;
+
+        Ok(Process {
+            child,
+            stdin,
+            stdout: forward_lines(stdout),
+            stderr: forward_lines(stderr),
+        })
+    }
+
+    fn execute(
+        &mut self,
+        request: &str,
+        marker: &str,
+        deadline: &Deadline,
+        path: &str,
+    ) -> Result<(String, String), Error> {
+        self.stdin
+            .write_all(request.as_bytes())
+            .and_then(|_| self.stdin.flush())
+            .context(IoSnafu)?;
+
+        let stdout = match read_until_marker(&self.stdout, marker, deadline, path) {
+            Ok(stdout) => stdout,
+            Err(e @ Error::Session { .. }) => {
+                // The process is gone, whatever it printed explains why.
+                let mut stderr = String::new();
+                while let Ok(line) = self.stderr.recv_timeout(Duration::from_secs(1)) {
+                    stderr.push_str(&line);
+                }
+
+                if stderr.trim().is_empty() {
+                    return Err(e);
+                }
+
+                return Err(Error::Stderr { stderr });
+            }
+            Err(e) => return Err(e),
+        };
+        let stderr = read_until_marker(&self.stderr, marker, deadline, path)?;
+
+        Ok((stdout, stderr))
+    }
+
+    fn shutdown(&mut self) -> std::io::Result<()> {
+        self.stdin.write_all(SHUTDOWN)?;
+        self.stdin.flush()?;
+        self.child.wait()?;
+
+        Ok(())
+    }
+
+    fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl MetadataSource for ExiftoolSession {
+    fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, source::Error> {
+        ExiftoolSession::read_metadata(self, &img_path).context(source::ExiftoolSnafu)
+    }
+}
+
+impl Drop for ExiftoolSession {
+    fn drop(&mut self) {
+        if let Some(mut process) = self.process.take() {
+            if let Err(e) = process.shutdown() {
+                warn!("Failed to stop exiftool cleanly, killing it: {:?}", e);
+                process.kill();
+            }
+        }
+    }
+}
+
+/// Tells a `-stay_open` exiftool to exit.
+pub(crate) const SHUTDOWN: &[u8] = b"-stay_open\nFalse\n";
+
+/// Arguments of a request, one per line, followed by `-executeN`.
+pub(crate) fn request(args: &[&str], id: u64) -> String {
+    let mut request = String::new();
+    for arg in args {
+        request.push_str(arg);
+        request.push('\n');
+    }
+    // -echo4 writes the marker to stderr once the request is processed,
+    // so both streams can be read up to the end of this request.
+    request.push_str(&format!("-echo4\n{}\n-execute{}\n", ready_marker(id), id));
+
+    request
+}
+
+pub(crate) fn ready_marker(id: u64) -> String {
+    format!("{{ready{}}}", id)
+}
+
+/// Sends every line read from `reader` through a channel, which is closed
+/// once the reader ends.
+fn forward_lines<R>(reader: R) -> Receiver<String>
+where
+    R: Read + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut line = vec![];
+
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if sender
+                        .send(String::from_utf8_lossy(&line).to_string())
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+/// Receives lines until the `marker` line, returning everything before it.
+fn read_until_marker(
+    lines: &Receiver<String>,
+    marker: &str,
+    deadline: &Deadline,
+    path: &str,
+) -> Result<String, Error> {
+    let mut output = String::new();
+
+    loop {
+        deadline.check().map_err(|i| i.into_error(path))?;
+
+        let line = match deadline.next_wait() {
+            Some(wait) => match lines.recv_timeout(wait) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            },
+            None => match lines.recv() {
+                Ok(line) => line,
+                Err(_) => break,
+            },
+        };
+
+        if line.trim_end() == marker {
+            return Ok(output);
+        }
+
+        output.push_str(&line);
+    }
+
+    Err(Error::Session {
+        reason: "exiftool exited unexpectedly".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        for line in lines {
+            sender.send(format!("{}\n", line)).unwrap();
+        }
+
+        receiver
+    }
+
+    #[test]
+    fn it_reads_until_the_marker() {
+        let receiver = lines(&["[{\"Make\": \"FUJIFILM\"}]", "{ready1}", "[{}]", "{ready2}"]);
+        let deadline = ExiftoolOptions::new().deadline();
+
+        assert_eq!(
+            read_until_marker(&receiver, "{ready1}", &deadline, "a.jpg").unwrap(),
+            "[{\"Make\": \"FUJIFILM\"}]\n"
+        );
+        assert_eq!(
+            read_until_marker(&receiver, "{ready2}", &deadline, "a.jpg").unwrap(),
+            "[{}]\n"
+        );
+    }
+
+    #[test]
+    fn it_fails_when_output_ends_before_the_marker() {
+        let receiver = lines(&["[{\"Make\": \"FUJIFILM\"}]"]);
+        let deadline = ExiftoolOptions::new().deadline();
+
+        assert!(matches!(
+            read_until_marker(&receiver, "{ready1}", &deadline, "a.jpg"),
+            Err(Error::Session { .. })
+        ));
+    }
+
+    #[test]
+    fn it_times_out_waiting_for_the_marker() {
+        let (_sender, receiver) = mpsc::channel::<String>();
+        let deadline = ExiftoolOptions::new()
+            .timeout(Duration::from_millis(20))
+            .deadline();
+
+        assert!(matches!(
+            read_until_marker(&receiver, "{ready1}", &deadline, "a.jpg"),
+            Err(Error::Timeout { path, .. }) if path == "a.jpg"
+        ));
+    }
+
+    #[test]
+    fn it_stops_waiting_when_cancelled() {
+        let (_sender, receiver) = mpsc::channel::<String>();
+        let cancel = CancelHandle::new();
+        let deadline = ExiftoolOptions::new()
+            .cancel_handle(cancel.clone())
+            .deadline();
+
+        let canceller = thread::spawn(move || cancel.cancel());
+
+        assert!(matches!(
+            read_until_marker(&receiver, "{ready1}", &deadline, "a.jpg"),
+            Err(Error::Cancelled { .. })
+        ));
+        canceller.join().unwrap();
+    }
+}
@@ -1,19 +1,29 @@
+use super::batch::{fail_with, BatchResult};
+use super::diagnostic::{diagnostics, split_stdout, Severity};
+use super::discover::find_exiftool;
+use super::options::{Deadline, ExiftoolOptions};
+use super::session::ExiftoolSession;
 use crate::exif::{
     json::{Error as JsonError, JsonValue},
-    ExifData,
+    numeric_tag,
+    source::{self, ExiftoolSnafu as SourceExiftoolSnafu, MetadataSource},
+    ExifData, NUMERIC_TAGS,
 };
-use crate::utils::extension::is_valid_extension;
-use log::debug;
+use crate::utils::extension::is_video_extension;
+use crate::utils::magic::{detect_file_type, FileType};
+use log::{debug, warn};
 use snafu::prelude::*;
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
-#[cfg(target_os = "windows")]
-use winapi::um::winbase::CREATE_NO_WINDOW;
+use std::process::{Child, ExitStatus, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
 
-/// Reads Metadata from a JPEG or RAF File. This spawns a new process that runs
-/// exiftool.
+pub(crate) const EXTRACT_EMBEDDED: &str = "-ee";
+
+/// Reads Metadata from a JPEG, HEIF, RAF or MOV/MP4 File. This spawns a new
+/// process that runs exiftool, use [`ExiftoolSession`] instead when reading
+/// many files.
 ///
 /// # Example
 ///
@@ -34,81 +44,373 @@ pub fn read_metadata<P>(img_path: &P, exiftool_path: Option<&Path>) -> Result<Ve
 where
     P: AsRef<Path>,
 {
-    if !is_valid_extension(img_path.as_ref()) {
-        return Err(Error::Path {
-            path: img_path.as_ref().to_string_lossy().to_string(),
+    read_metadata_with_options(
+        img_path,
+        &ExiftoolOptions::new().exiftool_path(exiftool_path),
+    )
+}
+
+/// Same as [`read_metadata`], killing exiftool when it takes longer than the
+/// timeout or is cancelled.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::{options::ExiftoolOptions, spawn};
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let options = ExiftoolOptions::new().timeout(Duration::from_secs(10));
+///
+/// let path = std::path::Path::new("tests/img/DSCF5230.JPG");
+/// let metadata = spawn::read_metadata_with_options(&path, &options)?;
+///
+/// println!("Metadata: {:?}", metadata);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_metadata_with_options<P>(
+    img_path: &P,
+    options: &ExiftoolOptions,
+) -> Result<Vec<ExifData>, Error>
+where
+    P: AsRef<Path>,
+{
+    let mut session = ExiftoolSession::with_options(options.clone())?;
+
+    session.read_metadata(img_path)
+}
+
+/// Reads Metadata from the bytes of a JPEG, HEIF, RAF or MOV/MP4 File, feeding
+/// them to exiftool through stdin. The file type is detected from its first
+/// bytes.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::spawn;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("tests/img/DSCF5230.JPG")?;
+/// let metadata = spawn::read_metadata_from_bytes(&data, None)?;
+///
+/// println!("Metadata: {:?}", metadata);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_metadata_from_bytes(
+    data: &[u8],
+    exiftool_path: Option<&Path>,
+) -> Result<Vec<ExifData>, Error> {
+    read_metadata_from_bytes_with_options(
+        data,
+        &ExiftoolOptions::new().exiftool_path(exiftool_path),
+    )
+}
+
+/// Same as [`read_metadata_from_bytes`], killing exiftool when it takes
+/// longer than the timeout or is cancelled.
+pub fn read_metadata_from_bytes_with_options(
+    data: &[u8],
+    options: &ExiftoolOptions,
+) -> Result<Vec<ExifData>, Error> {
+    let file_type = detect_file_type(data).context(UnsupportedDataSnafu)?;
+
+    let exiftool = find_exiftool(options.exiftool_path.as_deref())?;
+
+    let mut args = read_args();
+    if file_type == FileType::QuickTime {
+        args.push(EXTRACT_EMBEDDED.to_string());
+    }
+
+    let mut child = exiftool
+        .command()
+        .args(args)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(ExiftoolSnafu)?;
+
+    let mut stdin = child.stdin.take().context(SessionSnafu {
+        reason: "stdin not available",
+    })?;
+    let mut stdout = child.stdout.take().context(SessionSnafu {
+        reason: "stdout not available",
+    })?;
+    let mut stderr = child.stderr.take().context(SessionSnafu {
+        reason: "stderr not available",
+    })?;
+
+    let deadline = options.deadline();
+
+    // Every stream is handled on its own thread, exiftool may fill stdout
+    // before it is done reading stdin.
+    let (status, stdout, stderr) = std::thread::scope(|scope| {
+        scope.spawn(move || {
+            // exiftool stops reading once it has the metadata, so a broken
+            // pipe here is expected.
+            if let Err(e) = stdin.write_all(data) {
+                debug!("Stopped writing to exiftool: {:?}", e);
+            }
+        });
+        let stdout = scope.spawn(move || {
+            let mut output = vec![];
+            let _ = stdout.read_to_end(&mut output);
+
+            output
+        });
+        let stderr = scope.spawn(move || {
+            let mut output = vec![];
+            let _ = stderr.read_to_end(&mut output);
+
+            output
+        });
+
+        let status = wait_child(&mut child, &deadline, "-");
+
+        (
+            status,
+            stdout.join().unwrap_or_default(),
+            stderr.join().unwrap_or_default(),
+        )
+    });
+    status?;
+
+    let stdout = String::from_utf8_lossy(&stdout);
+
+    if stdout.trim().is_empty() {
+        return Err(Error::Stderr {
+            stderr: String::from_utf8_lossy(&stderr).to_string(),
         });
     }
 
-    let path = exiftool_exec_path(exiftool_path)?;
-    debug!("Exiftool Dir {:?}", path);
-    let mut cmd = spawn_exiftool(&path);
+    parse_output(&stdout)
+}
 
-    #[cfg(not(target_os = "windows"))]
-    let cmd = cmd.arg(path);
+/// Reads Metadata from a JPEG, HEIF, RAF or MOV/MP4 File, reading it to the end
+/// first, see [`read_metadata_from_bytes`].
+pub fn read_metadata_from_reader<R>(
+    mut reader: R,
+    exiftool_path: Option<&Path>,
+) -> Result<Vec<ExifData>, Error>
+where
+    R: Read,
+{
+    let mut data = vec![];
+    reader.read_to_end(&mut data).context(IoSnafu)?;
 
-    let output = cmd
-        .arg("-a")
-        .arg("-m")
-        .arg("-j")
-        .arg(img_path.as_ref())
-        .output()
-        .context(ExiftoolSnafu)?;
+    read_metadata_from_bytes(&data, exiftool_path)
+}
+
+/// [`MetadataSource`] that spawns a new exiftool process for every file.
+#[derive(Debug, Clone, Default)]
+pub struct ExiftoolSpawn {
+    options: ExiftoolOptions,
+}
+
+impl ExiftoolSpawn {
+    pub fn new(exiftool_path: Option<&Path>) -> Self {
+        Self::with_options(ExiftoolOptions::new().exiftool_path(exiftool_path))
+    }
+
+    pub fn with_options(options: ExiftoolOptions) -> Self {
+        ExiftoolSpawn { options }
+    }
+}
+
+impl MetadataSource for ExiftoolSpawn {
+    fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, source::Error> {
+        read_metadata_with_options(&img_path, &self.options).context(SourceExiftoolSnafu)
+    }
+}
+
+/// Reads Metadata from many JPEG, HEIF, RAF or MOV/MP4 Files in a single
+/// exiftool run.
+///
+/// Every file gets its own result, in the same order as `img_paths`. A file
+/// that cannot be read does not fail the rest of the batch.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::spawn;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let paths = ["tests/img/DSCF5230.JPG", "tests/img/DSCF5358.JPG"];
+///
+/// for (path, metadata) in spawn::read_metadata_batch(&paths, None) {
+///     println!("{:?}: {:?}", path, metadata?);
+/// }
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_metadata_batch<P>(img_paths: &[P], exiftool_path: Option<&Path>) -> BatchResult
+where
+    P: AsRef<Path>,
+{
+    read_metadata_batch_with_options(
+        img_paths,
+        &ExiftoolOptions::new().exiftool_path(exiftool_path),
+    )
+}
 
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
-        let json = json.as_array().unwrap().first().unwrap();
-        let value = JsonValue(json.clone());
-        let result: Vec<ExifData> = value.try_into().context(JsonSnafu)?;
+/// Same as [`read_metadata_batch`]. When the run times out or is cancelled,
+/// every file gets [`Error::Timeout`] or [`Error::Cancelled`].
+pub fn read_metadata_batch_with_options<P>(
+    img_paths: &[P],
+    options: &ExiftoolOptions,
+) -> BatchResult
+where
+    P: AsRef<Path>,
+{
+    match ExiftoolSession::with_options(options.clone()) {
+        Ok(mut session) => session.read_metadata_batch(img_paths),
+        Err(e) => {
+            let paths: Vec<PathBuf> = img_paths.iter().map(|p| p.as_ref().to_path_buf()).collect();
 
-        Ok(result)
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+            fail_with(&paths, e)
+        }
+    }
+}
+
+/// Waits for `child` to exit, killing it when the deadline is reached.
+fn wait_child(child: &mut Child, deadline: &Deadline, path: &str) -> Result<ExitStatus, Error> {
+    loop {
+        if let Some(status) = child.try_wait().context(IoSnafu)? {
+            return Ok(status);
+        }
+
+        if let Err(interrupt) = deadline.check() {
+            let error = interrupt.into_error(path);
+            warn!("Stopping exiftool: {}", error);
+
+            let _ = child.kill();
+            let _ = child.wait();
+
+            return Err(error);
+        }
 
-        Err(Error::Stderr {
-            stderr: stderr.to_string(),
-        })
+        let wait = deadline.next_wait().unwrap_or(Duration::MAX);
+        std::thread::sleep(wait.min(Duration::from_millis(10)));
     }
 }
 
-fn exiftool_exec_path(path: Option<&Path>) -> Result<PathBuf, Error> {
-    let root = if let Some(path) = path {
-        path.to_path_buf()
-    } else {
-        std::env::current_dir().context(CurrentDirSnafu)?
-    };
+/// Arguments reading every tag printed, named `Group:Tag` after their family
+/// 1 group, plus the raw numbers of [`NUMERIC_TAGS`] from the Fujifilm
+/// MakerNote as `FujiFilm:Tag#`.
+pub(crate) fn read_args() -> Vec<String> {
+    let mut args: Vec<String> = ["-a", "-m", "-j", "-G1", "-all"]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    args.extend(
+        NUMERIC_TAGS
+            .iter()
+            .map(|tag| format!("-FujiFilm:{}", numeric_tag(tag))),
+    );
 
-    #[cfg(target_os = "windows")]
-    let path = root.join("deps").join("exiftool").join("exiftool(-k).exe");
+    args
+}
 
-    #[cfg(not(target_os = "windows"))]
-    let path = root.join("deps").join("exiftool").join("exiftool");
+/// [`read_args`] followed by `img_path`. Movies are read with `-ee` as well,
+/// to include the metadata embedded in their streams. Images are not, `-ee`
+/// would add the tags of the previews embedded in a JPEG.
+pub(crate) fn file_args(img_path: &Path) -> Vec<String> {
+    let mut args = read_args();
+    if is_video_extension(img_path) {
+        args.push(EXTRACT_EMBEDDED.to_string());
+    }
+    args.push(img_path.to_string_lossy().to_string());
 
-    Ok(path)
+    args
 }
 
-#[cfg(not(target_os = "windows"))]
-pub fn spawn_exiftool(_: &Path) -> Command {
-    Command::new("perl")
+/// Reads the metadata of the only file in the output of exiftool. Warnings
+/// printed before the JSON are kept as `ExifTool:Warning` tags, see
+/// [`diagnostics`].
+pub(crate) fn parse_output(stdout: &str) -> Result<Vec<ExifData>, Error> {
+    let (printed, json) = split_stdout(stdout);
+
+    if json.trim().is_empty() {
+        let message: Vec<String> = printed.iter().map(|d| d.to_string()).collect();
+
+        return ExiftoolWarningSnafu {
+            message: message.join("\n"),
+        }
+        .fail();
+    }
+
+    let json: serde_json::Value = serde_json::from_str(json).context(InvalidJsonSnafu)?;
+    let entries = json.as_array().context(NonObjectEntrySnafu {
+        found: json_kind(&json),
+    })?;
+    let entry = entries.first().context(EmptyResultSnafu)?;
+
+    let mut fields = parse_entry(entry)?;
+    for diagnostic in printed {
+        warn!("exiftool: {}", diagnostic);
+        fields.push(diagnostic.to_exif());
+    }
+
+    Ok(fields)
 }
 
-#[cfg(target_os = "windows")]
-fn spawn_exiftool(exiftool_path: &Path) -> Command {
-    let mut cmd = Command::new(exiftool_path);
-    cmd.creation_flags(CREATE_NO_WINDOW);
+/// Reads the metadata of one file in the output of exiftool. Files exiftool
+/// could not read have an `Error` tag, which is returned as an error.
+pub(crate) fn parse_entry(entry: &serde_json::Value) -> Result<Vec<ExifData>, Error> {
+    ensure!(
+        entry.is_object(),
+        NonObjectEntrySnafu {
+            found: json_kind(entry)
+        }
+    );
+
+    let fields: Vec<ExifData> = JsonValue(entry.clone()).try_into().context(JsonSnafu)?;
+
+    for diagnostic in diagnostics(&fields) {
+        if diagnostic.severity == Severity::Error {
+            return ExiftoolWarningSnafu {
+                message: diagnostic.to_string(),
+            }
+            .fail();
+        }
+
+        warn!("exiftool: {}", diagnostic);
+    }
 
-    cmd
+    Ok(fields)
+}
+
+pub(crate) fn json_kind(value: &serde_json::Value) -> &'static str {
+    use serde_json::Value;
+
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
 }
 
 #[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
 pub enum Error {
-    #[snafu(display("Failed to get current dir: {:?}", source))]
-    CurrentDir { source: std::io::Error },
-
     #[snafu(display("Invalid File: {:?}", path))]
     Path { path: String },
 
+    #[snafu(display("Unsupported data, expected a JPEG, HEIF, RAF or MOV/MP4 file"))]
+    UnsupportedData,
+
+    #[snafu(display("Could not find exiftool, tried: {:?}", tried))]
+    NotFound { tried: Vec<PathBuf> },
+
     #[snafu(display("Failed to run exiftool: {:?}", source))]
     Exiftool { source: std::io::Error },
 
@@ -117,4 +419,120 @@ pub enum Error {
 
     #[snafu(display("Failed to parse JSON: {:?}", source))]
     Json { source: JsonError },
+
+    #[snafu(display("exiftool did not return valid JSON: {}", source))]
+    InvalidJson { source: serde_json::Error },
+
+    #[snafu(display("exiftool returned no metadata"))]
+    EmptyResult,
+
+    #[snafu(display("Expected a JSON object for the file, exiftool returned {}", found))]
+    NonObjectEntry { found: &'static str },
+
+    #[snafu(display("exiftool could not read the file: {}", message))]
+    ExiftoolWarning { message: String },
+
+    #[snafu(display("Failed to communicate with exiftool: {:?}", source))]
+    Io { source: std::io::Error },
+
+    #[snafu(display("Exiftool session failed: {}", reason))]
+    Session { reason: String },
+
+    #[snafu(display("Batch failed: {}", source))]
+    Batch { source: Arc<Error> },
+
+    #[snafu(display("Exiftool returned no metadata for {:?}", path))]
+    MissingResult { path: String },
+
+    #[snafu(display("Exiftool timed out after {:?} reading {:?}", elapsed, path))]
+    Timeout { path: String, elapsed: Duration },
+
+    #[snafu(display("Reading {:?} was cancelled", path))]
+    Cancelled { path: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::FindExifData;
+
+    #[test]
+    fn it_extracts_embedded_metadata_of_movies_only() {
+        let args = file_args(Path::new("DSCF0001.MOV"));
+
+        assert!(args.contains(&EXTRACT_EMBEDDED.to_string()));
+        assert_eq!(args.last().map(|a| a.as_str()), Some("DSCF0001.MOV"));
+
+        let args = file_args(Path::new("DSCF0001.JPG"));
+
+        assert!(!args.contains(&EXTRACT_EMBEDDED.to_string()));
+    }
+
+    #[test]
+    fn it_fails_on_invalid_output() {
+        assert!(matches!(
+            parse_output("[{\"SourceFile\": "),
+            Err(Error::InvalidJson { .. })
+        ));
+        assert!(matches!(parse_output("[]"), Err(Error::EmptyResult)));
+        assert!(matches!(
+            parse_output("[\"a.jpg\"]"),
+            Err(Error::NonObjectEntry { found: "a string" })
+        ));
+        assert!(matches!(
+            parse_output("{\"SourceFile\": \"a.jpg\"}"),
+            Err(Error::NonObjectEntry { found: "an object" })
+        ));
+    }
+
+    #[test]
+    fn it_fails_on_a_printed_warning() {
+        let result = parse_output("Warning: Error opening file - a.jpg\n");
+
+        assert!(matches!(
+            result,
+            Err(Error::ExiftoolWarning { message }) if message == "Warning: Error opening file - a.jpg"
+        ));
+    }
+
+    #[test]
+    fn it_fails_on_files_exiftool_could_not_read() {
+        let stdout = r#"[{"SourceFile": "a.jpg", "ExifTool:Error": "File format error"}]"#;
+
+        assert!(matches!(
+            parse_output(stdout),
+            Err(Error::ExiftoolWarning { message }) if message == "Error: File format error"
+        ));
+    }
+
+    #[test]
+    fn it_keeps_warnings_next_to_the_metadata() {
+        let stdout = r#"Warning: Bad MakerNotes offset
+[{
+    "SourceFile": "a.jpg",
+    "ExifTool:Warning": "[minor] Possibly incorrect maker notes offsets",
+    "FujiFilm:FilmMode": "Classic Chrome"
+}]"#;
+
+        let fields = parse_output(stdout).unwrap();
+
+        assert_eq!(
+            fields.as_slice().find("FilmMode"),
+            Some(ExifData::grouped(
+                "FujiFilm",
+                "FilmMode",
+                "Classic Chrome".into()
+            ))
+        );
+        assert_eq!(
+            diagnostics(&fields)
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "Warning: [minor] Possibly incorrect maker notes offsets",
+                "Warning: Bad MakerNotes offset"
+            ]
+        );
+    }
 }
@@ -0,0 +1,504 @@
+//! Async variants of [`super::spawn`] and [`super::session`], built on
+//! `tokio::process`. Enabled with the `tokio` feature.
+//!
+//! Requests take the timeout and cancel handle of [`ExiftoolOptions`], and
+//! return [`Error::Timeout`] or [`Error::Cancelled`] like the sync ones.
+//!
+//! A request future dropped before it completes, e.g. by
+//! `tokio::time::timeout` or `tokio::select!`, kills the exiftool it was
+//! waiting on. A session starts a new one on its next request, so the output
+//! left of the dropped request is never read.
+
+use super::batch::{
+    batch_args, fail_with, in_order, requests, split_output, split_paths, BatchResult,
+};
+use super::discover::{find_exiftool, Exiftool};
+use super::options::{Deadline, ExiftoolOptions};
+use super::session::{ready_marker, request, SHUTDOWN};
+use super::spawn::{file_args, parse_output, Error, ExiftoolSnafu, IoSnafu, SessionSnafu};
+use crate::exif::ExifData;
+use crate::utils::extension::is_valid_extension;
+use ::tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use ::tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
+use ::tokio::time;
+use log::{debug, warn};
+use snafu::prelude::*;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::process::Stdio;
+
+/// Reads Metadata from a JPEG, HEIF, RAF or MOV/MP4 File. This spawns a new
+/// process that runs exiftool, use [`AsyncExiftoolSession`] instead when
+/// reading many files.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::tokio as exiftool;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::path::Path::new("tests/img/DSCF5230.JPG");
+/// let metadata = exiftool::read_metadata(&path, None).await?;
+///
+/// println!("Metadata: {:?}", metadata);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub async fn read_metadata<P>(
+    img_path: &P,
+    exiftool_path: Option<&Path>,
+) -> Result<Vec<ExifData>, Error>
+where
+    P: AsRef<Path>,
+{
+    read_metadata_with_options(
+        img_path,
+        &ExiftoolOptions::new().exiftool_path(exiftool_path),
+    )
+    .await
+}
+
+/// Same as [`read_metadata`], killing exiftool when it takes longer than the
+/// timeout or is cancelled.
+pub async fn read_metadata_with_options<P>(
+    img_path: &P,
+    options: &ExiftoolOptions,
+) -> Result<Vec<ExifData>, Error>
+where
+    P: AsRef<Path>,
+{
+    if !is_valid_extension(img_path.as_ref()) {
+        return Err(Error::Path {
+            path: img_path.as_ref().to_string_lossy().to_string(),
+        });
+    }
+
+    let deadline = options.deadline();
+    let path = img_path.as_ref().to_string_lossy();
+
+    let mut cmd = command(&find_exiftool(options.exiftool_path.as_deref())?);
+    let output = cmd.args(file_args(img_path.as_ref())).output();
+    let output = with_deadline(
+        async { output.await.context(ExiftoolSnafu) },
+        &deadline,
+        &path,
+    )
+    .await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if stdout.trim().is_empty() {
+        return Err(Error::Stderr {
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    parse_output(&stdout)
+}
+
+/// Reads Metadata from many JPEG, HEIF, RAF or MOV/MP4 Files in a single
+/// exiftool run, see [`super::spawn::read_metadata_batch`].
+pub async fn read_metadata_batch<P>(img_paths: &[P], exiftool_path: Option<&Path>) -> BatchResult
+where
+    P: AsRef<Path>,
+{
+    read_metadata_batch_with_options(
+        img_paths,
+        &ExiftoolOptions::new().exiftool_path(exiftool_path),
+    )
+    .await
+}
+
+/// Same as [`read_metadata_batch`]. When the run times out or is cancelled,
+/// every file gets [`Error::Timeout`] or [`Error::Cancelled`].
+pub async fn read_metadata_batch_with_options<P>(
+    img_paths: &[P],
+    options: &ExiftoolOptions,
+) -> BatchResult
+where
+    P: AsRef<Path>,
+{
+    let (valid, mut results) = split_paths(img_paths);
+
+    for valid in requests(valid) {
+        let deadline = options.deadline();
+        let files = files(&valid);
+
+        let output = match find_exiftool(options.exiftool_path.as_deref()) {
+            Ok(exiftool) => {
+                let mut cmd = command(&exiftool);
+                let output = cmd.args(batch_args(&valid)).output();
+
+                with_deadline(
+                    async { output.await.context(ExiftoolSnafu) },
+                    &deadline,
+                    &files,
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let batch = match output {
+            Ok(output) => split_output(
+                &valid,
+                &String::from_utf8_lossy(&output.stdout),
+                &String::from_utf8_lossy(&output.stderr),
+            ),
+            Err(e) => fail_with(&valid, e),
+        };
+
+        results.extend(batch);
+    }
+
+    in_order(img_paths, results)
+}
+
+/// Async [`super::session::ExiftoolSession`], a long-lived exiftool process
+/// started with `-stay_open True`.
+///
+/// Call [`AsyncExiftoolSession::close`] to stop exiftool cleanly, otherwise it
+/// is killed when the session is dropped.
+///
+/// # Example
+///
+/// ```
+/// use fuji::exiftool::tokio::AsyncExiftoolSession;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut session = AsyncExiftoolSession::new(None)?;
+///
+/// for path in ["tests/img/DSCF5230.JPG", "tests/img/DSCF5358.JPG"] {
+///     let metadata = session.read_metadata(&path).await?;
+///     println!("Metadata: {:?}", metadata);
+/// }
+///
+/// session.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncExiftoolSession {
+    exiftool: Exiftool,
+    options: ExiftoolOptions,
+    process: Option<Process>,
+    counter: u64,
+}
+
+impl AsyncExiftoolSession {
+    pub fn new(exiftool_path: Option<&Path>) -> Result<Self, Error> {
+        Self::with_options(ExiftoolOptions::new().exiftool_path(exiftool_path))
+    }
+
+    /// Starts a session with a timeout and/or cancel handle. When a request
+    /// times out, is cancelled or dropped exiftool is killed, and started
+    /// again on the next request.
+    pub fn with_options(options: ExiftoolOptions) -> Result<Self, Error> {
+        let exiftool = find_exiftool(options.exiftool_path.as_deref())?;
+        debug!("Exiftool {:?}", exiftool);
+
+        let process = Process::spawn(&exiftool)?;
+
+        Ok(AsyncExiftoolSession {
+            exiftool,
+            options,
+            process: Some(process),
+            counter: 0,
+        })
+    }
+
+    /// Reads Metadata from a JPEG, HEIF, RAF or MOV/MP4 File using the running
+    /// exiftool process.
+    pub async fn read_metadata<P>(&mut self, img_path: &P) -> Result<Vec<ExifData>, Error>
+    where
+        P: AsRef<Path>,
+    {
+        if !is_valid_extension(img_path.as_ref()) {
+            return Err(Error::Path {
+                path: img_path.as_ref().to_string_lossy().to_string(),
+            });
+        }
+
+        let path = img_path.as_ref().to_string_lossy();
+        let args = file_args(img_path.as_ref());
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+        let (stdout, stderr) = self.execute(&args, &path).await?;
+
+        if stdout.trim().is_empty() {
+            return Err(Error::Stderr { stderr });
+        }
+
+        parse_output(&stdout)
+    }
+
+    /// Reads Metadata from many JPEG, HEIF, RAF or MOV/MP4 Files with a single
+    /// request, see [`super::spawn::read_metadata_batch`].
+    pub async fn read_metadata_batch<P>(&mut self, img_paths: &[P]) -> BatchResult
+    where
+        P: AsRef<Path>,
+    {
+        let (valid, mut results) = split_paths(img_paths);
+
+        for valid in requests(valid) {
+            let args = batch_args(&valid);
+            let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+
+            let batch = match self.execute(&args, &files(&valid)).await {
+                Ok((stdout, stderr)) => split_output(&valid, &stdout, &stderr),
+                Err(e) => fail_with(&valid, e),
+            };
+
+            results.extend(batch);
+        }
+
+        in_order(img_paths, results)
+    }
+
+    /// Stops exiftool and waits for it to exit.
+    pub async fn close(mut self) -> Result<(), Error> {
+        match self.process.take() {
+            Some(process) => process.shutdown().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Sends one request and waits for its response, see
+    /// [`super::session::ExiftoolSession`].
+    ///
+    /// The process is taken out of the session while the request runs. When
+    /// the request fails, or its future is dropped before the `{readyN}`
+    /// marker of `id` was read, the process is killed with it, and the next
+    /// request starts a new one instead of reading the rest of this output.
+    async fn execute(&mut self, args: &[&str], path: &str) -> Result<(String, String), Error> {
+        let deadline = self.options.deadline();
+        deadline.check().map_err(|i| i.into_error(path))?;
+
+        self.counter += 1;
+        let id = self.counter;
+
+        let mut process = match self.process.take() {
+            Some(process) => process,
+            None => {
+                debug!("Starting exiftool for request {}", id);
+                Process::spawn(&self.exiftool)?
+            }
+        };
+
+        let request = request(args, id);
+        let marker = ready_marker(id);
+        let result = with_deadline(process.execute(&request, &marker), &deadline, path).await;
+
+        match result {
+            Ok(output) => {
+                self.process = Some(process);
+
+                Ok(output)
+            }
+            Err(e) => {
+                warn!("Stopping exiftool: {}", e);
+                process.kill().await;
+
+                Err(e)
+            }
+        }
+    }
+}
+
+/// A running `-stay_open` exiftool, killed when it is dropped.
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    stderr: BufReader<ChildStderr>,
+}
+
+impl Process {
+    fn spawn(exiftool: &Exiftool) -> Result<Self, Error> {
+        let mut child = command(exiftool)
+            .arg("-stay_open")
+            .arg("True")
+            .arg("-@")
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(ExiftoolSnafu)?;
+
+        let stdin = child.stdin.take().context(SessionSnafu {
+            reason: "stdin not available",
+        })?;
+        let stdout = child.stdout.take().context(SessionSnafu {
+            reason: "stdout not available",
+        })?;
+        let stderr = child.stderr.take().context(SessionSnafu {
+            reason: "stderr not available",
+        })?;
+
+        Ok(Process {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            stderr: BufReader::new(stderr),
+        })
+    }
+
+    async fn execute(&mut self, request: &str, marker: &str) -> Result<(String, String), Error> {
+        self.stdin
+            .write_all(request.as_bytes())
+            .await
+            .context(IoSnafu)?;
+        self.stdin.flush().await.context(IoSnafu)?;
+
+        let stdout = match read_until_marker(&mut self.stdout, marker).await {
+            Ok(stdout) => stdout,
+            Err(e) => {
+                // The process is gone, whatever it printed explains why.
+                let mut stderr = String::new();
+                let _ = self.stderr.read_to_string(&mut stderr).await;
+
+                if stderr.trim().is_empty() {
+                    return Err(e);
+                }
+
+                return Err(Error::Stderr { stderr });
+            }
+        };
+        let stderr = read_until_marker(&mut self.stderr, marker).await?;
+
+        Ok((stdout, stderr))
+    }
+
+    async fn shutdown(mut self) -> Result<(), Error> {
+        self.stdin.write_all(SHUTDOWN).await.context(IoSnafu)?;
+        self.stdin.flush().await.context(IoSnafu)?;
+        self.child.wait().await.context(IoSnafu)?;
+
+        Ok(())
+    }
+
+    async fn kill(mut self) {
+        let _ = self.child.kill().await;
+    }
+}
+
+fn command(exiftool: &Exiftool) -> Command {
+    let mut cmd = Command::from(exiftool.command());
+    cmd.kill_on_drop(true);
+
+    cmd
+}
+
+/// Names of the files of a batch, to report timeouts and cancellations.
+fn files(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Waits for `future`, giving up on it once the deadline is reached. The
+/// future is dropped then, which kills the exiftool it was waiting on.
+async fn with_deadline<F, T>(future: F, deadline: &Deadline, path: &str) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let mut future = pin!(future);
+
+    loop {
+        deadline.check().map_err(|i| i.into_error(path))?;
+
+        match deadline.next_wait() {
+            Some(wait) => {
+                if let Ok(output) = time::timeout(wait, future.as_mut()).await {
+                    return output;
+                }
+            }
+            None => return future.await,
+        }
+    }
+}
+
+/// Reads lines until the `marker` line, returning everything before it.
+async fn read_until_marker<R>(reader: &mut R, marker: &str) -> Result<String, Error>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut output = String::new();
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).await.context(IoSnafu)?;
+
+        if read == 0 {
+            return Err(Error::Session {
+                reason: "exiftool exited unexpectedly".to_string(),
+            });
+        }
+
+        if line.trim_end() == marker {
+            return Ok(output);
+        }
+
+        output.push_str(&line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exiftool::options::CancelHandle;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn it_reads_until_the_marker() {
+        let mut reader = &b"[{\"Make\": \"FUJIFILM\"}]\n{ready1}\n"[..];
+
+        assert_eq!(
+            read_until_marker(&mut reader, "{ready1}").await.unwrap(),
+            "[{\"Make\": \"FUJIFILM\"}]\n"
+        );
+        assert!(matches!(
+            read_until_marker(&mut reader, "{ready2}").await,
+            Err(Error::Session { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_times_out_waiting() {
+        let deadline = ExiftoolOptions::new()
+            .timeout(Duration::from_millis(20))
+            .deadline();
+
+        let result: Result<(), Error> =
+            with_deadline(std::future::pending(), &deadline, "a.jpg").await;
+
+        assert!(matches!(result, Err(Error::Timeout { path, .. }) if path == "a.jpg"));
+    }
+
+    #[tokio::test]
+    async fn it_stops_waiting_when_cancelled() {
+        let cancel = CancelHandle::new();
+        let deadline = ExiftoolOptions::new()
+            .cancel_handle(cancel.clone())
+            .deadline();
+
+        let result: Result<(), Error> = with_deadline(
+            async {
+                cancel.cancel();
+                std::future::pending().await
+            },
+            &deadline,
+            "a.jpg",
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::Cancelled { .. })));
+    }
+}
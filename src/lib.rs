@@ -1,7 +1,7 @@
 //! # Fuji
 //!
-//! `fuji` is a library to read Fujifilm Recipes & Exif Metadata from a JPEG or RAF file using
-//! [exiftool](https://exiftool.org/).
+//! `fuji` is a library to read Fujifilm Recipes & Exif Metadata from a JPEG,
+//! HEIF or RAF file using [exiftool](https://exiftool.org/).
 //!
 //! ## Example
 //!
@@ -19,13 +19,15 @@
 //!
 //! // Recipe will contain details like FilmSimulation, WhiteBalance, etc.
 //! if let Some(details) = recipe {
-//!     println!("Film Simulation: {:?}", details.film_simulation);
+//!     println!("Film Simulation: {:?}", details.film_simulation());
 //!     println!("White Balance: {:?}", details.settings);
 //! }
 //! # Ok(())
 //! # }
 
+pub mod camera;
 pub mod exif;
 pub mod exiftool;
+pub mod native;
 pub mod recipe;
 mod utils;
@@ -0,0 +1,43 @@
+//! Boxes of the ISO Base Media File Format, shared by HEIF and QuickTime/MP4.
+
+pub(crate) struct IsoBox<'a> {
+    pub kind: [u8; 4],
+    pub body: &'a [u8],
+}
+
+/// Iterates the boxes of a container, stopping at the first truncated one.
+pub(crate) fn boxes(data: &[u8]) -> impl Iterator<Item = IsoBox<'_>> {
+    let mut pos = 0;
+
+    std::iter::from_fn(move || {
+        let header = data.get(pos..pos + 8)?;
+        let size = be(&header[..4]) as usize;
+        let kind = [header[4], header[5], header[6], header[7]];
+
+        let (header_len, size) = match size {
+            // Box extends to the end of the container
+            0 => (8, data.len() - pos),
+            // 64-bit size follows the type
+            1 => (16, be(data.get(pos + 8..pos + 16)?) as usize),
+            size => (8, size),
+        };
+
+        if size < header_len {
+            return None;
+        }
+
+        let body = data.get(pos + header_len..pos.checked_add(size)?)?;
+        pos += size;
+
+        Some(IsoBox { kind, body })
+    })
+}
+
+/// Finds the first box of the given type in a container.
+pub(crate) fn find_box<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<IsoBox<'a>> {
+    boxes(data).find(|b| b.kind == *kind)
+}
+
+pub(crate) fn be(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |acc, b| (acc << 8) | *b as u64)
+}
@@ -0,0 +1,83 @@
+//! Adobe DNG Converter keeps the MakerNote of the original raw in the
+//! `DNGPrivateData` tag of IFD0 instead of the EXIF IFD.
+
+pub const TAG_DNG_VERSION: u16 = 0xc612;
+pub const TAG_DNG_PRIVATE_DATA: u16 = 0xc634;
+
+const ADOBE: &[u8] = b"Adobe\0";
+const MAKER_NOTE_BLOCK: &[u8] = b"MakN";
+
+/// Finds the original MakerNote in the `DNGPrivateData`.
+///
+/// The data starts with `Adobe\0`, followed by blocks of a 4 byte name and a
+/// big-endian length. The `MakN` block holds the byte order & offset the
+/// MakerNote had in the original file, then the MakerNote itself.
+pub fn find_maker_note(private_data: &[u8]) -> Option<&[u8]> {
+    let mut pos = ADOBE.len();
+
+    if !private_data.starts_with(ADOBE) {
+        return None;
+    }
+
+    while pos + 8 <= private_data.len() {
+        let name = &private_data[pos..pos + 4];
+        let len = &private_data[pos + 4..pos + 8];
+        let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+
+        let block = private_data.get(pos + 8..(pos + 8).checked_add(len)?)?;
+
+        if name == MAKER_NOTE_BLOCK {
+            // Byte order (2) and original offset (4)
+            return block.get(6..);
+        }
+
+        pos += 8 + len;
+    }
+
+    None
+}
+
+/// `DNGVersion` is stored as 4 bytes, e.g. `1.4.0.0`.
+pub fn version(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(name: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut block = name.to_vec();
+        block.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        block.extend_from_slice(data);
+
+        block
+    }
+
+    #[test]
+    fn it_finds_the_maker_note_block() {
+        let mut data = ADOBE.to_vec();
+        data.extend(block(b"SR2 ", b"sony"));
+        data.extend(block(MAKER_NOTE_BLOCK, b"II\0\0\0\x10FUJIFILM"));
+
+        assert_eq!(find_maker_note(&data), Some(&b"FUJIFILM"[..]));
+    }
+
+    #[test]
+    fn it_ignores_other_private_data() {
+        assert_eq!(find_maker_note(b"Nikon\0MakN"), None);
+
+        let mut data = ADOBE.to_vec();
+        data.extend(block(b"SR2 ", b"sony"));
+
+        assert_eq!(find_maker_note(&data), None);
+    }
+
+    #[test]
+    fn it_prints_the_version() {
+        assert_eq!(version(&[1, 4, 0, 0]), "1.4.0.0");
+    }
+}
@@ -0,0 +1,368 @@
+use super::bmff::{be, boxes, find_box};
+use super::tiff::slice;
+use crate::utils::magic::{detect_file_type, FileType};
+use log::trace;
+use snafu::prelude::*;
+
+const META: &[u8; 4] = b"meta";
+const IINF: &[u8; 4] = b"iinf";
+const INFE: &[u8; 4] = b"infe";
+const ILOC: &[u8; 4] = b"iloc";
+const IDAT: &[u8; 4] = b"idat";
+const EXIF_ITEM: &[u8; 4] = b"Exif";
+
+/// Finds the TIFF structure stored in the `Exif` item of a HEIF file.
+///
+/// The item is listed in the `iinf` box of the top-level `meta` box, and the
+/// `iloc` box tells where its data is, either in the file (usually `mdat`) or
+/// in the `idat` box. The item data starts with the offset to the TIFF header.
+pub fn find_exif(data: &[u8]) -> Result<&[u8], Error> {
+    ensure!(detect_file_type(data) == Some(FileType::Heif), NotHeifSnafu);
+
+    let meta = find_box(data, META).context(MissingBoxSnafu { kind: "meta" })?;
+    let children = full_box_body(meta.body, "meta")?;
+
+    let iinf = find_box(children, IINF).context(MissingBoxSnafu { kind: "iinf" })?;
+    let item_id = exif_item_id(iinf.body)?.context(ExifNotFoundSnafu)?;
+
+    trace!("HEIF Exif item: {}", item_id);
+
+    let iloc = find_box(children, ILOC).context(MissingBoxSnafu { kind: "iloc" })?;
+    let location = item_location(iloc.body, item_id)?.context(ExifNotFoundSnafu)?;
+
+    trace!("HEIF Exif location: {:?}", location);
+
+    let source = match location.construction_method {
+        0 => data,
+        1 => {
+            find_box(children, IDAT)
+                .context(MissingBoxSnafu { kind: "idat" })?
+                .body
+        }
+        method => return UnsupportedConstructionSnafu { method }.fail(),
+    };
+
+    let item = slice(source, location.offset, location.length)
+        .ok()
+        .context(TruncatedSnafu {
+            offset: location.offset,
+        })?;
+
+    ensure!(item.len() >= 4, TruncatedSnafu { offset: 0usize });
+    let tiff_offset = 4 + be(&item[..4]) as usize;
+
+    item.get(tiff_offset..).context(TruncatedSnafu {
+        offset: tiff_offset,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ItemLocation {
+    construction_method: u8,
+    offset: usize,
+    length: usize,
+}
+
+/// Skips the version & flags of a full box.
+fn full_box_body<'a>(body: &'a [u8], kind: &'static str) -> Result<&'a [u8], Error> {
+    body.get(4..).context(MissingBoxSnafu { kind })
+}
+
+/// ID of the first `infe` entry with the `Exif` item type.
+fn exif_item_id(iinf: &[u8]) -> Result<Option<u32>, Error> {
+    let mut reader = Reader::new(iinf);
+    let version = reader.u8()?;
+    reader.skip(3)?;
+
+    let _count = if version == 0 {
+        reader.u16()? as u32
+    } else {
+        reader.u32()?
+    };
+
+    for infe in boxes(reader.rest()).filter(|b| b.kind == *INFE) {
+        let mut reader = Reader::new(infe.body);
+        let version = reader.u8()?;
+        reader.skip(3)?;
+
+        // Versions 0 & 1 have no item type
+        if version < 2 {
+            continue;
+        }
+
+        let item_id = if version == 2 {
+            reader.u16()? as u32
+        } else {
+            reader.u32()?
+        };
+        let _protection_index = reader.u16()?;
+        let item_type = reader.bytes(4)?;
+
+        if item_type == EXIF_ITEM {
+            return Ok(Some(item_id));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Location of `item_id` in the `iloc` box. Only items stored in a single
+/// extent are supported, which is how cameras write the Exif item.
+fn item_location(iloc: &[u8], item_id: u32) -> Result<Option<ItemLocation>, Error> {
+    let mut reader = Reader::new(iloc);
+    let version = reader.u8()?;
+    reader.skip(3)?;
+
+    let sizes = reader.u8()?;
+    let offset_size = sizes >> 4;
+    let length_size = sizes & 0xf;
+
+    let sizes = reader.u8()?;
+    let base_offset_size = sizes >> 4;
+    let index_size = if version == 1 || version == 2 {
+        sizes & 0xf
+    } else {
+        0
+    };
+
+    let count = if version < 2 {
+        reader.u16()? as u32
+    } else {
+        reader.u32()?
+    };
+
+    for _ in 0..count {
+        let id = if version < 2 {
+            reader.u16()? as u32
+        } else {
+            reader.u32()?
+        };
+
+        let construction_method = if version == 1 || version == 2 {
+            (reader.u16()? & 0xf) as u8
+        } else {
+            0
+        };
+        let _data_reference_index = reader.u16()?;
+        let base_offset = reader.sized(base_offset_size)?;
+
+        let extent_count = reader.u16()?;
+        let mut extents = vec![];
+
+        for _ in 0..extent_count {
+            reader.sized(index_size)?;
+            let offset = reader.sized(offset_size)?;
+            let length = reader.sized(length_size)?;
+
+            extents.push((offset, length));
+        }
+
+        if id != item_id {
+            continue;
+        }
+
+        ensure!(
+            extents.len() == 1,
+            UnsupportedExtentsSnafu {
+                count: extents.len()
+            }
+        );
+
+        let (offset, length) = extents[0];
+        let invalid = InvalidLocationSnafu {
+            base_offset,
+            offset,
+            length,
+        };
+
+        return Ok(Some(ItemLocation {
+            construction_method,
+            offset: base_offset
+                .checked_add(offset)
+                .and_then(|offset| usize::try_from(offset).ok())
+                .context(invalid)?,
+            length: usize::try_from(length).ok().context(invalid)?,
+        }));
+    }
+
+    Ok(None)
+}
+
+/// Reads big-endian numbers from the body of a box.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let bytes = self
+            .data
+            .get(self.pos..self.pos + len)
+            .context(TruncatedSnafu { offset: self.pos })?;
+        self.pos += len;
+
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), Error> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        Ok(be(self.bytes(2)?) as u16)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(be(self.bytes(4)?) as u32)
+    }
+
+    /// Number stored in `size` bytes, 0 bytes meaning a value of 0.
+    fn sized(&mut self, size: u8) -> Result<u64, Error> {
+        match size {
+            0 | 4 | 8 => Ok(be(self.bytes(size as usize)?)),
+            size => InvalidFieldSizeSnafu { size }.fail(),
+        }
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Not a HEIF file"))]
+    NotHeif,
+
+    #[snafu(display("HEIF file has no {} box", kind))]
+    MissingBox { kind: &'static str },
+
+    #[snafu(display("HEIF data at offset {} is truncated", offset))]
+    Truncated { offset: usize },
+
+    #[snafu(display("Invalid HEIF field size: {}", size))]
+    InvalidFieldSize { size: u8 },
+
+    #[snafu(display("Unsupported HEIF item construction method: {}", method))]
+    UnsupportedConstruction { method: u8 },
+
+    #[snafu(display("Exif item stored in {} extents is not supported", count))]
+    UnsupportedExtents { count: usize },
+
+    #[snafu(display(
+        "Invalid HEIF item location: offset {} + {}, length {}",
+        base_offset,
+        offset,
+        length
+    ))]
+    InvalidLocation {
+        base_offset: u64,
+        offset: u64,
+        length: u64,
+    },
+
+    #[snafu(display("No Exif item found"))]
+    ExifNotFound,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iso_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend_from_slice(body);
+
+        data
+    }
+
+    fn full_box(kind: &[u8; 4], version: u8, body: &[u8]) -> Vec<u8> {
+        let mut data = vec![version, 0, 0, 0];
+        data.extend_from_slice(body);
+
+        iso_box(kind, &data)
+    }
+
+    fn infe(item_id: u16, item_type: &[u8; 4]) -> Vec<u8> {
+        let mut body = item_id.to_be_bytes().to_vec();
+        body.extend_from_slice(&0u16.to_be_bytes());
+        body.extend_from_slice(item_type);
+        body.push(0);
+
+        full_box(INFE, 2, &body)
+    }
+
+    /// HEIF with an `hvc1` item and an `Exif` item stored in `idat`.
+    fn build_heif(exif: &[u8]) -> Vec<u8> {
+        let mut item = 6u32.to_be_bytes().to_vec();
+        item.extend_from_slice(b"Exif\0\0");
+        item.extend_from_slice(exif);
+
+        let mut iinf = 2u16.to_be_bytes().to_vec();
+        iinf.extend(infe(1, b"hvc1"));
+        iinf.extend(infe(2, EXIF_ITEM));
+
+        // Version 1, 4-byte offsets & lengths, no base offset
+        let mut iloc = vec![0x44, 0x00];
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&2u16.to_be_bytes());
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&0u16.to_be_bytes());
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&0u32.to_be_bytes());
+        iloc.extend_from_slice(&(item.len() as u32).to_be_bytes());
+
+        let mut meta = full_box(IINF, 0, &iinf);
+        meta.extend(full_box(ILOC, 1, &iloc));
+        meta.extend(iso_box(IDAT, &item));
+
+        let mut data = iso_box(b"ftyp", b"heix\0\0\0\0mif1heic");
+        data.extend(full_box(META, 0, &meta));
+
+        data
+    }
+
+    #[test]
+    fn it_finds_the_exif_item() {
+        let data = build_heif(b"II*\0\x08\0\0\0");
+
+        assert_eq!(find_exif(&data).unwrap(), b"II*\0\x08\0\0\0");
+    }
+
+    #[test]
+    fn it_rejects_overflowing_item_locations() {
+        // Version 1, 8-byte offsets, lengths & base offset
+        let mut iloc = vec![1, 0, 0, 0, 0x88, 0x80];
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&2u16.to_be_bytes());
+        iloc.extend_from_slice(&0u16.to_be_bytes());
+        iloc.extend_from_slice(&0u16.to_be_bytes());
+        iloc.extend_from_slice(&u64::MAX.to_be_bytes());
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&1u64.to_be_bytes());
+        iloc.extend_from_slice(&4u64.to_be_bytes());
+
+        assert!(matches!(
+            item_location(&iloc, 2),
+            Err(Error::InvalidLocation { .. })
+        ));
+    }
+
+    #[test]
+    fn it_fails_without_exif_item() {
+        let mut data = iso_box(b"ftyp", b"heic\0\0\0\0mif1");
+        data.extend(full_box(META, 0, &full_box(IINF, 0, &0u16.to_be_bytes())));
+
+        assert!(matches!(find_exif(&data), Err(Error::ExifNotFound)));
+    }
+}
@@ -0,0 +1,119 @@
+use snafu::prelude::*;
+
+const SOI: [u8; 2] = [0xff, 0xd8];
+const APP1: u8 = 0xe1;
+const SOS: u8 = 0xda;
+const EOI: u8 = 0xd9;
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+/// Finds the TIFF structure stored in the `Exif` APP1 segment of a JPEG.
+///
+/// Only the segments before the image data (`SOS`) are walked, the EXIF
+/// segment is always at the start of the file.
+pub fn find_exif(data: &[u8]) -> Result<&[u8], Error> {
+    ensure!(data.starts_with(&SOI), NotJpegSnafu);
+
+    let mut pos = SOI.len();
+
+    while pos + 4 <= data.len() {
+        ensure!(data[pos] == 0xff, InvalidMarkerSnafu { offset: pos });
+
+        let marker = data[pos + 1];
+
+        // Markers can be padded with any number of 0xff bytes.
+        if marker == 0xff {
+            pos += 1;
+            continue;
+        }
+
+        if marker == SOS || marker == EOI {
+            break;
+        }
+
+        let len = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        ensure!(len >= 2, InvalidMarkerSnafu { offset: pos });
+
+        let start = pos + 4;
+        let end = pos + 2 + len;
+        ensure!(end <= data.len(), TruncatedSnafu { offset: pos });
+
+        let segment = &data[start..end];
+
+        if marker == APP1 && segment.starts_with(EXIF_HEADER) {
+            return Ok(&segment[EXIF_HEADER.len()..]);
+        }
+
+        pos = end;
+    }
+
+    Err(Error::ExifNotFound)
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Not a JPEG file"))]
+    NotJpeg,
+
+    #[snafu(display("Invalid JPEG marker at offset {}", offset))]
+    InvalidMarker { offset: usize },
+
+    #[snafu(display("JPEG segment at offset {} is truncated", offset))]
+    Truncated { offset: usize },
+
+    #[snafu(display("No EXIF segment found"))]
+    ExifNotFound,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0xff, marker];
+        data.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        data.extend_from_slice(payload);
+
+        data
+    }
+
+    #[test]
+    fn it_finds_exif_after_other_segments() {
+        let mut data = SOI.to_vec();
+        data.extend(segment(0xe0, b"JFIF\0\x01\x01"));
+        data.extend(segment(APP1, b"Exif\0\0II*\0"));
+        data.extend([0xff, SOS]);
+
+        assert_eq!(find_exif(&data).unwrap(), b"II*\0");
+    }
+
+    #[test]
+    fn it_skips_non_exif_app1_segments() {
+        let mut data = SOI.to_vec();
+        data.extend(segment(APP1, b"http://ns.adobe.com/xap/1.0/\0"));
+        data.extend(segment(APP1, b"Exif\0\0MM\0*"));
+
+        assert_eq!(find_exif(&data).unwrap(), b"MM\0*");
+    }
+
+    #[test]
+    fn it_fails_when_not_a_jpeg() {
+        assert!(matches!(find_exif(b"FUJIFILMCCD-RAW"), Err(Error::NotJpeg)));
+    }
+
+    #[test]
+    fn it_fails_without_exif() {
+        let mut data = SOI.to_vec();
+        data.extend(segment(0xe0, b"JFIF\0\x01\x01"));
+        data.extend([0xff, SOS]);
+
+        assert!(matches!(find_exif(&data), Err(Error::ExifNotFound)));
+    }
+
+    #[test]
+    fn it_fails_when_truncated() {
+        let mut data = SOI.to_vec();
+        data.extend([0xff, APP1, 0x10, 0x00, b'E']);
+
+        assert!(matches!(find_exif(&data), Err(Error::Truncated { .. })));
+    }
+}
@@ -0,0 +1,748 @@
+use super::tags::{format_float, signed, typed, Conv, TagDef};
+use super::tiff::{read_ifd, slice, ByteOrder, Entry, Error as TiffError};
+use crate::exif::{numeric_tag, ExifData, NUMERIC_TAGS};
+use log::trace;
+use snafu::prelude::*;
+
+const HEADER: &[u8] = b"FUJIFILM";
+
+/// exiftool's family 1 group of the MakerNote tags.
+pub const GROUP: &str = "FujiFilm";
+
+/// Decodes a Fujifilm MakerNote block.
+///
+/// The block starts with a `FUJIFILM` header followed by a little-endian
+/// offset to the IFD. Value offsets are relative to the start of the block
+/// and the byte order is always little-endian, no matter what the enclosing
+/// TIFF uses.
+pub fn parse(data: &[u8]) -> Result<Vec<ExifData>, Error> {
+    ensure!(data.starts_with(HEADER), InvalidHeaderSnafu);
+
+    let order = ByteOrder::LittleEndian;
+    let offset = slice(data, HEADER.len(), 4).context(TiffSnafu)?;
+    let offset = order.u32(offset) as usize;
+
+    let ifd = read_ifd(data, 0, offset, order).context(TiffSnafu)?;
+
+    let mut fields = vec![];
+
+    for entry in &ifd.entries {
+        let Some(def) = super::tags::find(FUJIFILM_TAGS, entry.tag) else {
+            continue;
+        };
+        let Some(value) = def.value(entry) else {
+            continue;
+        };
+
+        trace!("MakerNote 0x{:04x} {}: {:?}", entry.tag, def.name, value);
+
+        fields.push(ExifData::grouped(GROUP, def.name, value));
+
+        // Same as exiftool's `-FujiFilm:Tag#`
+        if NUMERIC_TAGS.contains(&def.name) {
+            if let Some(number) = typed(entry) {
+                fields.push(ExifData::grouped(GROUP, &numeric_tag(def.name), number));
+            }
+        }
+    }
+
+    Ok(fields)
+}
+
+fn white_balance_fine_tune(entry: &Entry) -> Option<String> {
+    let numbers = entry.numbers();
+    let red = numbers.first()?.as_i64()?;
+    let blue = numbers.get(1)?.as_i64()?;
+
+    Some(format!("Red {:+}, Blue {:+}", red, blue))
+}
+
+/// Highlight & Shadow tones are stored as multiples of -16.
+fn tone(entry: &Entry) -> Option<String> {
+    let value = entry.first_i64()?;
+    let tone = (-value) as f64 / 16.0;
+
+    let label = match value {
+        -64 => " (hardest)",
+        -48 => " (very hard)",
+        -32 => " (hard)",
+        -16 => " (medium hard)",
+        0 => " (normal)",
+        16 => " (medium soft)",
+        32 => " (soft)",
+        _ => "",
+    };
+
+    let tone = if tone > 0.0 {
+        format!("+{}", format_float(tone))
+    } else {
+        format_float(tone)
+    };
+
+    Some(format!("{}{}", tone, label))
+}
+
+/// Printed like exiftool, with a `+` on positive values only.
+fn clarity(entry: &Entry) -> Option<String> {
+    let value = entry.first_i64()? / 1000;
+
+    if value > 0 {
+        Some(format!("{:+}", value))
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn percentage(entry: &Entry) -> Option<String> {
+    let value = entry.first_i64()?;
+
+    Some(format!("{}%", value))
+}
+
+const OFF_ON: &[(i64, &str)] = &[(0, "Off"), (1, "On")];
+
+const OFF_WEAK_STRONG: &[(i64, &str)] = &[(0, "Off"), (32, "Weak"), (64, "Strong")];
+
+const WEAK_STRONG: &[(i64, &str)] = &[(1, "Weak"), (2, "Strong")];
+
+const SHARPNESS: &[(i64, &str)] = &[
+    (0x00, "-4 (softest)"),
+    (0x01, "-3 (very soft)"),
+    (0x02, "-2 (soft)"),
+    (0x03, "0 (normal)"),
+    (0x04, "+2 (hard)"),
+    (0x05, "+3 (very hard)"),
+    (0x06, "+4 (hardest)"),
+    (0x82, "-1 (medium soft)"),
+    (0x84, "+1 (medium hard)"),
+    (0x8000, "Film Simulation"),
+    (0xffff, "n/a"),
+];
+
+const WHITE_BALANCE: &[(i64, &str)] = &[
+    (0x000, "Auto"),
+    (0x001, "Auto (white priority)"),
+    (0x002, "Auto (ambiance priority)"),
+    (0x100, "Daylight"),
+    (0x200, "Cloudy"),
+    (0x300, "Daylight Fluorescent"),
+    (0x301, "Day White Fluorescent"),
+    (0x302, "White Fluorescent"),
+    (0x303, "Warm White Fluorescent"),
+    (0x304, "Living Room Warm White Fluorescent"),
+    (0x400, "Incandescent"),
+    (0x500, "Flash"),
+    (0x600, "Underwater"),
+    (0xf00, "Custom"),
+    (0xf01, "Custom2"),
+    (0xf02, "Custom3"),
+    (0xf03, "Custom4"),
+    (0xf04, "Custom5"),
+    (0xff0, "Kelvin"),
+];
+
+const SATURATION: &[(i64, &str)] = &[
+    (0x000, "0 (normal)"),
+    (0x080, "+1 (medium high)"),
+    (0x100, "+2 (high)"),
+    (0x0c0, "+3 (very high)"),
+    (0x0e0, "+4 (highest)"),
+    (0x180, "-1 (medium low)"),
+    (0x200, "Low"),
+    (0x300, "None (B&W)"),
+    (0x301, "B&W Red Filter"),
+    (0x302, "B&W Yellow Filter"),
+    (0x303, "B&W Green Filter"),
+    (0x310, "B&W Sepia"),
+    (0x400, "-2 (low)"),
+    (0x4c0, "-3 (very low)"),
+    (0x4e0, "-4 (lowest)"),
+    (0x500, "Acros"),
+    (0x501, "Acros Red Filter"),
+    (0x502, "Acros Yellow Filter"),
+    (0x503, "Acros Green Filter"),
+    (0x8000, "Film Simulation"),
+];
+
+const CONTRAST: &[(i64, &str)] = &[
+    (0x000, "Normal"),
+    (0x080, "Medium High"),
+    (0x100, "High"),
+    (0x180, "Medium Low"),
+    (0x200, "Low"),
+    (0x8000, "Film Simulation"),
+];
+
+const NOISE_REDUCTION: &[(i64, &str)] = &[(0x40, "Low"), (0x80, "Normal"), (0x100, "n/a")];
+
+const HIGH_ISO_NOISE_REDUCTION: &[(i64, &str)] = &[
+    (0x000, "0 (normal)"),
+    (0x100, "+2 (strong)"),
+    (0x180, "+1 (medium strong)"),
+    (0x1c0, "+3 (very strong)"),
+    (0x1e0, "+4 (strongest)"),
+    (0x200, "-2 (weak)"),
+    (0x280, "-1 (medium weak)"),
+    (0x2c0, "-3 (very weak)"),
+    (0x2e0, "-4 (weakest)"),
+];
+
+const FLASH_MODE: &[(i64, &str)] = &[
+    (0x0, "Auto"),
+    (0x1, "On"),
+    (0x2, "Off"),
+    (0x3, "Red-eye reduction"),
+    (0x4, "External"),
+    (0x10, "Commander"),
+    (0x8000, "Not Attached"),
+    (0x8120, "TTL"),
+    (0x9840, "Manual"),
+    (0x9880, "Multi-flash"),
+];
+
+const FOCUS_MODE: &[(i64, &str)] = &[(0, "Auto"), (1, "Manual"), (65535, "Movie")];
+
+const AF_MODE: &[(i64, &str)] = &[
+    (0, "No"),
+    (1, "Single Point"),
+    (256, "Zone"),
+    (512, "Wide/Tracking"),
+];
+
+const PICTURE_MODE: &[(i64, &str)] = &[
+    (0x0, "Auto"),
+    (0x1, "Portrait"),
+    (0x2, "Landscape"),
+    (0x3, "Macro"),
+    (0x4, "Sports"),
+    (0x5, "Night Scene"),
+    (0x6, "Program AE"),
+    (0x7, "Natural Light"),
+    (0x8, "Anti-blur"),
+    (0x9, "Beach & Snow"),
+    (0xa, "Sunset"),
+    (0xb, "Museum"),
+    (0xc, "Party"),
+    (0xd, "Flower"),
+    (0xe, "Text"),
+    (0xf, "Natural Light & Flash"),
+    (0x10, "Beach"),
+    (0x11, "Snow"),
+    (0x12, "Fireworks"),
+    (0x13, "Underwater"),
+    (0x14, "Portrait with Skin Correction"),
+    (0x16, "Panorama"),
+    (0x17, "Night (tripod)"),
+    (0x18, "Pro Low-light"),
+    (0x19, "Pro Focus"),
+    (0x1a, "Portrait 2"),
+    (0x1b, "Dog Face Detection"),
+    (0x1c, "Cat Face Detection"),
+    (0x30, "HDR"),
+    (0x40, "Advanced Filter"),
+    (0x100, "Aperture-priority AE"),
+    (0x200, "Shutter speed priority AE"),
+    (0x300, "Manual"),
+];
+
+const GRAIN_EFFECT_SIZE: &[(i64, &str)] = &[(0, "Off"), (16, "Small"), (32, "Large")];
+
+const CROP_MODE: &[(i64, &str)] = &[
+    (0, "n/a"),
+    (1, "Full-frame on GFX"),
+    (2, "Sports Finder Mode"),
+    (4, "Electronic Shutter 1.25x Crop"),
+];
+
+const SHUTTER_TYPE: &[(i64, &str)] = &[
+    (0, "Mechanical"),
+    (1, "Electronic"),
+    (2, "Electronic (long shutter speed)"),
+    (3, "Electronic Front Curtain"),
+];
+
+const AUTO_BRACKETING: &[(i64, &str)] = &[(0, "Off"), (1, "On"), (2, "Pre-shot")];
+
+const PANORAMA_DIRECTION: &[(i64, &str)] = &[(1, "Right"), (2, "Up"), (3, "Left"), (4, "Down")];
+
+const ADVANCED_FILTER: &[(i64, &str)] = &[
+    (0x10000, "Pop Color"),
+    (0x20000, "Hi Key"),
+    (0x30000, "Toy Camera"),
+    (0x40000, "Miniature"),
+    (0x50000, "Dynamic Tone"),
+    (0x60001, "Partial Color Red"),
+    (0x60002, "Partial Color Yellow"),
+    (0x60003, "Partial Color Green"),
+    (0x60004, "Partial Color Blue"),
+    (0x60005, "Partial Color Orange"),
+    (0x60006, "Partial Color Purple"),
+    (0x70000, "Soft Focus"),
+    (0x90000, "Low Key"),
+];
+
+const DYNAMIC_RANGE: &[(i64, &str)] = &[(1, "Standard"), (3, "Wide")];
+
+const FILM_MODE: &[(i64, &str)] = &[
+    (0x000, "F0/Standard (Provia)"),
+    (0x100, "F1/Studio Portrait"),
+    (0x110, "F1a/Studio Portrait Enhanced Saturation"),
+    (0x120, "F1b/Studio Portrait Smooth Skin Tone (Astia)"),
+    (0x130, "F1c/Studio Portrait Increased Sharpness"),
+    (0x200, "F2/Fujichrome (Velvia)"),
+    (0x300, "F3/Studio Portrait Ex"),
+    (0x400, "F4/Velvia"),
+    (0x500, "Pro Neg. Std"),
+    (0x501, "Pro Neg. Hi"),
+    (0x600, "Classic Chrome"),
+    (0x700, "Eterna"),
+    (0x800, "Classic Negative"),
+    (0x900, "Bleach Bypass"),
+    (0xa00, "Nostalgic Neg"),
+    (0xb00, "Reala ACE"),
+];
+
+const DYNAMIC_RANGE_SETTING: &[(i64, &str)] = &[
+    (0x000, "Auto"),
+    (0x001, "Manual"),
+    (0x100, "Standard (100%)"),
+    (0x200, "Wide1 (230%)"),
+    (0x201, "Wide2 (400%)"),
+    (0x8000, "Film Simulation"),
+];
+
+const IMAGE_GENERATION: &[(i64, &str)] = &[(0, "Original Image"), (1, "Re-developed from RAW")];
+
+const D_RANGE_PRIORITY: &[(i64, &str)] = &[(0, "Auto"), (1, "Fixed")];
+
+const VIDEO_RECORDING_MODE: &[(i64, &str)] = &[
+    (0x00, "Normal"),
+    (0x10, "F-Log"),
+    (0x20, "HLG"),
+    (0x30, "F-Log2"),
+];
+
+const BLUR_WARNING: &[(i64, &str)] = &[(0, "None"), (1, "Blur Warning")];
+
+const FOCUS_WARNING: &[(i64, &str)] = &[(0, "Good"), (1, "Out of focus")];
+
+const EXPOSURE_WARNING: &[(i64, &str)] = &[(0, "Good"), (1, "Bad exposure")];
+
+/// Tags of the Fujifilm MakerNote IFD, named and printed like exiftool's
+/// `FujiFilm::Main` table so the `FromExifData` impls can read them.
+pub const FUJIFILM_TAGS: &[TagDef] = &[
+    TagDef {
+        tag: 0x0000,
+        name: "Version",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x0010,
+        name: "InternalSerialNumber",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1000,
+        name: "Quality",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1001,
+        name: "Sharpness",
+        conv: Conv::Lookup(SHARPNESS),
+    },
+    TagDef {
+        tag: 0x1002,
+        name: "WhiteBalance",
+        conv: Conv::Lookup(WHITE_BALANCE),
+    },
+    TagDef {
+        tag: 0x1003,
+        name: "Saturation",
+        conv: Conv::Lookup(SATURATION),
+    },
+    TagDef {
+        tag: 0x1004,
+        name: "Contrast",
+        conv: Conv::Lookup(CONTRAST),
+    },
+    TagDef {
+        tag: 0x1005,
+        name: "ColorTemperature",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x100a,
+        name: "WhiteBalanceFineTune",
+        conv: Conv::Custom(white_balance_fine_tune),
+    },
+    TagDef {
+        tag: 0x100b,
+        name: "NoiseReduction",
+        conv: Conv::Lookup(NOISE_REDUCTION),
+    },
+    // Newer bodies store High ISO NR under 0x100e, exiftool names both tags
+    // `NoiseReduction` and this one, being later in the IFD, wins.
+    TagDef {
+        tag: 0x100e,
+        name: "NoiseReduction",
+        conv: Conv::Lookup(HIGH_ISO_NOISE_REDUCTION),
+    },
+    TagDef {
+        tag: 0x100f,
+        name: "Clarity",
+        conv: Conv::Custom(clarity),
+    },
+    TagDef {
+        tag: 0x1010,
+        name: "FujiFlashMode",
+        conv: Conv::Lookup(FLASH_MODE),
+    },
+    TagDef {
+        tag: 0x1011,
+        name: "FlashExposureComp",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1020,
+        name: "Macro",
+        conv: Conv::Lookup(OFF_ON),
+    },
+    TagDef {
+        tag: 0x1021,
+        name: "FocusMode",
+        conv: Conv::Lookup(FOCUS_MODE),
+    },
+    TagDef {
+        tag: 0x1022,
+        name: "AFMode",
+        conv: Conv::Lookup(AF_MODE),
+    },
+    TagDef {
+        tag: 0x1023,
+        name: "FocusPixel",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1030,
+        name: "SlowSync",
+        conv: Conv::Lookup(OFF_ON),
+    },
+    TagDef {
+        tag: 0x1031,
+        name: "PictureMode",
+        conv: Conv::Lookup(PICTURE_MODE),
+    },
+    TagDef {
+        tag: 0x1032,
+        name: "ExposureCount",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1040,
+        name: "ShadowTone",
+        conv: Conv::Custom(tone),
+    },
+    TagDef {
+        tag: 0x1041,
+        name: "HighlightTone",
+        conv: Conv::Custom(tone),
+    },
+    TagDef {
+        tag: 0x1045,
+        name: "LensModulationOptimizer",
+        conv: Conv::Lookup(OFF_ON),
+    },
+    TagDef {
+        tag: 0x1047,
+        name: "GrainEffectRoughness",
+        conv: Conv::Lookup(OFF_WEAK_STRONG),
+    },
+    TagDef {
+        tag: 0x1048,
+        name: "ColorChromeEffect",
+        conv: Conv::Lookup(OFF_WEAK_STRONG),
+    },
+    TagDef {
+        tag: 0x1049,
+        name: "BWAdjustment",
+        conv: Conv::Custom(signed),
+    },
+    TagDef {
+        tag: 0x104b,
+        name: "BWMagentaGreen",
+        conv: Conv::Custom(signed),
+    },
+    TagDef {
+        tag: 0x104c,
+        name: "GrainEffectSize",
+        conv: Conv::Lookup(GRAIN_EFFECT_SIZE),
+    },
+    TagDef {
+        tag: 0x104d,
+        name: "CropMode",
+        conv: Conv::Lookup(CROP_MODE),
+    },
+    TagDef {
+        tag: 0x104e,
+        name: "ColorChromeFXBlue",
+        conv: Conv::Lookup(OFF_WEAK_STRONG),
+    },
+    TagDef {
+        tag: 0x1050,
+        name: "ShutterType",
+        conv: Conv::Lookup(SHUTTER_TYPE),
+    },
+    TagDef {
+        tag: 0x1100,
+        name: "AutoBracketing",
+        conv: Conv::Lookup(AUTO_BRACKETING),
+    },
+    TagDef {
+        tag: 0x1101,
+        name: "SequenceNumber",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1105,
+        name: "PixelShiftShots",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1153,
+        name: "PanoramaAngle",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1154,
+        name: "PanoramaDirection",
+        conv: Conv::Lookup(PANORAMA_DIRECTION),
+    },
+    TagDef {
+        tag: 0x1201,
+        name: "AdvancedFilter",
+        conv: Conv::Lookup(ADVANCED_FILTER),
+    },
+    TagDef {
+        tag: 0x1300,
+        name: "BlurWarning",
+        conv: Conv::Lookup(BLUR_WARNING),
+    },
+    TagDef {
+        tag: 0x1301,
+        name: "FocusWarning",
+        conv: Conv::Lookup(FOCUS_WARNING),
+    },
+    TagDef {
+        tag: 0x1302,
+        name: "ExposureWarning",
+        conv: Conv::Lookup(EXPOSURE_WARNING),
+    },
+    TagDef {
+        tag: 0x1400,
+        name: "DynamicRange",
+        conv: Conv::Lookup(DYNAMIC_RANGE),
+    },
+    TagDef {
+        tag: 0x1401,
+        name: "FilmMode",
+        conv: Conv::Lookup(FILM_MODE),
+    },
+    TagDef {
+        tag: 0x1402,
+        name: "DynamicRangeSetting",
+        conv: Conv::Lookup(DYNAMIC_RANGE_SETTING),
+    },
+    TagDef {
+        tag: 0x1403,
+        name: "DevelopmentDynamicRange",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1404,
+        name: "MinFocalLength",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1405,
+        name: "MaxFocalLength",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1406,
+        name: "MaxApertureAtMinFocal",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1407,
+        name: "MaxApertureAtMaxFocal",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x140b,
+        name: "AutoDynamicRange",
+        conv: Conv::Custom(percentage),
+    },
+    TagDef {
+        tag: 0x1431,
+        name: "Rating",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1436,
+        name: "ImageGeneration",
+        conv: Conv::Lookup(IMAGE_GENERATION),
+    },
+    TagDef {
+        tag: 0x1438,
+        name: "ImageCount",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1443,
+        name: "DRangePriority",
+        conv: Conv::Lookup(D_RANGE_PRIORITY),
+    },
+    TagDef {
+        tag: 0x1444,
+        name: "DRangePriorityAuto",
+        conv: Conv::Lookup(WEAK_STRONG),
+    },
+    TagDef {
+        tag: 0x1445,
+        name: "DRangePriorityFixed",
+        conv: Conv::Lookup(WEAK_STRONG),
+    },
+    TagDef {
+        tag: 0x1447,
+        name: "FujiModel",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x1448,
+        name: "FujiModel2",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x3803,
+        name: "VideoRecordingMode",
+        conv: Conv::Lookup(VIDEO_RECORDING_MODE),
+    },
+    TagDef {
+        tag: 0x8000,
+        name: "FileSource",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x8002,
+        name: "OrderNumber",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x8003,
+        name: "FrameNumber",
+        conv: Conv::Raw,
+    },
+];
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("MakerNote does not start with a FUJIFILM header"))]
+    InvalidHeader,
+
+    #[snafu(display("Failed to read MakerNote IFD: {}", source))]
+    Tiff { source: TiffError },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifValue;
+    use crate::native::tiff::{TYPE_SHORT, TYPE_SLONG};
+
+    fn entry(tag: u16, format: u16, data: Vec<u8>) -> Entry {
+        let count = match format {
+            TYPE_SHORT => data.len() / 2,
+            _ => data.len() / 4,
+        };
+
+        Entry::new(tag, format, count as u32, data, ByteOrder::LittleEndian)
+    }
+
+    fn print(tag: u16, entry: &Entry) -> Option<String> {
+        super::super::tags::find(FUJIFILM_TAGS, tag)?.print(entry)
+    }
+
+    #[test]
+    fn it_prints_white_balance_fine_tune() {
+        let mut data = 80i32.to_le_bytes().to_vec();
+        data.extend_from_slice(&(-40i32).to_le_bytes());
+        let e = entry(0x100a, TYPE_SLONG, data);
+
+        assert_eq!(print(0x100a, &e), Some("Red +80, Blue -40".to_string()));
+    }
+
+    #[test]
+    fn it_prints_tones() {
+        let e = entry(0x1041, TYPE_SLONG, 32i32.to_le_bytes().to_vec());
+        assert_eq!(print(0x1041, &e), Some("-2 (soft)".to_string()));
+
+        let e = entry(0x1040, TYPE_SLONG, (-8i32).to_le_bytes().to_vec());
+        assert_eq!(print(0x1040, &e), Some("+0.5".to_string()));
+
+        let e = entry(0x1040, TYPE_SLONG, 0i32.to_le_bytes().to_vec());
+        assert_eq!(print(0x1040, &e), Some("0 (normal)".to_string()));
+    }
+
+    #[test]
+    fn it_prints_lookups() {
+        let e = entry(0x1003, TYPE_SHORT, 0xe0u16.to_le_bytes().to_vec());
+        assert_eq!(print(0x1003, &e), Some("+4 (highest)".to_string()));
+
+        let e = entry(0x1401, TYPE_SHORT, 0xfffu16.to_le_bytes().to_vec());
+        assert_eq!(print(0x1401, &e), Some("Unknown (4095)".to_string()));
+    }
+
+    #[test]
+    fn it_prints_clarity() {
+        let e = entry(0x100f, TYPE_SLONG, (-2000i32).to_le_bytes().to_vec());
+
+        assert_eq!(print(0x100f, &e), Some("-2".to_string()));
+
+        let e = entry(0x100f, TYPE_SLONG, 2000i32.to_le_bytes().to_vec());
+        assert_eq!(print(0x100f, &e), Some("+2".to_string()));
+
+        let e = entry(0x100f, TYPE_SLONG, 0i32.to_le_bytes().to_vec());
+        assert_eq!(print(0x100f, &e), Some("0".to_string()));
+    }
+
+    #[test]
+    fn it_adds_raw_numbers() {
+        let mut data = HEADER.to_vec();
+        data.extend_from_slice(&12u32.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        for (tag, value) in [(0x1001u16, 0x84u16), (0x1401, 0x800)] {
+            data.extend_from_slice(&tag.to_le_bytes());
+            data.extend_from_slice(&TYPE_SHORT.to_le_bytes());
+            data.extend_from_slice(&1u32.to_le_bytes());
+            data.extend_from_slice(&value.to_le_bytes());
+            data.extend_from_slice(&[0, 0]);
+        }
+        data.extend_from_slice(&0u32.to_le_bytes());
+
+        assert_eq!(
+            parse(&data).unwrap(),
+            vec![
+                ExifData::grouped(GROUP, "Sharpness", "+1 (medium hard)".into()),
+                ExifData::grouped(GROUP, "Sharpness#", ExifValue::Int(132)),
+                ExifData::grouped(GROUP, "FilmMode", "Classic Negative".into()),
+                ExifData::grouped(GROUP, "FilmMode#", ExifValue::Int(2048)),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_fails_without_header() {
+        assert!(matches!(parse(b"NIKON\0\0\0"), Err(Error::InvalidHeader)));
+    }
+}
@@ -0,0 +1,441 @@
+//! Pure Rust reader for EXIF & Fujifilm MakerNote metadata, it does not need
+//! exiftool nor Perl to be installed.
+//!
+//! The tags are named and printed the same way exiftool does, so the result
+//! can be passed to [`crate::recipe::read::from_exif`] as-is.
+
+use crate::exif::{
+    source::{self, MetadataSource},
+    ExifData,
+};
+use crate::utils::extension::{is_valid_extension, is_video_extension};
+use crate::utils::magic::{detect_file_type, FileType};
+use log::{debug, trace};
+use snafu::prelude::*;
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+mod bmff;
+pub mod dng;
+pub mod heif;
+pub mod jpeg;
+pub mod makernote;
+pub mod quicktime;
+pub mod raf;
+mod tags;
+pub mod tiff;
+
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_MAKER_NOTE: u16 = 0x927c;
+
+// exiftool's family 1 groups, the MakerNote ones are `makernote::GROUP`
+const GROUP_IFD0: &str = "IFD0";
+const GROUP_EXIF_IFD: &str = "ExifIFD";
+const GROUP_FILE: &str = "File";
+
+/// Reads Metadata from a JPEG, HEIF, RAF or MOV/MP4 File without spawning
+/// exiftool.
+///
+/// # Example
+///
+/// ```
+/// use fuji::native;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // Read metadata from a Fujifilm image
+/// let path = std::path::Path::new("tests/img/DSCF5230.JPG");
+/// let metadata = native::read_metadata(&path)?;
+///
+/// println!("Metadata: {:?}", metadata);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_metadata<P>(img_path: &P) -> Result<Vec<ExifData>, Error>
+where
+    P: AsRef<Path>,
+{
+    let img_path = img_path.as_ref();
+
+    if !is_valid_extension(img_path) {
+        return Err(Error::Path {
+            path: img_path.to_string_lossy().to_string(),
+        });
+    }
+
+    if is_video_extension(img_path) {
+        let file = std::fs::File::open(img_path).context(ReadSnafu)?;
+
+        return read_quicktime(BufReader::new(file));
+    }
+
+    let data = std::fs::read(img_path).context(ReadSnafu)?;
+
+    match detect_file_type(&data) {
+        Some(FileType::Jpeg) => read_jpeg(&data),
+        Some(FileType::Raf) => read_raf(&data),
+        Some(FileType::Heif) => read_heif(&data),
+        Some(FileType::Tiff) => read_converted(&data),
+        Some(FileType::QuickTime) => read_quicktime(Cursor::new(&data)),
+        None => Err(Error::Unsupported {
+            path: img_path.to_string_lossy().to_string(),
+        }),
+    }
+}
+
+/// Reads Metadata from the bytes of a JPEG, HEIF, RAF or MOV/MP4 File, the file
+/// type is detected from its first bytes.
+///
+/// # Example
+///
+/// ```
+/// use fuji::native;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let data = std::fs::read("tests/img/DSCF5230.JPG")?;
+/// let metadata = native::read_metadata_from_bytes(&data)?;
+///
+/// println!("Metadata: {:?}", metadata);
+///
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_metadata_from_bytes(data: &[u8]) -> Result<Vec<ExifData>, Error> {
+    match detect_file_type(data) {
+        Some(FileType::Jpeg) => read_jpeg(data),
+        Some(FileType::Raf) => read_raf(data),
+        Some(FileType::Heif) => read_heif(data),
+        Some(FileType::Tiff) => read_converted(data),
+        Some(FileType::QuickTime) => read_quicktime(Cursor::new(data)),
+        None => Err(Error::UnsupportedData),
+    }
+}
+
+/// Reads Metadata from a JPEG, HEIF, RAF or MOV/MP4 File, reading it to the end
+/// first.
+pub fn read_metadata_from_reader<R>(mut reader: R) -> Result<Vec<ExifData>, Error>
+where
+    R: Read,
+{
+    let mut data = vec![];
+    reader.read_to_end(&mut data).context(ReadSnafu)?;
+
+    read_metadata_from_bytes(&data)
+}
+
+/// [`MetadataSource`] backed by the native reader.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NativeReader;
+
+impl MetadataSource for NativeReader {
+    fn read_metadata(&mut self, img_path: &Path) -> Result<Vec<ExifData>, source::Error> {
+        read_metadata(&img_path).context(source::NativeSnafu)
+    }
+}
+
+/// Reads Metadata from the bytes of a JPEG File.
+pub fn read_jpeg(data: &[u8]) -> Result<Vec<ExifData>, Error> {
+    let exif = jpeg::find_exif(data).context(JpegSnafu)?;
+
+    read_tiff(exif)
+}
+
+/// Reads Metadata from the bytes of a HEIF (`.HIF`) File.
+pub fn read_heif(data: &[u8]) -> Result<Vec<ExifData>, Error> {
+    let exif = heif::find_exif(data).context(HeifSnafu)?;
+
+    read_tiff(exif)
+}
+
+/// Reads Metadata from the bytes of a RAF File.
+///
+/// The EXIF & MakerNote come from the embedded JPEG preview, the RAF header
+/// adds the raw image size and the RAF (firmware) version.
+pub fn read_raf(data: &[u8]) -> Result<Vec<ExifData>, Error> {
+    let raf = raf::Raf::parse(data).context(RafSnafu)?;
+    let exif = raf.exif().context(RafSnafu)?;
+
+    let mut fields = read_tiff(exif)?;
+    raf.fields().into_iter().for_each(|v| push(&mut fields, v));
+
+    Ok(fields)
+}
+
+/// Reads Metadata from the bytes of a DNG or TIFF File converted from a raw,
+/// e.g. by Adobe DNG Converter or X RAW Studio.
+///
+/// Like exiftool, the `FileType` is added (`DNG` or `TIFF`), which is how
+/// [`crate::recipe::FujifilmRecipeDetails::converted`] is detected.
+pub fn read_converted(data: &[u8]) -> Result<Vec<ExifData>, Error> {
+    let mut fields = read_tiff(data)?;
+
+    let file_type = if fields.iter().any(|e| e.tag() == "DNGVersion") {
+        "DNG"
+    } else {
+        "TIFF"
+    };
+    push(
+        &mut fields,
+        ExifData::grouped(GROUP_FILE, "FileType", file_type.into()),
+    );
+
+    Ok(fields)
+}
+
+/// Reads the tags of a TIFF structure (IFD0, the EXIF sub-IFD and the
+/// Fujifilm MakerNote). The MakerNote of a DNG is read from its
+/// `DNGPrivateData` when the EXIF IFD has none.
+///
+/// Every value is tagged with its exiftool family 1 group (`IFD0`, `ExifIFD`,
+/// `FujiFilm`), a tag repeating in several groups is kept in each of them.
+/// Looked up without a group, the MakerNote value takes precedence over its
+/// generic EXIF counterpart, see [`crate::exif::ExifMap`].
+pub fn read_tiff(data: &[u8]) -> Result<Vec<ExifData>, Error> {
+    let tiff = tiff::Tiff::parse(data).context(TiffSnafu)?;
+    let ifd0 = tiff.ifd(tiff.first_ifd).context(TiffSnafu)?;
+
+    read_ifds(&ifd0, |offset| tiff.ifd(offset))
+}
+
+/// Reads Metadata from a QuickTime (MOV) or MP4 File written by a Fujifilm
+/// camera.
+///
+/// The camera stores an EXIF IFD with the Fujifilm MakerNote in the `MVTG`
+/// atom of `moov/udta`. Only the `moov` box is read, the video data is
+/// skipped.
+pub fn read_quicktime<R>(mut reader: R) -> Result<Vec<ExifData>, Error>
+where
+    R: Read + Seek,
+{
+    let moov = quicktime::read_moov(&mut reader).context(QuickTimeSnafu)?;
+    let mvtg = quicktime::find_mvtg(&moov).context(QuickTimeSnafu)?;
+
+    let order = tiff::ByteOrder::LittleEndian;
+    let ifd0 = tiff::read_ifd(mvtg, 0, 0, order).context(TiffSnafu)?;
+
+    read_ifds(&ifd0, |offset| tiff::read_ifd(mvtg, 0, offset, order))
+}
+
+/// Reads the tags of `ifd0`, of the EXIF sub-IFD it points to, read with
+/// `sub_ifd`, and of the MakerNote, see [`read_tiff`].
+fn read_ifds<F>(ifd0: &tiff::Ifd, sub_ifd: F) -> Result<Vec<ExifData>, Error>
+where
+    F: Fn(usize) -> Result<tiff::Ifd, tiff::Error>,
+{
+    let mut fields: Vec<ExifData> = vec![];
+    push_entries(&mut fields, GROUP_IFD0, &ifd0.entries);
+
+    let exif_ifd = if let Some(offset) = ifd0.get(TAG_EXIF_IFD).and_then(|e| e.offset()) {
+        Some(sub_ifd(offset).context(TiffSnafu)?)
+    } else {
+        None
+    };
+
+    if let Some(exif_ifd) = &exif_ifd {
+        push_entries(&mut fields, GROUP_EXIF_IFD, &exif_ifd.entries);
+    }
+
+    let maker_note = exif_ifd
+        .as_ref()
+        .and_then(|ifd| ifd.get(TAG_MAKER_NOTE))
+        .or_else(|| ifd0.get(TAG_MAKER_NOTE))
+        .map(|e| e.data.as_slice())
+        .or_else(|| {
+            ifd0.get(dng::TAG_DNG_PRIVATE_DATA)
+                .and_then(|e| dng::find_maker_note(&e.data))
+        });
+
+    if let Some(maker_note) = maker_note {
+        match makernote::parse(maker_note) {
+            Ok(values) => values.into_iter().for_each(|v| push(&mut fields, v)),
+            Err(makernote::Error::InvalidHeader) => {
+                debug!("MakerNote is not a Fujifilm MakerNote, skipping it");
+            }
+            Err(e) => return Err(Error::MakerNote { source: e }),
+        }
+    }
+
+    Ok(fields)
+}
+
+fn push_entries(fields: &mut Vec<ExifData>, group: &str, entries: &[tiff::Entry]) {
+    for entry in entries {
+        let Some(def) = tags::find(tags::EXIF_TAGS, entry.tag) else {
+            continue;
+        };
+
+        if let Some(value) = def.value(entry) {
+            trace!("EXIF 0x{:04x} {}: {:?}", entry.tag, def.name, value);
+
+            push(fields, ExifData::grouped(group, def.name, value));
+        }
+    }
+}
+
+/// Adds `exif`, replacing the value of the same tag in the same group.
+fn push(fields: &mut Vec<ExifData>, exif: ExifData) {
+    if let Some(existing) = fields
+        .iter_mut()
+        .find(|e| e.tag() == exif.tag() && e.group() == exif.group())
+    {
+        *existing = exif;
+    } else {
+        fields.push(exif);
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid File: {:?}", path))]
+    Path { path: String },
+
+    #[snafu(display("Unsupported File: {:?}", path))]
+    Unsupported { path: String },
+
+    #[snafu(display("Unsupported data, expected a JPEG, HEIF, RAF or MOV/MP4 file"))]
+    UnsupportedData,
+
+    #[snafu(display("Failed to read file: {:?}", source))]
+    Read { source: std::io::Error },
+
+    #[snafu(display("Failed to read JPEG: {}", source))]
+    Jpeg { source: jpeg::Error },
+
+    #[snafu(display("Failed to read HEIF: {}", source))]
+    Heif { source: heif::Error },
+
+    #[snafu(display("Failed to read QuickTime: {}", source))]
+    QuickTime { source: quicktime::Error },
+
+    #[snafu(display("Failed to read RAF: {}", source))]
+    Raf { source: raf::Error },
+
+    #[snafu(display("Failed to read TIFF structure: {}", source))]
+    Tiff { source: tiff::Error },
+
+    #[snafu(display("Failed to read MakerNote: {}", source))]
+    MakerNote { source: makernote::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::FindExifData;
+
+    fn ifd_entry(tag: u16, format: u16, count: u32, value: [u8; 4]) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&tag.to_le_bytes());
+        data.extend_from_slice(&format.to_le_bytes());
+        data.extend_from_slice(&count.to_le_bytes());
+        data.extend_from_slice(&value);
+
+        data
+    }
+
+    /// TIFF with IFD0 (Make), an EXIF IFD (WhiteBalance) and a Fujifilm
+    /// MakerNote (WhiteBalance & FilmMode).
+    fn fujifilm_tiff() -> Vec<u8> {
+        let mut maker_note = b"FUJIFILM".to_vec();
+        maker_note.extend_from_slice(&12u32.to_le_bytes());
+        maker_note.extend_from_slice(&2u16.to_le_bytes());
+        maker_note.extend(ifd_entry(0x1002, 3, 1, [0xf0, 0x0f, 0, 0]));
+        maker_note.extend(ifd_entry(0x1401, 3, 1, [0x00, 0x08, 0, 0]));
+        maker_note.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut data = b"II*\0".to_vec();
+        data.extend_from_slice(&8u32.to_le_bytes());
+        // IFD0 at 8: 2 entries, ends at 8 + 2 + 24 + 4 = 38
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend(ifd_entry(0x010f, 2, 4, *b"FUJI"));
+        data.extend(ifd_entry(TAG_EXIF_IFD, 4, 1, 38u32.to_le_bytes()));
+        data.extend_from_slice(&0u32.to_le_bytes());
+        // EXIF IFD at 38: 2 entries, ends at 38 + 2 + 24 + 4 = 68
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend(ifd_entry(
+            TAG_MAKER_NOTE,
+            7,
+            maker_note.len() as u32,
+            68u32.to_le_bytes(),
+        ));
+        data.extend(ifd_entry(0xa403, 3, 1, [1, 0, 0, 0]));
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend(maker_note);
+
+        data
+    }
+
+    #[test]
+    fn it_reads_exif_and_maker_note() {
+        let data = fujifilm_tiff();
+        let fields = read_tiff(&data).unwrap();
+        let fields = fields.as_slice();
+
+        assert_eq!(
+            fields.find("Make"),
+            Some(ExifData::grouped(GROUP_IFD0, "Make", "FUJI".into()))
+        );
+        assert_eq!(
+            fields.find("FilmMode"),
+            Some(ExifData::grouped(
+                makernote::GROUP,
+                "FilmMode",
+                "Classic Negative".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn it_prefers_maker_note_values() {
+        let data = fujifilm_tiff();
+        let fields = read_tiff(&data).unwrap();
+
+        let white_balance: Vec<(Option<&str>, String)> = fields
+            .iter()
+            .filter(|e| e.tag() == "WhiteBalance")
+            .map(|e| (e.group(), e.value().to_string()))
+            .collect();
+
+        assert_eq!(
+            white_balance,
+            vec![
+                (Some(GROUP_EXIF_IFD), "Manual".to_string()),
+                (Some(makernote::GROUP), "Kelvin".to_string()),
+            ]
+        );
+        assert_eq!(
+            fields.find("WhiteBalance").map(|e| e.value().to_string()),
+            Some("Kelvin".to_string())
+        );
+    }
+
+    #[test]
+    fn it_reads_converted_tiff() {
+        let data = fujifilm_tiff();
+        let fields = read_metadata_from_bytes(&data).unwrap();
+
+        assert_eq!(
+            fields.find("File:FileType"),
+            Some(ExifData::grouped(GROUP_FILE, "FileType", "TIFF".into()))
+        );
+        assert_eq!(
+            fields
+                .find("MakerNotes:FilmMode")
+                .map(|e| e.value().to_string()),
+            Some("Classic Negative".to_string())
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_data() {
+        let result = read_metadata_from_reader(&b"GIF89a"[..]);
+
+        assert!(matches!(result, Err(Error::UnsupportedData)));
+    }
+
+    #[test]
+    fn it_rejects_invalid_extensions() {
+        let result = read_metadata(&Path::new("tests/img/foo.png"));
+
+        assert!(matches!(result, Err(Error::Path { .. })));
+    }
+}
@@ -0,0 +1,186 @@
+use super::bmff::{be, find_box};
+use log::trace;
+use snafu::prelude::*;
+use std::io::{Read, Seek, SeekFrom};
+
+const MOOV: &[u8; 4] = b"moov";
+const UDTA: &[u8; 4] = b"udta";
+const MVTG: &[u8; 4] = b"MVTG";
+
+/// Fujifilm writes a 16 byte header in front of the IFD of the `MVTG` atom.
+const MVTG_HEADER: usize = 16;
+
+/// The `moov` box only holds the track tables, anything larger is not a
+/// movie the camera wrote.
+const MAX_MOOV_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Reads the body of the top-level `moov` box of a QuickTime (MOV) or MP4
+/// file.
+///
+/// The other boxes, `mdat` with the video & audio streams in particular, are
+/// skipped without being read.
+pub fn read_moov<R>(reader: &mut R) -> Result<Vec<u8>, Error>
+where
+    R: Read + Seek,
+{
+    loop {
+        let start = reader.stream_position().context(ReadSnafu)?;
+
+        let mut header = [0u8; 8];
+        match reader.read_exact(&mut header) {
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                return MissingBoxSnafu { kind: "moov" }.fail();
+            }
+            result => result.context(ReadSnafu)?,
+        }
+
+        let kind = &header[4..8];
+        let (header_len, size) = match be(&header[..4]) {
+            // Box extends to the end of the file
+            0 => (8, None),
+            // 64-bit size follows the type
+            1 => {
+                let mut size = [0u8; 8];
+                reader.read_exact(&mut size).context(ReadSnafu)?;
+
+                (16, Some(be(&size)))
+            }
+            size => (8, Some(size)),
+        };
+
+        let len = match size {
+            Some(size) => {
+                ensure!(size >= header_len, InvalidSizeSnafu { size });
+                Some(size - header_len)
+            }
+            None => None,
+        };
+
+        trace!(
+            "QuickTime box {:?}: {:?} bytes",
+            String::from_utf8_lossy(kind),
+            len
+        );
+
+        if kind == MOOV {
+            let len = len.unwrap_or(MAX_MOOV_SIZE);
+            ensure!(len <= MAX_MOOV_SIZE, InvalidSizeSnafu { size: len });
+
+            let mut body = vec![];
+            reader.take(len).read_to_end(&mut body).context(ReadSnafu)?;
+
+            return Ok(body);
+        }
+
+        let len = len.context(MissingBoxSnafu { kind: "moov" })?;
+        let offset = i64::try_from(len)
+            .ok()
+            .context(InvalidSizeSnafu { size: len })?;
+        let end = reader.seek(SeekFrom::Current(offset)).context(ReadSnafu)?;
+
+        // Never go back to a box already read, which would loop forever
+        ensure!(end > start, InvalidSizeSnafu { size: len });
+    }
+}
+
+/// Finds the IFD of the Fujifilm `MVTG` atom in the body of the `moov` box.
+///
+/// The IFD is always little-endian, starts right after the header and its
+/// value offsets are relative to its own start, like exiftool reads it.
+pub fn find_mvtg(moov: &[u8]) -> Result<&[u8], Error> {
+    let udta = find_box(moov, UDTA).context(MissingBoxSnafu { kind: "udta" })?;
+    let mvtg = find_box(udta.body, MVTG).context(MissingBoxSnafu { kind: "MVTG" })?;
+
+    mvtg.body.get(MVTG_HEADER..).context(TruncatedSnafu {
+        offset: MVTG_HEADER,
+    })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Failed to read QuickTime file: {}", source))]
+    Read { source: std::io::Error },
+
+    #[snafu(display("QuickTime file has no {} box", kind))]
+    MissingBox { kind: &'static str },
+
+    #[snafu(display("Invalid QuickTime box size: {}", size))]
+    InvalidSize { size: u64 },
+
+    #[snafu(display("QuickTime data at offset {} is truncated", offset))]
+    Truncated { offset: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn iso_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(kind);
+        data.extend_from_slice(body);
+
+        data
+    }
+
+    fn build_mov(mvtg: &[u8]) -> Vec<u8> {
+        let mut udta = iso_box(b"FFMV", b"FUJIFILM DIGITAL CAMERA X-T5");
+        udta.extend(iso_box(MVTG, mvtg));
+
+        let mut moov = iso_box(b"mvhd", &[0; 100]);
+        moov.extend(iso_box(UDTA, &udta));
+
+        let mut data = iso_box(b"ftyp", b"qt  \0\0\0\0qt  ");
+        data.extend(iso_box(b"wide", b""));
+        data.extend(iso_box(b"mdat", &[0; 1024]));
+        data.extend(iso_box(MOOV, &moov));
+
+        data
+    }
+
+    #[test]
+    fn it_finds_the_mvtg_ifd() {
+        let mut mvtg = vec![0; MVTG_HEADER];
+        mvtg.extend_from_slice(b"\x01\0IFD");
+
+        let data = build_mov(&mvtg);
+        let moov = read_moov(&mut Cursor::new(&data)).unwrap();
+
+        assert_eq!(find_mvtg(&moov).unwrap(), b"\x01\0IFD");
+    }
+
+    #[test]
+    fn it_fails_without_moov() {
+        let mut data = iso_box(b"ftyp", b"qt  \0\0\0\0qt  ");
+        data.extend(iso_box(b"mdat", &[0; 16]));
+
+        assert!(matches!(
+            read_moov(&mut Cursor::new(&data)),
+            Err(Error::MissingBox { kind: "moov" })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_sizes_seeking_backwards() {
+        let mut data = iso_box(b"ftyp", b"qt  \0\0\0\0qt  ");
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(b"mdat");
+        data.extend_from_slice(&u64::MAX.to_be_bytes());
+
+        assert!(matches!(
+            read_moov(&mut Cursor::new(&data)),
+            Err(Error::InvalidSize { .. })
+        ));
+    }
+
+    #[test]
+    fn it_fails_without_mvtg() {
+        let moov = iso_box(UDTA, &iso_box(b"FFMV", b""));
+
+        assert!(matches!(
+            find_mvtg(&moov),
+            Err(Error::MissingBox { kind: "MVTG" })
+        ));
+    }
+}
@@ -0,0 +1,290 @@
+use super::jpeg;
+use super::tiff::slice;
+use crate::exif::ExifData;
+use log::trace;
+use snafu::prelude::*;
+
+pub const MAGIC: &[u8] = b"FUJIFILMCCD-RAW ";
+
+/// exiftool's family 1 group of the RAF header tags.
+const GROUP: &str = "RAF";
+const HEADER_LEN: usize = 0x6c;
+const TAG_RAW_IMAGE_FULL_SIZE: u16 = 0x100;
+const TAG_RAW_IMAGE_CROPPED_SIZE: u16 = 0x111;
+
+/// Directory of a RAF file. All the values are stored big-endian.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RafHeader {
+    /// Format version, e.g. `0201`.
+    pub format_version: String,
+    /// Camera ID, e.g. `FF129502`.
+    pub camera_id: String,
+    /// Camera model, e.g. `X100VI`.
+    pub camera_model: String,
+    /// Firmware (RAF) version, e.g. `0100`.
+    pub firmware: String,
+    pub jpeg_offset: usize,
+    pub jpeg_length: usize,
+    pub meta_offset: usize,
+    pub meta_length: usize,
+    pub cfa_offset: usize,
+    pub cfa_length: usize,
+}
+
+/// Size of the raw image data, as stored in the RAF metadata block.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RawDimensions {
+    pub width: u16,
+    pub height: u16,
+}
+
+#[derive(Debug)]
+pub struct Raf<'a> {
+    pub header: RafHeader,
+    data: &'a [u8],
+}
+
+impl<'a> Raf<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        ensure!(data.starts_with(MAGIC), NotRafSnafu);
+        ensure!(data.len() >= HEADER_LEN, TruncatedSnafu { offset: 0usize });
+
+        let header = RafHeader {
+            format_version: text(&data[0x10..0x14]),
+            camera_id: text(&data[0x14..0x1c]),
+            camera_model: text(&data[0x1c..0x3c]),
+            firmware: text(&data[0x3c..0x40]),
+            jpeg_offset: be_u32(&data[0x54..]),
+            jpeg_length: be_u32(&data[0x58..]),
+            meta_offset: be_u32(&data[0x5c..]),
+            meta_length: be_u32(&data[0x60..]),
+            cfa_offset: be_u32(&data[0x64..]),
+            cfa_length: be_u32(&data[0x68..]),
+        };
+
+        trace!("RafHeader: {:?}", header);
+
+        Ok(Raf { header, data })
+    }
+
+    /// Embedded full size JPEG preview.
+    pub fn jpeg(&self) -> Result<&'a [u8], Error> {
+        let offset = self.header.jpeg_offset;
+
+        slice(self.data, offset, self.header.jpeg_length)
+            .ok()
+            .context(TruncatedSnafu { offset })
+    }
+
+    /// TIFF structure holding the EXIF & MakerNote of the embedded JPEG.
+    pub fn exif(&self) -> Result<&'a [u8], Error> {
+        jpeg::find_exif(self.jpeg()?).context(JpegSnafu)
+    }
+
+    /// Full raw image size, including borders.
+    pub fn raw_dimensions(&self) -> Option<RawDimensions> {
+        self.meta_dimensions(TAG_RAW_IMAGE_FULL_SIZE)
+    }
+
+    /// Raw image size after cropping the borders.
+    pub fn cropped_dimensions(&self) -> Option<RawDimensions> {
+        self.meta_dimensions(TAG_RAW_IMAGE_CROPPED_SIZE)
+    }
+
+    /// Values of the RAF header & metadata block, named like exiftool does.
+    pub fn fields(&self) -> Vec<ExifData> {
+        let mut fields = vec![
+            ExifData::grouped(GROUP, "RAFVersion", self.header.firmware.as_str().into()),
+            ExifData::grouped(GROUP, "RAFCameraID", self.header.camera_id.as_str().into()),
+            ExifData::grouped(
+                GROUP,
+                "RAFCameraModel",
+                self.header.camera_model.as_str().into(),
+            ),
+        ];
+
+        if let Some(size) = self.raw_dimensions() {
+            let value = format!("{}x{}", size.width, size.height);
+            fields.push(ExifData::grouped(GROUP, "RawImageFullSize", value.into()));
+        }
+
+        if let Some(size) = self.cropped_dimensions() {
+            let value = format!("{}x{}", size.width, size.height);
+            fields.push(ExifData::grouped(
+                GROUP,
+                "RawImageCroppedSize",
+                value.into(),
+            ));
+        }
+
+        fields
+    }
+
+    /// The metadata block is a big-endian list of `(tag, size, value)`
+    /// records, prefixed by the number of records.
+    fn meta_records(&self) -> Vec<(u16, &'a [u8])> {
+        let offset = self.header.meta_offset;
+        let Ok(meta) = slice(self.data, offset, self.header.meta_length) else {
+            return vec![];
+        };
+
+        if meta.len() < 4 {
+            return vec![];
+        }
+
+        let count = be_u32(meta);
+        let mut pos = 4;
+        let mut records = vec![];
+
+        for _ in 0..count {
+            if pos + 4 > meta.len() {
+                break;
+            }
+
+            let tag = u16::from_be_bytes([meta[pos], meta[pos + 1]]);
+            let size = u16::from_be_bytes([meta[pos + 2], meta[pos + 3]]) as usize;
+            pos += 4;
+
+            let Some(value) = meta.get(pos..pos + size) else {
+                break;
+            };
+
+            records.push((tag, value));
+            pos += size;
+        }
+
+        records
+    }
+
+    fn meta_dimensions(&self, tag: u16) -> Option<RawDimensions> {
+        let (_, value) = self.meta_records().into_iter().find(|(t, _)| *t == tag)?;
+
+        if value.len() < 4 {
+            return None;
+        }
+
+        Some(RawDimensions {
+            height: u16::from_be_bytes([value[0], value[1]]),
+            width: u16::from_be_bytes([value[2], value[3]]),
+        })
+    }
+}
+
+fn be_u32(bytes: &[u8]) -> usize {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+}
+
+fn text(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Not a RAF file"))]
+    NotRaf,
+
+    #[snafu(display("RAF data at offset {} is truncated", offset))]
+    Truncated { offset: usize },
+
+    #[snafu(display("Failed to read embedded JPEG: {}", source))]
+    Jpeg { source: jpeg::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a RAF with the given JPEG preview and a metadata block holding
+    /// the full & cropped raw sizes.
+    fn build_raf(jpeg: &[u8]) -> Vec<u8> {
+        let mut meta = vec![];
+        meta.extend_from_slice(&2u32.to_be_bytes());
+        meta.extend_from_slice(&TAG_RAW_IMAGE_FULL_SIZE.to_be_bytes());
+        meta.extend_from_slice(&4u16.to_be_bytes());
+        meta.extend_from_slice(&4182u16.to_be_bytes());
+        meta.extend_from_slice(&6384u16.to_be_bytes());
+        meta.extend_from_slice(&TAG_RAW_IMAGE_CROPPED_SIZE.to_be_bytes());
+        meta.extend_from_slice(&4u16.to_be_bytes());
+        meta.extend_from_slice(&4160u16.to_be_bytes());
+        meta.extend_from_slice(&6240u16.to_be_bytes());
+
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(b"0201");
+        data.extend_from_slice(b"FF129502");
+        let mut model = b"X100VI".to_vec();
+        model.resize(32, 0);
+        data.extend(model);
+        data.extend_from_slice(b"0100");
+        data.resize(0x54, 0);
+
+        let jpeg_offset = HEADER_LEN;
+        let meta_offset = jpeg_offset + jpeg.len();
+
+        data.extend_from_slice(&(jpeg_offset as u32).to_be_bytes());
+        data.extend_from_slice(&(jpeg.len() as u32).to_be_bytes());
+        data.extend_from_slice(&(meta_offset as u32).to_be_bytes());
+        data.extend_from_slice(&(meta.len() as u32).to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(jpeg);
+        data.extend(meta);
+
+        data
+    }
+
+    #[test]
+    fn it_parses_the_header() {
+        let data = build_raf(&[0xff, 0xd8, 0xff, 0xd9]);
+        let raf = Raf::parse(&data).unwrap();
+
+        assert_eq!(raf.header.format_version, "0201");
+        assert_eq!(raf.header.camera_id, "FF129502");
+        assert_eq!(raf.header.camera_model, "X100VI");
+        assert_eq!(raf.header.firmware, "0100");
+        assert_eq!(raf.header.jpeg_offset, HEADER_LEN);
+        assert_eq!(raf.header.jpeg_length, 4);
+        assert_eq!(raf.jpeg().unwrap(), &[0xff, 0xd8, 0xff, 0xd9]);
+    }
+
+    #[test]
+    fn it_reads_raw_dimensions() {
+        let data = build_raf(&[0xff, 0xd8, 0xff, 0xd9]);
+        let raf = Raf::parse(&data).unwrap();
+
+        assert_eq!(
+            raf.raw_dimensions(),
+            Some(RawDimensions {
+                width: 6384,
+                height: 4182
+            })
+        );
+        assert_eq!(
+            raf.cropped_dimensions(),
+            Some(RawDimensions {
+                width: 6240,
+                height: 4160
+            })
+        );
+        assert!(raf.fields().contains(&ExifData::grouped(
+            GROUP,
+            "RawImageFullSize",
+            "6384x4182".into()
+        )));
+    }
+
+    #[test]
+    fn it_fails_when_not_a_raf() {
+        assert!(matches!(Raf::parse(b"II*\0"), Err(Error::NotRaf)));
+    }
+
+    #[test]
+    fn it_fails_when_jpeg_is_out_of_bounds() {
+        let mut data = build_raf(&[0xff, 0xd8, 0xff, 0xd9]);
+        data.truncate(HEADER_LEN + 2);
+        let raf = Raf::parse(&data).unwrap();
+
+        assert!(matches!(raf.jpeg(), Err(Error::Truncated { .. })));
+    }
+}
@@ -0,0 +1,470 @@
+use super::tiff::{Entry, Number};
+use crate::exif::ExifValue;
+
+/// How the raw value of a tag is turned into the string exiftool would print.
+pub enum Conv {
+    /// Value printed as-is, numbers are joined with spaces.
+    Raw,
+    /// Value looked up in a table, unknown values fall back to `Unknown (v)`.
+    Lookup(&'static [(i64, &'static str)]),
+    /// Custom conversion.
+    Custom(fn(&Entry) -> Option<String>),
+}
+
+pub struct TagDef {
+    pub tag: u16,
+    pub name: &'static str,
+    pub conv: Conv,
+}
+
+impl TagDef {
+    pub fn print(&self, entry: &Entry) -> Option<String> {
+        match &self.conv {
+            Conv::Raw => raw(entry),
+            Conv::Lookup(table) => lookup(entry, table),
+            Conv::Custom(f) => f(entry),
+        }
+    }
+
+    /// Typed value of the tag, [`Conv::Raw`] tags keep their numbers while
+    /// converted ones are strings, like in exiftool's JSON.
+    pub fn value(&self, entry: &Entry) -> Option<ExifValue> {
+        match &self.conv {
+            Conv::Raw => typed(entry),
+            _ => self.print(entry).map(ExifValue::from),
+        }
+    }
+}
+
+pub fn find(table: &'static [TagDef], tag: u16) -> Option<&'static TagDef> {
+    table.iter().find(|def| def.tag == tag)
+}
+
+pub fn raw(entry: &Entry) -> Option<String> {
+    if entry.is_text() {
+        return Some(entry.text());
+    }
+
+    if entry.is_undefined() {
+        let is_text = entry
+            .data
+            .iter()
+            .all(|b| b.is_ascii_graphic() || *b == b' ' || *b == 0);
+
+        return if is_text && entry.data.len() <= 64 {
+            Some(entry.text())
+        } else {
+            Some(format!("(Binary data {} bytes)", entry.data.len()))
+        };
+    }
+
+    let values: Vec<String> = entry.numbers().iter().map(format_number).collect();
+
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.join(" "))
+}
+
+/// Raw value of an entry, keeping its numbers typed. Lists are only used for
+/// entries with more than one number.
+pub fn typed(entry: &Entry) -> Option<ExifValue> {
+    if entry.is_text() {
+        return Some(ExifValue::Str(entry.text()));
+    }
+
+    if entry.is_undefined() {
+        return match raw(entry) {
+            Some(text) if !text.starts_with("(Binary data") => Some(ExifValue::Str(text)),
+            _ => Some(ExifValue::Binary(entry.data.clone())),
+        };
+    }
+
+    let mut values: Vec<ExifValue> = entry
+        .numbers()
+        .iter()
+        .map(|number| match *number {
+            Number::Unsigned(v) => ExifValue::Int(v as i64),
+            Number::Signed(v) => ExifValue::Int(v as i64),
+            Number::Rational(n, d) => ExifValue::Rational(n as i64, d as i64),
+            Number::SRational(n, d) => ExifValue::Rational(n as i64, d as i64),
+            Number::Float(v) => ExifValue::Float(v),
+        })
+        .collect();
+
+    match values.len() {
+        0 => None,
+        1 => values.pop(),
+        _ => Some(ExifValue::List(values)),
+    }
+}
+
+pub fn lookup(entry: &Entry, table: &[(i64, &'static str)]) -> Option<String> {
+    let value = entry.first_i64()?;
+
+    let printed = table
+        .iter()
+        .find(|(k, _)| *k == value)
+        .map(|(_, v)| v.to_string())
+        .unwrap_or_else(|| format!("Unknown ({})", value));
+
+    Some(printed)
+}
+
+pub fn format_number(number: &Number) -> String {
+    match number {
+        Number::Unsigned(v) => v.to_string(),
+        Number::Signed(v) => v.to_string(),
+        _ => match number.as_f64() {
+            Some(v) => format_float(v),
+            None => "undef".to_string(),
+        },
+    }
+}
+
+/// Formats a float without trailing zeros, e.g. `5.6`, `23`, `0.3333333333`.
+pub fn format_float(value: f64) -> String {
+    let rounded = (value * 1e10).round() / 1e10;
+
+    rounded.to_string()
+}
+
+/// Signed values print with an explicit `+`, like exiftool does for
+/// adjustments.
+pub fn signed(entry: &Entry) -> Option<String> {
+    let value = entry.first_i64()?;
+
+    if value > 0 {
+        Some(format!("+{}", value))
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn exposure_time(entry: &Entry) -> Option<String> {
+    let value = entry.first_f64()?;
+
+    if value > 0.0 && value < 0.25001 {
+        Some(format!("1/{}", (1.0 / value).round()))
+    } else {
+        Some(format_float(value))
+    }
+}
+
+fn f_number(entry: &Entry) -> Option<String> {
+    let value = entry.first_f64()?;
+
+    Some(format!("{:.1}", value))
+}
+
+fn focal_length(entry: &Entry) -> Option<String> {
+    let value = entry.first_f64()?;
+
+    Some(format!("{:.1} mm", value))
+}
+
+fn focal_length_35mm(entry: &Entry) -> Option<String> {
+    let value = entry.first_i64()?;
+
+    Some(format!("{} mm", value))
+}
+
+fn exposure_compensation(entry: &Entry) -> Option<String> {
+    let value = entry.first_f64()?;
+
+    if value > 0.0 {
+        Some(format!("+{}", format_float(value)))
+    } else {
+        Some(format_float(value))
+    }
+}
+
+fn dng_version(entry: &Entry) -> Option<String> {
+    Some(super::dng::version(&entry.data))
+}
+
+const ORIENTATION: &[(i64, &str)] = &[
+    (1, "Horizontal (normal)"),
+    (2, "Mirror horizontal"),
+    (3, "Rotate 180"),
+    (4, "Mirror vertical"),
+    (5, "Mirror horizontal and rotate 270 CW"),
+    (6, "Rotate 90 CW"),
+    (7, "Mirror horizontal and rotate 90 CW"),
+    (8, "Rotate 270 CW"),
+];
+
+const RESOLUTION_UNIT: &[(i64, &str)] = &[(1, "None"), (2, "inches"), (3, "cm")];
+
+const EXPOSURE_PROGRAM: &[(i64, &str)] = &[
+    (0, "Not Defined"),
+    (1, "Manual"),
+    (2, "Program AE"),
+    (3, "Aperture-priority AE"),
+    (4, "Shutter speed priority AE"),
+    (5, "Creative (Slow speed)"),
+    (6, "Action (High speed)"),
+    (7, "Portrait"),
+    (8, "Landscape"),
+    (9, "Bulb"),
+];
+
+const METERING_MODE: &[(i64, &str)] = &[
+    (0, "Unknown"),
+    (1, "Average"),
+    (2, "Center-weighted average"),
+    (3, "Spot"),
+    (4, "Multi-spot"),
+    (5, "Multi-segment"),
+    (6, "Partial"),
+    (255, "Other"),
+];
+
+const COLOR_SPACE: &[(i64, &str)] = &[(1, "sRGB"), (2, "Adobe RGB"), (0xffff, "Uncalibrated")];
+
+const EXPOSURE_MODE: &[(i64, &str)] = &[(0, "Auto"), (1, "Manual"), (2, "Auto bracket")];
+
+const AUTO_MANUAL: &[(i64, &str)] = &[(0, "Auto"), (1, "Manual")];
+
+const SCENE_CAPTURE_TYPE: &[(i64, &str)] = &[
+    (0, "Standard"),
+    (1, "Landscape"),
+    (2, "Portrait"),
+    (3, "Night"),
+];
+
+const NORMAL_LOW_HIGH: &[(i64, &str)] = &[(0, "Normal"), (1, "Low"), (2, "High")];
+
+const NORMAL_SOFT_HARD: &[(i64, &str)] = &[(0, "Normal"), (1, "Soft"), (2, "Hard")];
+
+/// Standard tags from IFD0 and the EXIF sub-IFD. Only the tags that are
+/// useful when reading a Fujifilm file are listed, pointers and binary blobs
+/// are left out.
+pub const EXIF_TAGS: &[TagDef] = &[
+    TagDef {
+        tag: 0x010e,
+        name: "ImageDescription",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x010f,
+        name: "Make",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x0110,
+        name: "Model",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x0112,
+        name: "Orientation",
+        conv: Conv::Lookup(ORIENTATION),
+    },
+    TagDef {
+        tag: 0x011a,
+        name: "XResolution",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x011b,
+        name: "YResolution",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x0128,
+        name: "ResolutionUnit",
+        conv: Conv::Lookup(RESOLUTION_UNIT),
+    },
+    TagDef {
+        tag: 0x0131,
+        name: "Software",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x0132,
+        name: "ModifyDate",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x013b,
+        name: "Artist",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x8298,
+        name: "Copyright",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x829a,
+        name: "ExposureTime",
+        conv: Conv::Custom(exposure_time),
+    },
+    TagDef {
+        tag: 0x829d,
+        name: "FNumber",
+        conv: Conv::Custom(f_number),
+    },
+    TagDef {
+        tag: 0x8822,
+        name: "ExposureProgram",
+        conv: Conv::Lookup(EXPOSURE_PROGRAM),
+    },
+    TagDef {
+        tag: 0x8827,
+        name: "ISO",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9000,
+        name: "ExifVersion",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9003,
+        name: "DateTimeOriginal",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9004,
+        name: "CreateDate",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9010,
+        name: "OffsetTime",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9011,
+        name: "OffsetTimeOriginal",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9012,
+        name: "OffsetTimeDigitized",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9201,
+        name: "ShutterSpeedValue",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9202,
+        name: "ApertureValue",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9203,
+        name: "BrightnessValue",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9204,
+        name: "ExposureCompensation",
+        conv: Conv::Custom(exposure_compensation),
+    },
+    TagDef {
+        tag: 0x9205,
+        name: "MaxApertureValue",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x9207,
+        name: "MeteringMode",
+        conv: Conv::Lookup(METERING_MODE),
+    },
+    TagDef {
+        tag: 0x9209,
+        name: "Flash",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0x920a,
+        name: "FocalLength",
+        conv: Conv::Custom(focal_length),
+    },
+    TagDef {
+        tag: 0xa001,
+        name: "ColorSpace",
+        conv: Conv::Lookup(COLOR_SPACE),
+    },
+    TagDef {
+        tag: 0xa002,
+        name: "ExifImageWidth",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0xa003,
+        name: "ExifImageHeight",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0xa402,
+        name: "ExposureMode",
+        conv: Conv::Lookup(EXPOSURE_MODE),
+    },
+    TagDef {
+        tag: 0xa403,
+        name: "WhiteBalance",
+        conv: Conv::Lookup(AUTO_MANUAL),
+    },
+    TagDef {
+        tag: 0xa405,
+        name: "FocalLengthIn35mmFormat",
+        conv: Conv::Custom(focal_length_35mm),
+    },
+    TagDef {
+        tag: 0xa406,
+        name: "SceneCaptureType",
+        conv: Conv::Lookup(SCENE_CAPTURE_TYPE),
+    },
+    TagDef {
+        tag: 0xa408,
+        name: "Contrast",
+        conv: Conv::Lookup(NORMAL_LOW_HIGH),
+    },
+    TagDef {
+        tag: 0xa409,
+        name: "Saturation",
+        conv: Conv::Lookup(NORMAL_LOW_HIGH),
+    },
+    TagDef {
+        tag: 0xa40a,
+        name: "Sharpness",
+        conv: Conv::Lookup(NORMAL_SOFT_HARD),
+    },
+    TagDef {
+        tag: 0xa431,
+        name: "SerialNumber",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0xa432,
+        name: "LensInfo",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0xa433,
+        name: "LensMake",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0xa434,
+        name: "LensModel",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: 0xa435,
+        name: "LensSerialNumber",
+        conv: Conv::Raw,
+    },
+    TagDef {
+        tag: super::dng::TAG_DNG_VERSION,
+        name: "DNGVersion",
+        conv: Conv::Custom(dng_version),
+    },
+];
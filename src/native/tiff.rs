@@ -0,0 +1,370 @@
+use snafu::prelude::*;
+
+const TYPE_BYTE: u16 = 1;
+const TYPE_ASCII: u16 = 2;
+pub const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+const TYPE_RATIONAL: u16 = 5;
+const TYPE_SBYTE: u16 = 6;
+const TYPE_UNDEFINED: u16 = 7;
+const TYPE_SSHORT: u16 = 8;
+pub const TYPE_SLONG: u16 = 9;
+const TYPE_SRATIONAL: u16 = 10;
+const TYPE_FLOAT: u16 = 11;
+const TYPE_DOUBLE: u16 = 12;
+const TYPE_IFD: u16 = 13;
+
+/// Upper bound of entries accepted in a single IFD, anything above this is
+/// treated as a corrupt directory.
+const MAX_ENTRIES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+impl ByteOrder {
+    pub fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+
+        match self {
+            ByteOrder::LittleEndian => u16::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u16::from_be_bytes(bytes),
+        }
+    }
+
+    pub fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+
+        match self {
+            ByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u32::from_be_bytes(bytes),
+        }
+    }
+
+    pub fn u64(&self, bytes: &[u8]) -> u64 {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&bytes[..8]);
+
+        match self {
+            ByteOrder::LittleEndian => u64::from_le_bytes(buf),
+            ByteOrder::BigEndian => u64::from_be_bytes(buf),
+        }
+    }
+}
+
+/// A single numeric component of an IFD entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Unsigned(u32),
+    Signed(i32),
+    Rational(u32, u32),
+    SRational(i32, i32),
+    Float(f64),
+}
+
+impl Number {
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Number::Unsigned(v) => Some(v as i64),
+            Number::Signed(v) => Some(v as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Number::Unsigned(v) => Some(v as f64),
+            Number::Signed(v) => Some(v as f64),
+            Number::Rational(_, 0) | Number::SRational(_, 0) => None,
+            Number::Rational(n, d) => Some(n as f64 / d as f64),
+            Number::SRational(n, d) => Some(n as f64 / d as f64),
+            Number::Float(v) => Some(v),
+        }
+    }
+}
+
+/// An IFD entry with its value bytes already resolved, regardless of whether
+/// they were stored inline or at an offset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub tag: u16,
+    pub format: u16,
+    pub count: u32,
+    pub data: Vec<u8>,
+    order: ByteOrder,
+}
+
+impl Entry {
+    pub fn new(tag: u16, format: u16, count: u32, data: Vec<u8>, order: ByteOrder) -> Self {
+        Entry {
+            tag,
+            format,
+            count,
+            data,
+            order,
+        }
+    }
+
+    pub fn is_text(&self) -> bool {
+        self.format == TYPE_ASCII
+    }
+
+    pub fn is_undefined(&self) -> bool {
+        self.format == TYPE_UNDEFINED
+    }
+
+    /// Value as text, trimming the trailing NUL padding and spaces cameras
+    /// like to leave behind.
+    pub fn text(&self) -> String {
+        let end = self
+            .data
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(self.data.len());
+
+        String::from_utf8_lossy(&self.data[..end])
+            .trim()
+            .to_string()
+    }
+
+    pub fn numbers(&self) -> Vec<Number> {
+        let order = self.order;
+        let size = component_size(self.format).unwrap_or(1);
+
+        self.data
+            .chunks_exact(size)
+            .filter_map(|chunk| match self.format {
+                TYPE_BYTE | TYPE_UNDEFINED => Some(Number::Unsigned(chunk[0] as u32)),
+                TYPE_SBYTE => Some(Number::Signed(chunk[0] as i8 as i32)),
+                TYPE_SHORT => Some(Number::Unsigned(order.u16(chunk) as u32)),
+                TYPE_SSHORT => Some(Number::Signed(order.u16(chunk) as i16 as i32)),
+                TYPE_LONG | TYPE_IFD => Some(Number::Unsigned(order.u32(chunk))),
+                TYPE_SLONG => Some(Number::Signed(order.u32(chunk) as i32)),
+                TYPE_RATIONAL => Some(Number::Rational(
+                    order.u32(&chunk[..4]),
+                    order.u32(&chunk[4..]),
+                )),
+                TYPE_SRATIONAL => Some(Number::SRational(
+                    order.u32(&chunk[..4]) as i32,
+                    order.u32(&chunk[4..]) as i32,
+                )),
+                TYPE_FLOAT => Some(Number::Float(f32::from_bits(order.u32(chunk)) as f64)),
+                TYPE_DOUBLE => Some(Number::Float(f64::from_bits(order.u64(chunk)))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn first_i64(&self) -> Option<i64> {
+        self.numbers().first().and_then(|n| n.as_i64())
+    }
+
+    pub fn first_f64(&self) -> Option<f64> {
+        self.numbers().first().and_then(|n| n.as_f64())
+    }
+
+    /// Offset stored in the entry, used for sub-IFD pointers.
+    pub fn offset(&self) -> Option<usize> {
+        self.first_i64().map(|v| v as usize)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ifd {
+    pub entries: Vec<Entry>,
+    pub next: usize,
+}
+
+impl Ifd {
+    pub fn get(&self, tag: u16) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.tag == tag)
+    }
+}
+
+/// A TIFF structure, offsets inside it are relative to the start of `data`.
+#[derive(Debug)]
+pub struct Tiff<'a> {
+    pub data: &'a [u8],
+    pub order: ByteOrder,
+    pub first_ifd: usize,
+}
+
+impl<'a> Tiff<'a> {
+    /// Parses the TIFF header (`II*\0` or `MM\0*`).
+    pub fn parse(data: &'a [u8]) -> Result<Self, Error> {
+        ensure!(data.len() >= 8, InvalidHeaderSnafu);
+
+        let order = match &data[..2] {
+            b"II" => ByteOrder::LittleEndian,
+            b"MM" => ByteOrder::BigEndian,
+            _ => return Err(Error::InvalidHeader),
+        };
+
+        ensure!(order.u16(&data[2..4]) == 42, InvalidHeaderSnafu);
+
+        let first_ifd = order.u32(&data[4..8]) as usize;
+
+        Ok(Tiff {
+            data,
+            order,
+            first_ifd,
+        })
+    }
+
+    pub fn ifd(&self, offset: usize) -> Result<Ifd, Error> {
+        read_ifd(self.data, 0, offset, self.order)
+    }
+}
+
+/// Reads the IFD found at `base + offset`. Value offsets of the entries are
+/// resolved relative to `base`.
+pub fn read_ifd(data: &[u8], base: usize, offset: usize, order: ByteOrder) -> Result<Ifd, Error> {
+    let start = base + offset;
+    let header = slice(data, start, 2)?;
+    let len = order.u16(header) as usize;
+
+    ensure!(len <= MAX_ENTRIES, TooManyEntriesSnafu { offset: start });
+
+    let mut entries = Vec::with_capacity(len);
+
+    for i in 0..len {
+        let raw = slice(data, start + 2 + i * 12, 12)?;
+
+        let tag = order.u16(&raw[0..2]);
+        let format = order.u16(&raw[2..4]);
+        let count = order.u32(&raw[4..8]);
+
+        let size = match component_size(format) {
+            Some(size) => size,
+            // Unknown formats are skipped, just like exiftool does.
+            None => continue,
+        };
+
+        let total = size
+            .checked_mul(count as usize)
+            .context(TruncatedSnafu { offset: start })?;
+
+        let value = if total <= 4 {
+            &raw[8..8 + total]
+        } else {
+            let value_offset = order.u32(&raw[8..12]) as usize;
+
+            match slice(data, base + value_offset, total) {
+                Ok(v) => v,
+                // A single broken entry should not discard the whole IFD.
+                Err(_) => continue,
+            }
+        };
+
+        entries.push(Entry::new(tag, format, count, value.to_vec(), order));
+    }
+
+    let next = slice(data, start + 2 + len * 12, 4)
+        .map(|b| order.u32(b) as usize)
+        .unwrap_or(0);
+
+    Ok(Ifd { entries, next })
+}
+
+fn component_size(format: u16) -> Option<usize> {
+    match format {
+        TYPE_BYTE | TYPE_ASCII | TYPE_SBYTE | TYPE_UNDEFINED => Some(1),
+        TYPE_SHORT | TYPE_SSHORT => Some(2),
+        TYPE_LONG | TYPE_SLONG | TYPE_FLOAT | TYPE_IFD => Some(4),
+        TYPE_RATIONAL | TYPE_SRATIONAL | TYPE_DOUBLE => Some(8),
+        _ => None,
+    }
+}
+
+pub(crate) fn slice(data: &[u8], offset: usize, len: usize) -> Result<&[u8], Error> {
+    let end = offset.checked_add(len).context(TruncatedSnafu { offset })?;
+
+    data.get(offset..end).context(TruncatedSnafu { offset })
+}
+
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid TIFF header"))]
+    InvalidHeader,
+
+    #[snafu(display("Unexpected end of data at offset {}", offset))]
+    Truncated { offset: usize },
+
+    #[snafu(display("Too many entries in IFD at offset {}", offset))]
+    TooManyEntries { offset: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn little_endian_tiff() -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(b"II");
+        data.extend_from_slice(&42u16.to_le_bytes());
+        data.extend_from_slice(&8u32.to_le_bytes());
+
+        // IFD with 2 entries.
+        data.extend_from_slice(&2u16.to_le_bytes());
+        // Make, ASCII, 9 bytes, stored at offset 38
+        data.extend_from_slice(&0x010fu16.to_le_bytes());
+        data.extend_from_slice(&TYPE_ASCII.to_le_bytes());
+        data.extend_from_slice(&9u32.to_le_bytes());
+        data.extend_from_slice(&38u32.to_le_bytes());
+        // Orientation, SHORT, inline
+        data.extend_from_slice(&0x0112u16.to_le_bytes());
+        data.extend_from_slice(&TYPE_SHORT.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[1, 0, 0, 0]);
+        // Next IFD
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(b"FUJIFILM\0");
+
+        data
+    }
+
+    #[test]
+    fn it_parses_little_endian_ifd() {
+        let data = little_endian_tiff();
+        let tiff = Tiff::parse(&data).unwrap();
+
+        assert_eq!(tiff.order, ByteOrder::LittleEndian);
+
+        let ifd = tiff.ifd(tiff.first_ifd).unwrap();
+
+        assert_eq!(ifd.entries.len(), 2);
+        assert_eq!(ifd.get(0x010f).unwrap().text(), "FUJIFILM");
+        assert_eq!(ifd.get(0x0112).unwrap().first_i64(), Some(1));
+        assert_eq!(ifd.next, 0);
+    }
+
+    #[test]
+    fn it_parses_big_endian_numbers() {
+        let entry = Entry::new(
+            0x9204,
+            TYPE_SRATIONAL,
+            1,
+            vec![0xff, 0xff, 0xff, 0xfd, 0, 0, 0, 3],
+            ByteOrder::BigEndian,
+        );
+
+        assert_eq!(entry.numbers(), vec![Number::SRational(-3, 3)]);
+        assert_eq!(entry.first_f64(), Some(-1.0));
+    }
+
+    #[test]
+    fn it_fails_with_invalid_header() {
+        assert!(Tiff::parse(b"XX*\0\x08\0\0\0").is_err());
+        assert!(Tiff::parse(b"II").is_err());
+    }
+
+    #[test]
+    fn it_fails_when_truncated() {
+        let data = little_endian_tiff();
+        let tiff = Tiff::parse(&data[..12]).unwrap();
+
+        assert!(tiff.ifd(tiff.first_ifd).is_err());
+    }
+}
@@ -1,12 +1,19 @@
-use crate::exif::{ExifData, FromExifData};
+use crate::camera::CameraModel;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::from_exif::monochromatic_color;
+use crate::recipe::report::{Field, FieldReport, FieldStatus};
 use crate::recipe::{
-    Clarity, Color, ColorChromeEffect, ColorChromeEffectFxBlue, DRangePriority, DynamicRange,
-    GrainEffect, HighISONoiseReduction, MonochromaticColor, Settings, Sharpness, ToneCurve,
-    TransIIISettings, TransIISettings, TransISettings, TransIVSettings, TransSensor,
-    TransVSettings, WhiteBalance,
+    BayerSettings, Clarity, Color, ColorChromeEffect, ColorChromeEffectFxBlue, DRangePriority,
+    DynamicRange, Gfx100Settings, Gfx50Settings, GrainEffect, HighISONoiseReduction,
+    MonochromaticColor, Settings, Sharpness, ToneCurve, TransIIISettings, TransIISettings,
+    TransISettings, TransIVSettings, TransSensor, TransVSettings, WhiteBalance,
 };
+use serde::Deserialize;
 
-#[derive(Default)]
+/// Settings read so far, built into the [`Settings`] of a sensor. Also reads
+/// the `settings` of a serialized recipe, see [`TransSensor::settings`].
+#[derive(Default, Deserialize)]
+#[serde(default)]
 pub struct SettingsBuilder {
     white_balance: Option<WhiteBalance>,
     dynamic_range: Option<DynamicRange>,
@@ -20,6 +27,8 @@ pub struct SettingsBuilder {
     sharpness: Option<Sharpness>,
     high_iso_noise_reduction: Option<HighISONoiseReduction>,
     clarity: Option<Clarity>,
+    #[serde(skip)]
+    report: Vec<FieldReport>,
 }
 
 impl SettingsBuilder {
@@ -92,6 +101,63 @@ impl SettingsBuilder {
         }
     }
 
+    pub fn build_for_gfx50(self) -> Gfx50Settings {
+        Gfx50Settings {
+            white_balance: self.white_balance.unwrap_or_default(),
+            dynamic_range: self.dynamic_range.unwrap_or_default(),
+            d_range_priority: self.d_range_priority,
+            grain_effect: self.grain_effect.unwrap_or_default(),
+            color_chrome_effect: self.color_chrome_effect.unwrap_or_default(),
+            color_chrome_fx_blue: self.color_chrome_fx_blue,
+            tone_curve: self.tone_curve.unwrap_or_default(),
+            color: self.color.unwrap_or_default(),
+            monochromatic_color: self.monochromatic_color.unwrap_or_default(),
+            sharpness: self.sharpness.unwrap_or_default(),
+            high_iso_noise_reduction: self.high_iso_noise_reduction.unwrap_or_default(),
+            clarity: self.clarity,
+        }
+    }
+
+    pub fn build_for_gfx100(self) -> Gfx100Settings {
+        Gfx100Settings {
+            white_balance: self.white_balance.unwrap_or_default(),
+            dynamic_range: self.dynamic_range.unwrap_or_default(),
+            d_range_priority: self.d_range_priority.unwrap_or_default(),
+            grain_effect: self.grain_effect.unwrap_or_default(),
+            color_chrome_effect: self.color_chrome_effect.unwrap_or_default(),
+            color_chrome_fx_blue: self.color_chrome_fx_blue.unwrap_or_default(),
+            tone_curve: self.tone_curve.unwrap_or_default(),
+            color: self.color.unwrap_or_default(),
+            monochromatic_color: self.monochromatic_color.unwrap_or_default(),
+            sharpness: self.sharpness.unwrap_or_default(),
+            high_iso_noise_reduction: self.high_iso_noise_reduction.unwrap_or_default(),
+            clarity: self.clarity.unwrap_or_default(),
+        }
+    }
+
+    pub fn build_for_bayer(self) -> BayerSettings {
+        BayerSettings {
+            white_balance: self.white_balance.unwrap_or_default(),
+            dynamic_range: self.dynamic_range.unwrap_or_default(),
+            tone_curve: self.tone_curve.unwrap_or_default(),
+            color: self.color.unwrap_or_default(),
+            sharpness: self.sharpness.unwrap_or_default(),
+            high_iso_noise_reduction: self.high_iso_noise_reduction.unwrap_or_default(),
+        }
+    }
+
+    /// How each setting used by `sensor` was read from the EXIF. Empty unless
+    /// the builder was read with [`FromExifData::from_exif`].
+    pub fn report(&self, sensor: &TransSensor) -> Vec<FieldReport> {
+        let fields = sensor.fields();
+
+        self.report
+            .iter()
+            .filter(|report| fields.contains(&report.field))
+            .cloned()
+            .collect()
+    }
+
     pub fn with_white_balance(&mut self, white_balance: Option<WhiteBalance>) -> &mut Self {
         self.white_balance = white_balance;
         self
@@ -166,21 +232,69 @@ impl SettingsBuilder {
 }
 
 impl FromExifData for SettingsBuilder {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let white_balance = WhiteBalance::from_exif(data);
-        let dynamic_range = DynamicRange::from_exif(data);
-        let d_range_priority = DRangePriority::from_exif(data);
-        let grain_effect = GrainEffect::from_exif(data);
-        let color_chrome_effect = ColorChromeEffect::from_exif(data);
-        let color_chrome_fx_blue = ColorChromeEffectFxBlue::from_exif(data);
-        let tone_curve = ToneCurve::from_exif(data);
-        let color = Color::from_exif(data);
-        let monochromatic_color = MonochromaticColor::from_exif(data);
-        let sharpness = Sharpness::from_exif(data);
-        let high_iso_noise_reduction = HighISONoiseReduction::from_exif(data);
-        let clarity = Clarity::from_exif(data);
-
-        let builder = SettingsBuilder {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        let sensor = TransSensor::from_exif(data);
+
+        Some(SettingsBuilder::from_exif_for_sensor(data, sensor.as_ref()))
+    }
+}
+
+impl SettingsBuilder {
+    /// Reads the settings recorded by `sensor`, when the caller already knows
+    /// it. See [`FromExifData::from_exif`] otherwise.
+    pub fn from_exif_for_sensor<D>(data: &D, sensor: Option<&TransSensor>) -> Self
+    where
+        D: FindExifData + ?Sized,
+    {
+        let mut report = vec![];
+
+        // The GFX 50S & 50R lack settings of the GFX50S II, which are left
+        // out instead of being read as off
+        let camera = match sensor {
+            Some(TransSensor::Gfx50) => data
+                .find("Model")
+                .and_then(|exif| CameraModel::lookup(&exif.value().to_string())),
+            _ => None,
+        };
+        let unsupported = |field| camera.as_ref().is_some_and(|c| !c.supports(field));
+
+        let white_balance = read(data, Field::WhiteBalance, &mut report);
+        let dynamic_range = read(data, Field::DynamicRange, &mut report);
+        let d_range_priority = if unsupported(Field::DRangePriority) {
+            None
+        } else {
+            read(data, Field::DRangePriority, &mut report)
+        };
+        let grain_effect = read(data, Field::GrainEffect, &mut report);
+        let color_chrome_effect = read(data, Field::ColorChromeEffect, &mut report);
+        let color_chrome_fx_blue = if unsupported(Field::ColorChromeFxBlue) {
+            None
+        } else {
+            read(data, Field::ColorChromeFxBlue, &mut report)
+        };
+        let tone_curve = read(data, Field::ToneCurve, &mut report);
+        let color = read(data, Field::Color, &mut report);
+        // X-Trans III cameras tone with a single strength instead of WC/MG
+        let monochromatic_color = match sensor {
+            Some(TransSensor::TransIII) => record(
+                Field::MonochromaticColor,
+                monochromatic_color::strength_from_exif(data),
+                &mut report,
+            ),
+            _ => read(data, Field::MonochromaticColor, &mut report),
+        };
+        let sharpness = read(data, Field::Sharpness, &mut report);
+        let high_iso_noise_reduction = read(data, Field::HighIsoNoiseReduction, &mut report);
+        let clarity = if unsupported(Field::Clarity) {
+            None
+        } else {
+            read(data, Field::Clarity, &mut report)
+        };
+
+        SettingsBuilder {
             white_balance,
             dynamic_range,
             d_range_priority,
@@ -193,12 +307,34 @@ impl FromExifData for SettingsBuilder {
             sharpness,
             high_iso_noise_reduction,
             clarity,
-        };
-
-        Some(builder)
+            report,
+        }
     }
 }
 
+/// Reads a setting, keeping how it was read in `report`. Settings that were
+/// not read get their default value when built.
+fn read<T, D>(data: &D, field: Field, report: &mut Vec<FieldReport>) -> Option<T>
+where
+    T: TryFromExifData,
+    D: FindExifData + ?Sized,
+{
+    record(field, T::try_from_exif(data), report)
+}
+
+fn record<T>(
+    field: Field,
+    result: Result<T, FieldError>,
+    report: &mut Vec<FieldReport>,
+) -> Option<T> {
+    report.push(FieldReport {
+        field,
+        status: FieldStatus::of(&result),
+    });
+
+    result.ok()
+}
+
 impl TransSensor {
     pub fn settings(&self, builder: SettingsBuilder) -> Settings {
         match self {
@@ -207,6 +343,50 @@ impl TransSensor {
             TransSensor::TransIII => Settings::TransIII(builder.build_for_iii()),
             TransSensor::TransIV => Settings::TransIV(builder.build_for_iv()),
             TransSensor::TransV => Settings::TransV(builder.build_for_v()),
+            TransSensor::Gfx50 => Settings::Gfx50(builder.build_for_gfx50()),
+            TransSensor::Gfx100 => Settings::Gfx100(builder.build_for_gfx100()),
+            TransSensor::Bayer => Settings::Bayer(builder.build_for_bayer()),
+        }
+    }
+
+    /// Settings of the sensor, see [`Self::settings`].
+    pub fn fields(&self) -> &'static [Field] {
+        match self {
+            TransSensor::TransI | TransSensor::TransII | TransSensor::Bayer => &[
+                Field::WhiteBalance,
+                Field::DynamicRange,
+                Field::ToneCurve,
+                Field::Color,
+                Field::Sharpness,
+                Field::HighIsoNoiseReduction,
+            ],
+            TransSensor::TransIII => &[
+                Field::WhiteBalance,
+                Field::DynamicRange,
+                Field::GrainEffect,
+                Field::ToneCurve,
+                Field::Color,
+                Field::MonochromaticColor,
+                Field::Sharpness,
+                Field::HighIsoNoiseReduction,
+            ],
+            TransSensor::TransIV
+            | TransSensor::TransV
+            | TransSensor::Gfx50
+            | TransSensor::Gfx100 => &[
+                Field::WhiteBalance,
+                Field::DynamicRange,
+                Field::DRangePriority,
+                Field::GrainEffect,
+                Field::ColorChromeEffect,
+                Field::ColorChromeFxBlue,
+                Field::ToneCurve,
+                Field::Color,
+                Field::MonochromaticColor,
+                Field::Sharpness,
+                Field::HighIsoNoiseReduction,
+                Field::Clarity,
+            ],
         }
     }
 }
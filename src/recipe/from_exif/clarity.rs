@@ -1,22 +1,48 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::Clarity;
 use log::trace;
 
+const TAG: &str = "Clarity";
+
 impl FromExifData for Clarity {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = data.find("Clarity")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for Clarity {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        // Stored as multiples of 1000
+        if let Some(number) = data.find_number(TAG) {
+            trace!("Clarity::from_exif: raw {:?}", number);
+
+            return Ok(Clarity {
+                value: number / 1000,
+            });
+        }
 
-        trace!("Clarity::from_exif: {:?}", exif);
+        if let Some(exif) = data.find(TAG) {
+            trace!("Clarity::from_exif: {:?}", exif);
 
-        let value: i64 = exif.try_into().ok()?;
+            if let Ok(value) = i64::try_from(exif) {
+                return Ok(Clarity { value });
+            }
+        }
 
-        Some(Clarity { value })
+        Err(FieldError::not_read(data, TAG))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_positive_numbers() {
@@ -38,4 +64,14 @@ mod tests {
 
         assert_eq!(Clarity::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Clarity", "0"),
+            ExifData::new("Clarity#", "-3000"),
+        ];
+
+        assert_eq!(Clarity::from_exif(&exif), Some(Clarity { value: -3 }));
+    }
 }
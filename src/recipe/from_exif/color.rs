@@ -1,22 +1,74 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
-use crate::recipe::Color;
+use super::legacy;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::report::Field;
+use crate::recipe::{Color, FilmSimulation};
 use log::trace;
 
+const TAG: &str = "Saturation";
+
 impl FromExifData for Color {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = data.find("Saturation")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for Color {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(value) = data.find_number(TAG).and_then(from_number) {
+            trace!("Color::from_exif: raw {:?}", value);
+
+            return Ok(Color { value });
+        }
 
-        trace!("Color::from_exif: {:?}", exif);
+        if let Some(exif) = data.find(TAG) {
+            trace!("Color::from_exif: {:?}", exif);
 
-        let value: i64 = exif.try_into().ok()?;
+            if let Ok(value) = i64::try_from(exif.clone()) {
+                return Ok(Color { value });
+            }
 
-        Some(Color { value })
+            if let Some(value) = legacy::from_word(data, Field::Color, &exif.value().to_string()) {
+                return Ok(Color { value });
+            }
+        }
+
+        // Monochrome film simulations use the same tag, they have no color
+        match FilmSimulation::from_exif(data) {
+            Some(
+                FilmSimulation::Acros { .. }
+                | FilmSimulation::Monochrome { .. }
+                | FilmSimulation::Sepia,
+            ) => Ok(Color::default()),
+            _ => Err(FieldError::not_read(data, TAG)),
+        }
+    }
+}
+
+fn from_number(number: i64) -> Option<i64> {
+    match number {
+        0x4e0 => Some(-4),
+        0x4c0 => Some(-3),
+        0x400 => Some(-2),
+        0x180 => Some(-1),
+        0x000 => Some(0),
+        0x080 => Some(1),
+        0x100 => Some(2),
+        0x0c0 => Some(3),
+        0x0e0 => Some(4),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_positive_numbers() {
@@ -38,4 +90,31 @@ mod tests {
 
         assert_eq!(Color::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_has_no_color_in_monochrome() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Saturation", "Acros Red Filter"),
+            ExifData::new("Saturation#", "1281"),
+        ];
+
+        assert_eq!(Color::try_from_exif(&exif), Ok(Color { value: 0 }));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Saturation", "Film Simulation")];
+
+        assert_eq!(
+            Color::try_from_exif(&exif),
+            Err(FieldError::unknown("Saturation", "Film Simulation"))
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Saturation", "0 (normal)"),
+            ExifData::new("Saturation#", "224"),
+        ];
+
+        assert_eq!(Color::from_exif(&exif), Some(Color { value: 4 }));
+    }
 }
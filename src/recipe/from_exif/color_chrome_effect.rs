@@ -1,30 +1,61 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use super::or_default;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::{ColorChromeEffect, SettingStrength};
 use log::trace;
 
+const TAG: &str = "ColorChromeEffect";
+
 impl FromExifData for ColorChromeEffect {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = data.find("ColorChromeEffect")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        // Off unless a known strength was written
+        or_default(Self::try_from_exif(data))
+    }
+}
 
-        trace!("ColorChromeEffect::from_exif: {:?}", exif);
+impl TryFromExifData for ColorChromeEffect {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(strength) = data.find_number(TAG).and_then(from_number) {
+            trace!("ColorChromeEffect::from_exif: raw {:?}", strength);
 
-        match exif.value().to_lowercase().as_str() {
-            "strong" => Some(ColorChromeEffect {
-                strength: SettingStrength::Strong,
-            }),
-            "weak" => Some(ColorChromeEffect {
-                strength: SettingStrength::Weak,
-            }),
-            _ => Some(ColorChromeEffect {
-                strength: SettingStrength::Off,
-            }),
+            return Ok(ColorChromeEffect { strength });
         }
+
+        let Some(exif) = data.find(TAG) else {
+            return Err(FieldError::missing(TAG));
+        };
+
+        trace!("ColorChromeEffect::from_exif: {:?}", exif);
+
+        let strength = match exif.value().to_string().to_lowercase().as_str() {
+            "off" => SettingStrength::Off,
+            "weak" => SettingStrength::Weak,
+            "strong" => SettingStrength::Strong,
+            _ => return Err(FieldError::unknown(TAG, exif.value())),
+        };
+
+        Ok(ColorChromeEffect { strength })
+    }
+}
+
+fn from_number(number: i64) -> Option<SettingStrength> {
+    match number {
+        0 => Some(SettingStrength::Off),
+        32 => Some(SettingStrength::Weak),
+        64 => Some(SettingStrength::Strong),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_strong_effect() {
@@ -68,4 +99,34 @@ mod tests {
 
         assert_eq!(ColorChromeEffect::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_reads_the_print_of_unknown_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("ColorChromeEffect", "Weak"),
+            ExifData::new("ColorChromeEffect#", "48"),
+        ];
+
+        assert_eq!(
+            ColorChromeEffect::try_from_exif(&exif),
+            Ok(ColorChromeEffect {
+                strength: SettingStrength::Weak
+            })
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("ColorChromeEffect", "Fort"),
+            ExifData::new("ColorChromeEffect#", "64"),
+        ];
+
+        assert_eq!(
+            ColorChromeEffect::from_exif(&exif),
+            Some(ColorChromeEffect {
+                strength: SettingStrength::Strong
+            })
+        );
+    }
 }
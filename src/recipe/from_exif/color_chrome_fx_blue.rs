@@ -1,30 +1,59 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use super::or_default;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::{ColorChromeEffectFxBlue, SettingStrength};
 use log::trace;
 
+const TAG: &str = "ColorChromeFXBlue";
+
 impl FromExifData for ColorChromeEffectFxBlue {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = data.find("ColorChromeFXBlue")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        // Off unless a known strength was written
+        or_default(Self::try_from_exif(data))
+    }
+}
 
-        trace!("ColorChromeEffectFxBlue::from_exif: {:?}", exif);
+impl TryFromExifData for ColorChromeEffectFxBlue {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(number) = data.find_number(TAG) {
+            trace!("ColorChromeEffectFxBlue::from_exif: raw {:?}", number);
+
+            let strength = match number {
+                0 => SettingStrength::Off,
+                32 => SettingStrength::Weak,
+                64 => SettingStrength::Strong,
+                _ => return Err(FieldError::unknown(TAG, number)),
+            };
 
-        match exif.value().to_lowercase().as_str() {
-            "strong" => Some(ColorChromeEffectFxBlue {
-                strength: SettingStrength::Strong,
-            }),
-            "weak" => Some(ColorChromeEffectFxBlue {
-                strength: SettingStrength::Weak,
-            }),
-            _ => Some(ColorChromeEffectFxBlue {
-                strength: SettingStrength::Off,
-            }),
+            return Ok(ColorChromeEffectFxBlue { strength });
         }
+
+        let Some(exif) = data.find(TAG) else {
+            return Err(FieldError::missing(TAG));
+        };
+
+        trace!("ColorChromeEffectFxBlue::from_exif: {:?}", exif);
+
+        let strength = match exif.value().to_string().to_lowercase().as_str() {
+            "off" => SettingStrength::Off,
+            "weak" => SettingStrength::Weak,
+            "strong" => SettingStrength::Strong,
+            _ => return Err(FieldError::unknown(TAG, exif.value())),
+        };
+
+        Ok(ColorChromeEffectFxBlue { strength })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_strong_effect() {
@@ -68,4 +97,19 @@ mod tests {
 
         assert_eq!(ColorChromeEffectFxBlue::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("ColorChromeFXBlue", "Faible"),
+            ExifData::new("ColorChromeFXBlue#", "32"),
+        ];
+
+        assert_eq!(
+            ColorChromeEffectFxBlue::from_exif(&exif),
+            Some(ColorChromeEffectFxBlue {
+                strength: SettingStrength::Weak
+            })
+        );
+    }
 }
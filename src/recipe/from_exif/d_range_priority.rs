@@ -1,33 +1,93 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use super::or_default;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::DRangePriority;
 use log::trace;
 
+const TAG: &str = "DRangePriority";
+const AUTO_TAG: &str = "DRangePriorityAuto";
+const FIXED_TAG: &str = "DRangePriorityFixed";
+
 impl FromExifData for DRangePriority {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let value: String = if let Some(exif) = data.find("DRangePriorityAuto") {
-            trace!("DRangePriority::from_exif: {:?}", exif);
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        or_default(Self::try_from_exif(data))
+    }
+}
 
-            exif.value().to_string()
-        } else if let Some(exif) = data.find("DRangePriority") {
-            trace!("DRangePriority::from_exif: {:?}", exif);
+impl TryFromExifData for DRangePriority {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(number) = from_number(data) {
+            return Ok(number);
+        }
 
-            exif.value().to_string()
+        let exif = if let Some(exif) = data.find(AUTO_TAG) {
+            exif
+        } else if let Some(exif) = data.find(TAG) {
+            exif
         } else {
-            "".to_string()
+            // The tags are only written when D-Range Priority is on
+            return Ok(DRangePriority::Off);
         };
 
-        match value.to_lowercase().as_str() {
-            "strong" => Some(DRangePriority::Strong),
-            "weak" => Some(DRangePriority::Weak),
-            "auto" => Some(DRangePriority::Auto),
-            _ => Some(DRangePriority::Off),
+        trace!("DRangePriority::from_exif: {:?}", exif);
+
+        let mut value = exif.value().to_string().to_lowercase();
+
+        // The strength of a fixed priority has its own tag
+        if value == "fixed" {
+            if let Some(fixed) = data.find(FIXED_TAG) {
+                trace!("DRangePriority::from_exif: {:?}", fixed);
+
+                value = fixed.value().to_string().to_lowercase();
+            }
+        }
+
+        match value.as_str() {
+            "strong" => Ok(DRangePriority::Strong),
+            "weak" => Ok(DRangePriority::Weak),
+            "auto" => Ok(DRangePriority::Auto),
+            "off" => Ok(DRangePriority::Off),
+            _ => Err(FieldError::unknown(exif.tag(), exif.value())),
         }
     }
 }
 
+fn from_number<D>(data: &D) -> Option<DRangePriority>
+where
+    D: FindExifData + ?Sized,
+{
+    let strength = |number| match number {
+        1 => Some(DRangePriority::Weak),
+        2 => Some(DRangePriority::Strong),
+        _ => None,
+    };
+
+    if let Some(auto) = data.find_number(AUTO_TAG) {
+        trace!("DRangePriority::from_number: auto {:?}", auto);
+
+        return strength(auto);
+    }
+
+    let priority = data.find_number(TAG)?;
+
+    trace!("DRangePriority::from_number: {:?}", priority);
+
+    match priority {
+        0 => Some(DRangePriority::Auto),
+        1 => strength(data.find_number(FIXED_TAG)?),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_auto_priority() {
@@ -53,10 +113,40 @@ mod tests {
         assert_eq!(DRangePriority::from_exif(&exif), Some(DRangePriority::Weak));
     }
 
+    #[test]
+    fn it_parses_fixed_priority() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("DRangePriority", "Fixed"),
+            ExifData::new("DRangePriorityFixed", "Strong"),
+        ];
+
+        assert_eq!(
+            DRangePriority::from_exif(&exif),
+            Some(DRangePriority::Strong)
+        );
+    }
+
     #[test]
     fn it_parses_as_off_when_missing() {
         let exif: Vec<ExifData> = vec![ExifData::new("Foo", "3")];
 
         assert_eq!(DRangePriority::from_exif(&exif), Some(DRangePriority::Off));
     }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("DRangePriority#", "1"),
+            ExifData::new("DRangePriorityFixed#", "2"),
+        ];
+
+        assert_eq!(
+            DRangePriority::from_exif(&exif),
+            Some(DRangePriority::Strong)
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("DRangePriority#", "0")];
+
+        assert_eq!(DRangePriority::from_exif(&exif), Some(DRangePriority::Auto));
+    }
 }
@@ -1,26 +1,59 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::DynamicRange;
 use log::trace;
 
+const SETTING_TAG: &str = "DynamicRangeSetting";
+const TAG: &str = "DevelopmentDynamicRange";
+
 impl FromExifData for DynamicRange {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        if let Some(exif) = data.find("DynamicRangeSetting") {
-            if exif.value().to_lowercase() != "manual" {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for DynamicRange {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(setting) = data.find_number(SETTING_TAG) {
+            trace!("DynamicRange::from_exif: raw setting {:?}", setting);
+
+            // 0x001 is Manual
+            if setting != 0x001 {
+                return Ok(DynamicRange::Auto);
+            }
+
+            match data.find_number(TAG) {
+                Some(100) => return Ok(DynamicRange::DR100),
+                Some(200) => return Ok(DynamicRange::DR200),
+                Some(400) => return Ok(DynamicRange::DR400),
+                _ => {}
+            }
+        }
+
+        if let Some(exif) = data.find(SETTING_TAG) {
+            if exif.value().to_string().to_lowercase() != "manual" {
                 trace!("DynamicRange::from_exif: {:?}", exif);
 
-                return Some(DynamicRange::Auto);
+                return Ok(DynamicRange::Auto);
             }
         };
 
-        let exif = data.find("DevelopmentDynamicRange")?;
+        let Some(exif) = data.find(TAG) else {
+            return Err(FieldError::not_read(data, TAG));
+        };
 
         trace!("DynamicRange::from_exif: {:?}", exif);
 
-        match exif.value().to_lowercase().as_str() {
-            "100" => Some(DynamicRange::DR100),
-            "200" => Some(DynamicRange::DR200),
-            "400" => Some(DynamicRange::DR400),
-            _ => None,
+        match exif.value().to_string().to_lowercase().as_str() {
+            "100" => Ok(DynamicRange::DR100),
+            "200" => Ok(DynamicRange::DR200),
+            "400" => Ok(DynamicRange::DR400),
+            _ => Err(FieldError::unknown(TAG, exif.value())),
         }
     }
 }
@@ -28,6 +61,7 @@ impl FromExifData for DynamicRange {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_auto_dynamic_range() {
@@ -63,4 +97,19 @@ mod tests {
 
         assert_eq!(DynamicRange::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("DynamicRangeSetting", "Manuel"),
+            ExifData::new("DynamicRangeSetting#", "1"),
+            ExifData::new("DevelopmentDynamicRange#", "400"),
+        ];
+
+        assert_eq!(DynamicRange::from_exif(&exif), Some(DynamicRange::DR400));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("DynamicRangeSetting#", "0")];
+
+        assert_eq!(DynamicRange::from_exif(&exif), Some(DynamicRange::Auto));
+    }
 }
@@ -1,80 +1,142 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::{FilmSimulation, MonochromaticFilter};
 use log::trace;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
 impl FromExifData for FilmSimulation {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = if let Some(v) = data.find("FilmMode") {
-            v
-        } else if let Some(v) = data.find("Saturation") {
-            v
-        } else {
-            return None;
-        };
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for FilmSimulation {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        from_number(data)
+            .or_else(|| from_print(data))
+            .ok_or_else(|| FieldError::not_read(data, "FilmMode"))
+    }
+}
 
-        trace!("FilmSimulation::from_exif: {:?}", exif);
-
-        static RE: Lazy<Regex> = Lazy::new(|| {
-            Regex::new(
-            r"(?i:provia|velvia|astia|classic chrome|classic neg|nostalgic neg|acros yellow filter|acros red filter|acros green filter|acros|eterna|b&w sepia|b&w yellow filter|b&w red filter|b&w green filter|b&w|reala ace|pro neg\.? std|pro neg\.? standard|pro neg\.? hi|bleach bypass)",
-        ).unwrap()
-        });
-
-        let captures = RE.captures(exif.value())?;
-
-        trace!("FilmSimulation Captures: {:?}", captures);
-
-        match captures[0].to_lowercase().as_str() {
-            "provia" => Some(FilmSimulation::ProviaStandard),
-            "velvia" => Some(FilmSimulation::VelviaVivid),
-            "astia" => Some(FilmSimulation::AstiaSoft),
-            "classic chrome" => Some(FilmSimulation::ClassicChrome),
-            "classic neg" => Some(FilmSimulation::ClassicNeg),
-            "nostalgic neg" => Some(FilmSimulation::NostalgicNeg),
-            "pro neg. std" => Some(FilmSimulation::ProNegStd),
-            "pro neg std" => Some(FilmSimulation::ProNegStd),
-            "pro neg. standard" => Some(FilmSimulation::ProNegStd),
-            "pro neg standard" => Some(FilmSimulation::ProNegStd),
-            "pro neg. hi" => Some(FilmSimulation::ProNegHi),
-            "pro neg hi" => Some(FilmSimulation::ProNegHi),
-            "eterna" => Some(FilmSimulation::EternaCinema),
-            "bleach bypass" => Some(FilmSimulation::BleachBypass),
-            "acros yellow filter" => Some(FilmSimulation::Acros {
-                filter: MonochromaticFilter::Yellow,
-            }),
-            "acros red filter" => Some(FilmSimulation::Acros {
-                filter: MonochromaticFilter::Red,
-            }),
-            "acros green filter" => Some(FilmSimulation::Acros {
-                filter: MonochromaticFilter::Green,
-            }),
-            "acros" => Some(FilmSimulation::Acros {
-                filter: MonochromaticFilter::Std,
-            }),
-            "b&w" => Some(FilmSimulation::Monochrome {
-                filter: MonochromaticFilter::Std,
-            }),
-            "b&w yellow filter" => Some(FilmSimulation::Monochrome {
-                filter: MonochromaticFilter::Yellow,
-            }),
-            "b&w red filter" => Some(FilmSimulation::Monochrome {
-                filter: MonochromaticFilter::Red,
-            }),
-            "b&w green filter" => Some(FilmSimulation::Monochrome {
-                filter: MonochromaticFilter::Green,
-            }),
-            "b&w sepia" => Some(FilmSimulation::Sepia),
-            "reala ace" => Some(FilmSimulation::RealaAce),
+fn from_number<D>(data: &D) -> Option<FilmSimulation>
+where
+    D: FindExifData + ?Sized,
+{
+    if let Some(film_mode) = data.find_number("FilmMode") {
+        trace!("FilmSimulation::from_number: FilmMode {:#x}", film_mode);
+
+        return match film_mode {
+            0x000 => Some(FilmSimulation::ProviaStandard),
+            0x120 => Some(FilmSimulation::AstiaSoft),
+            0x200 | 0x400 => Some(FilmSimulation::VelviaVivid),
+            0x500 => Some(FilmSimulation::ProNegStd),
+            0x501 => Some(FilmSimulation::ProNegHi),
+            0x600 => Some(FilmSimulation::ClassicChrome),
+            0x700 => Some(FilmSimulation::EternaCinema),
+            0x800 => Some(FilmSimulation::ClassicNeg),
+            0x900 => Some(FilmSimulation::BleachBypass),
+            0xa00 => Some(FilmSimulation::NostalgicNeg),
+            0xb00 => Some(FilmSimulation::RealaAce),
             _ => None,
-        }
+        };
+    }
+
+    // Monochrome film simulations are stored in the Saturation instead
+    let saturation = data.find_number("Saturation")?;
+
+    trace!("FilmSimulation::from_number: Saturation {:#x}", saturation);
+
+    let filter = match saturation & 0xf {
+        0x1 => MonochromaticFilter::Red,
+        0x2 => MonochromaticFilter::Yellow,
+        0x3 => MonochromaticFilter::Green,
+        _ => MonochromaticFilter::Std,
+    };
+
+    match saturation {
+        0x300..=0x303 => Some(FilmSimulation::Monochrome { filter }),
+        0x310 => Some(FilmSimulation::Sepia),
+        0x500..=0x503 => Some(FilmSimulation::Acros { filter }),
+        _ => None,
+    }
+}
+
+fn from_print<D>(data: &D) -> Option<FilmSimulation>
+where
+    D: FindExifData + ?Sized,
+{
+    let exif = match data.find("FilmMode") {
+        Some(exif) => exif,
+        None => data.find("Saturation")?,
+    };
+
+    trace!("FilmSimulation::from_print: {:?}", exif);
+
+    static RE: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(
+        r"(?i:provia|velvia|astia|classic chrome|classic neg|nostalgic neg|acros yellow filter|acros red filter|acros green filter|acros|eterna|b&w sepia|b&w yellow filter|b&w red filter|b&w green filter|b&w|reala ace|pro neg\.? std|pro neg\.? standard|pro neg\.? hi|bleach bypass)",
+    ).unwrap()
+    });
+
+    let captures = RE.captures(exif.value().as_str()?)?;
+
+    trace!("FilmSimulation Captures: {:?}", captures);
+
+    match captures[0].to_lowercase().as_str() {
+        "provia" => Some(FilmSimulation::ProviaStandard),
+        "velvia" => Some(FilmSimulation::VelviaVivid),
+        "astia" => Some(FilmSimulation::AstiaSoft),
+        "classic chrome" => Some(FilmSimulation::ClassicChrome),
+        "classic neg" => Some(FilmSimulation::ClassicNeg),
+        "nostalgic neg" => Some(FilmSimulation::NostalgicNeg),
+        "pro neg. std" => Some(FilmSimulation::ProNegStd),
+        "pro neg std" => Some(FilmSimulation::ProNegStd),
+        "pro neg. standard" => Some(FilmSimulation::ProNegStd),
+        "pro neg standard" => Some(FilmSimulation::ProNegStd),
+        "pro neg. hi" => Some(FilmSimulation::ProNegHi),
+        "pro neg hi" => Some(FilmSimulation::ProNegHi),
+        "eterna" => Some(FilmSimulation::EternaCinema),
+        "bleach bypass" => Some(FilmSimulation::BleachBypass),
+        "acros yellow filter" => Some(FilmSimulation::Acros {
+            filter: MonochromaticFilter::Yellow,
+        }),
+        "acros red filter" => Some(FilmSimulation::Acros {
+            filter: MonochromaticFilter::Red,
+        }),
+        "acros green filter" => Some(FilmSimulation::Acros {
+            filter: MonochromaticFilter::Green,
+        }),
+        "acros" => Some(FilmSimulation::Acros {
+            filter: MonochromaticFilter::Std,
+        }),
+        "b&w" => Some(FilmSimulation::Monochrome {
+            filter: MonochromaticFilter::Std,
+        }),
+        "b&w yellow filter" => Some(FilmSimulation::Monochrome {
+            filter: MonochromaticFilter::Yellow,
+        }),
+        "b&w red filter" => Some(FilmSimulation::Monochrome {
+            filter: MonochromaticFilter::Red,
+        }),
+        "b&w green filter" => Some(FilmSimulation::Monochrome {
+            filter: MonochromaticFilter::Green,
+        }),
+        "b&w sepia" => Some(FilmSimulation::Sepia),
+        "reala ace" => Some(FilmSimulation::RealaAce),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_provia() {
@@ -326,4 +388,66 @@ mod tests {
 
         assert_eq!(FilmSimulation::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("FilmMode", "Negativo Clásico"),
+            ExifData::new("FilmMode#", "2048"),
+        ];
+
+        assert_eq!(
+            FilmSimulation::from_exif(&exif),
+            Some(FilmSimulation::ClassicNeg)
+        );
+    }
+
+    #[test]
+    fn it_parses_raw_monochrome_from_saturation() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Saturation#", "1282")];
+
+        assert_eq!(
+            FilmSimulation::from_exif(&exif),
+            Some(FilmSimulation::Acros {
+                filter: MonochromaticFilter::Yellow
+            })
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Saturation#", "784")];
+
+        assert_eq!(
+            FilmSimulation::from_exif(&exif),
+            Some(FilmSimulation::Sepia)
+        );
+    }
+
+    #[test]
+    fn it_falls_back_to_print_values() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("FilmMode", "Eterna"),
+            ExifData::new("FilmMode#", "4095"),
+        ];
+
+        assert_eq!(
+            FilmSimulation::from_exif(&exif),
+            Some(FilmSimulation::EternaCinema)
+        );
+    }
+
+    #[test]
+    fn it_reports_unknown_film_modes() {
+        let exif: Vec<ExifData> = vec![ExifData::new("FilmMode#", "4095")];
+
+        assert_eq!(
+            FilmSimulation::try_from_exif(&exif),
+            Err(FieldError::unknown("FilmMode", 4095))
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Saturation", "+2 (high)")];
+
+        assert_eq!(
+            FilmSimulation::try_from_exif(&exif),
+            Err(FieldError::missing("FilmMode"))
+        );
+    }
 }
@@ -0,0 +1,84 @@
+use crate::camera::FirmwareVersion;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use log::trace;
+
+/// Written by exiftool for some RAF files, JPEG & HEIF files only have the
+/// version at the end of `Software`.
+const TAGS: [&str; 2] = ["FirmwareVersion", "Software"];
+
+impl FromExifData for FirmwareVersion {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for FirmwareVersion {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        let mut error = FieldError::missing(TAGS[1]);
+
+        for tag in TAGS {
+            let Some(exif) = data.find(tag) else {
+                continue;
+            };
+
+            trace!("FirmwareVersion::from_exif: {:?}", exif);
+
+            let value = exif.value().to_string();
+            match value.parse() {
+                Ok(version) => return Ok(version),
+                Err(_) => error = FieldError::unknown(tag, value),
+            }
+        }
+
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifData;
+
+    #[test]
+    fn it_parses_the_software_version() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Software", "Digital Camera X-T3 Ver3.00")];
+
+        assert_eq!(
+            FirmwareVersion::from_exif(&exif),
+            Some(FirmwareVersion::new(3, 0))
+        );
+    }
+
+    #[test]
+    fn it_prefers_the_firmware_version() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Software", "Digital Camera X-T3 Ver3.00"),
+            ExifData::new("FirmwareVersion", "4.10"),
+        ];
+
+        assert_eq!(
+            FirmwareVersion::from_exif(&exif),
+            Some(FirmwareVersion::new(4, 10))
+        );
+    }
+
+    #[test]
+    fn it_does_not_parse_other_software() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Software", "Adobe Lightroom 13.1")];
+
+        assert_eq!(
+            FirmwareVersion::try_from_exif(&exif),
+            Err(FieldError::unknown("Software", "Adobe Lightroom 13.1"))
+        );
+        assert_eq!(
+            FirmwareVersion::try_from_exif(&Vec::<ExifData>::new()),
+            Err(FieldError::missing("Software"))
+        );
+    }
+}
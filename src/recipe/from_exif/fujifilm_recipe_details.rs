@@ -1,17 +1,154 @@
-use crate::exif::{ExifData, FromExifData};
-use crate::recipe::{builder::SettingsBuilder, FilmSimulation, FujifilmRecipeDetails, TransSensor};
+use crate::camera::{CameraModel, FirmwareVersion};
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::report::{Field, RecipeReport, RecipeWarning};
+use crate::recipe::{
+    builder::SettingsBuilder, FujifilmRecipeDetails, ProcessingMode, Settings, ShootingMode,
+    TransSensor,
+};
+use log::warn;
 
 impl FromExifData for FujifilmRecipeDetails {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let film_simulation = FilmSimulation::from_exif(data)?;
-        let sensor = TransSensor::from_exif(data)?;
-        let builder = SettingsBuilder::from_exif(data)?;
-        let settings = sensor.settings(builder);
-
-        Some(FujifilmRecipeDetails {
-            film_simulation,
-            sensor,
-            settings,
-        })
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
     }
 }
+
+impl TryFromExifData for FujifilmRecipeDetails {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        read_with_report(data).map(|report| report.recipe)
+    }
+}
+
+/// Reads the recipe, with how each of its settings was read.
+pub(crate) fn read_with_report<D>(data: &D) -> Result<RecipeReport, FieldError>
+where
+    D: FindExifData + ?Sized,
+{
+    let processing_mode = ProcessingMode::try_from_exif(data)?;
+    let shooting_mode = ShootingMode::from_exif(data).unwrap_or_default();
+    let sensor = TransSensor::try_from_exif(data)?;
+    let builder = SettingsBuilder::from_exif_for_sensor(data, Some(&sensor));
+    let fields = builder.report(&sensor);
+    let settings = sensor.settings(builder);
+    let converted = is_converted(data);
+
+    let firmware = FirmwareVersion::from_exif(data);
+    let camera = data
+        .find("Model")
+        .and_then(|exif| CameraModel::lookup(&exif.value().to_string()))
+        .map(|camera| camera.at_firmware(firmware.as_ref()));
+
+    let recipe = FujifilmRecipeDetails {
+        processing_mode,
+        shooting_mode,
+        sensor,
+        settings,
+        converted,
+    };
+
+    let warnings = camera
+        .as_ref()
+        .map(|camera| unsupported(camera, firmware.as_ref(), &recipe))
+        .unwrap_or_default();
+
+    for warning in &warnings {
+        warn!("{}", warning);
+    }
+
+    Ok(RecipeReport {
+        recipe,
+        fields,
+        firmware,
+        camera,
+        warnings,
+    })
+}
+
+/// The film simulation & settings of the recipe `camera` does not support.
+/// Settings are only reported when they are not their default value, as the
+/// settings of the sensor are always there.
+fn unsupported(
+    camera: &CameraModel,
+    firmware: Option<&FirmwareVersion>,
+    recipe: &FujifilmRecipeDetails,
+) -> Vec<RecipeWarning> {
+    let mut warnings = vec![];
+
+    if let Some(film_simulation) = recipe.film_simulation() {
+        if !camera.supports_film_simulation(film_simulation) {
+            warnings.push(RecipeWarning::UnsupportedFilmSimulation {
+                film_simulation: film_simulation.clone(),
+                model: camera.name.clone(),
+                firmware: firmware.copied(),
+            });
+        }
+    }
+
+    for field in recipe.sensor.fields() {
+        if !camera.supports(*field) && is_set(&recipe.settings, *field) {
+            warnings.push(RecipeWarning::UnsupportedSetting {
+                field: *field,
+                model: camera.name.clone(),
+                firmware: firmware.copied(),
+            });
+        }
+    }
+
+    warnings
+}
+
+fn is_set(settings: &Settings, field: Field) -> bool {
+    let (
+        white_balance,
+        dynamic_range,
+        d_range_priority,
+        tone_curve,
+        color,
+        sharpness,
+        clarity,
+        high_iso_noise_reduction,
+        grain_effect,
+        color_chrome_effect,
+        color_chrome_fx_blue,
+        monochromatic_color,
+    ) = settings.get_values();
+
+    fn not_default<T: Default + PartialEq>(value: Option<T>) -> bool {
+        value.is_some_and(|value| value != T::default())
+    }
+
+    match field {
+        Field::WhiteBalance => not_default(Some(white_balance)),
+        Field::DynamicRange => not_default(Some(dynamic_range)),
+        Field::DRangePriority => not_default(d_range_priority),
+        Field::GrainEffect => not_default(grain_effect),
+        Field::ColorChromeEffect => not_default(color_chrome_effect),
+        Field::ColorChromeFxBlue => not_default(color_chrome_fx_blue),
+        Field::ToneCurve => not_default(Some(tone_curve)),
+        Field::Color => not_default(Some(color)),
+        Field::MonochromaticColor => not_default(monochromatic_color),
+        Field::Sharpness => not_default(Some(sharpness)),
+        Field::HighIsoNoiseReduction => not_default(Some(high_iso_noise_reduction)),
+        Field::Clarity => not_default(clarity),
+    }
+}
+
+/// Cameras write JPEG, HEIF and RAF files, a DNG or TIFF comes from a raw
+/// converter.
+fn is_converted<D>(data: &D) -> bool
+where
+    D: FindExifData + ?Sized,
+{
+    data.find("FileType").is_some_and(|exif| {
+        matches!(
+            exif.value().to_string().to_uppercase().as_str(),
+            "DNG" | "TIFF"
+        )
+    })
+}
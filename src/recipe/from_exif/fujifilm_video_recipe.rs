@@ -0,0 +1,74 @@
+use crate::exif::{FindExifData, FromExifData};
+use crate::recipe::{FilmSimulation, FujifilmVideoRecipe, ToneCurve, VideoGamma, WhiteBalance};
+
+impl FromExifData for FujifilmVideoRecipe {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        // Older bodies only record in the standard gamma and have no tag
+        let gamma = VideoGamma::from_exif(data).unwrap_or_default();
+        let white_balance = WhiteBalance::from_exif(data)?;
+
+        let (film_simulation, tone_curve) = if gamma.is_log() {
+            (None, None)
+        } else {
+            (
+                Some(FilmSimulation::from_exif(data)?),
+                ToneCurve::from_exif(data),
+            )
+        };
+
+        Some(FujifilmVideoRecipe {
+            film_simulation,
+            gamma,
+            white_balance,
+            tone_curve,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifData;
+
+    #[test]
+    fn it_reads_a_standard_recipe() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("FilmMode#", "1792"),
+            ExifData::new("WhiteBalance#", "0"),
+            ExifData::new("HighlightTone#", "-16"),
+            ExifData::new("ShadowTone#", "16"),
+            ExifData::new("VideoRecordingMode#", "0"),
+        ];
+
+        assert_eq!(
+            FujifilmVideoRecipe::from_exif(&exif),
+            Some(FujifilmVideoRecipe {
+                film_simulation: Some(FilmSimulation::EternaCinema),
+                gamma: VideoGamma::Standard,
+                white_balance: WhiteBalance::default(),
+                tone_curve: Some(ToneCurve {
+                    highlights: 1.0,
+                    shadows: -1.0,
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn it_skips_the_film_simulation_in_f_log() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("FilmMode#", "1792"),
+            ExifData::new("WhiteBalance#", "0"),
+            ExifData::new("VideoRecordingMode#", "16"),
+        ];
+
+        let recipe = FujifilmVideoRecipe::from_exif(&exif).unwrap();
+
+        assert_eq!(recipe.gamma, VideoGamma::FLog);
+        assert_eq!(recipe.film_simulation, None);
+        assert_eq!(recipe.tone_curve, None);
+    }
+}
@@ -1,52 +1,95 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use super::or_default;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::{GrainEffect, GrainSize, GrainStrength};
 use log::trace;
 
+const TAG: &str = "GrainEffectRoughness";
+const SIZE_TAG: &str = "GrainEffectSize";
+
 impl FromExifData for GrainEffect {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let strength_exif = data.find("GrainEffectRoughness")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        // Off unless a known strength was written
+        or_default(Self::try_from_exif(data))
+    }
+}
+
+impl TryFromExifData for GrainEffect {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(effect) = from_number(data) {
+            return Ok(effect);
+        }
+
+        let Some(strength_exif) = data.find(TAG) else {
+            return Err(FieldError::missing(TAG));
+        };
 
         trace!("GrainStrength::from_exif: {:?}", strength_exif);
 
-        let size_exif = data.find("GrainEffectSize").unwrap_or_default();
+        let size_exif = data.find(SIZE_TAG).unwrap_or_default();
 
         trace!("GrainSize::from_exif: {:?}", size_exif);
 
-        let grain_size = match size_exif.value().to_lowercase().as_str() {
-            "small" => Some(GrainSize::Small),
-            "large" => Some(GrainSize::Large),
-            _ => None,
+        let strength = match strength_exif.value().to_string().to_lowercase().as_str() {
+            "off" => return Ok(GrainEffect::Off),
+            "strong" => GrainStrength::Strong,
+            "weak" => GrainStrength::Weak,
+            _ => return Err(FieldError::unknown(TAG, strength_exif.value())),
         };
 
-        if let Some(grain_size) = grain_size {
-            match strength_exif.value().to_lowercase().as_str() {
-                "strong" => Some(GrainEffect::StrengthAndSize {
-                    strength: GrainStrength::Strong,
-                    size: grain_size,
-                }),
-                "weak" => Some(GrainEffect::StrengthAndSize {
-                    strength: GrainStrength::Weak,
-                    size: grain_size,
-                }),
-                _ => Some(GrainEffect::Off),
-            }
-        } else {
-            match strength_exif.value().to_lowercase().as_str() {
-                "strong" => Some(GrainEffect::OnlyStrength {
-                    strength: GrainStrength::Strong,
-                }),
-                "weak" => Some(GrainEffect::OnlyStrength {
-                    strength: GrainStrength::Weak,
-                }),
-                _ => Some(GrainEffect::Off),
-            }
-        }
+        Ok(
+            match size_exif.value().to_string().to_lowercase().as_str() {
+                "small" => GrainEffect::StrengthAndSize {
+                    strength,
+                    size: GrainSize::Small,
+                },
+                "large" => GrainEffect::StrengthAndSize {
+                    strength,
+                    size: GrainSize::Large,
+                },
+                _ => GrainEffect::OnlyStrength { strength },
+            },
+        )
     }
 }
 
+fn from_number<D>(data: &D) -> Option<GrainEffect>
+where
+    D: FindExifData + ?Sized,
+{
+    let strength = data.find_number(TAG)?;
+
+    trace!("GrainStrength::from_number: {:?}", strength);
+
+    let strength = match strength {
+        0 => return Some(GrainEffect::Off),
+        32 => GrainStrength::Weak,
+        64 => GrainStrength::Strong,
+        _ => return None,
+    };
+
+    Some(match data.find_number(SIZE_TAG) {
+        Some(16) => GrainEffect::StrengthAndSize {
+            strength,
+            size: GrainSize::Small,
+        },
+        Some(32) => GrainEffect::StrengthAndSize {
+            strength,
+            size: GrainSize::Large,
+        },
+        _ => GrainEffect::OnlyStrength { strength },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_strong_grain() {
@@ -149,4 +192,42 @@ mod tests {
 
         assert_eq!(GrainEffect::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_reads_the_print_of_unknown_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("GrainEffectRoughness", "Strong"),
+            ExifData::new("GrainEffectSize", "Small"),
+            ExifData::new("GrainEffectRoughness#", "96"),
+        ];
+
+        assert_eq!(
+            GrainEffect::try_from_exif(&exif),
+            Ok(GrainEffect::StrengthAndSize {
+                strength: GrainStrength::Strong,
+                size: GrainSize::Small,
+            })
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("GrainEffectRoughness", "Fort"),
+            ExifData::new("GrainEffectRoughness#", "32"),
+            ExifData::new("GrainEffectSize#", "32"),
+        ];
+
+        assert_eq!(
+            GrainEffect::from_exif(&exif),
+            Some(GrainEffect::StrengthAndSize {
+                strength: GrainStrength::Weak,
+                size: GrainSize::Large,
+            })
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("GrainEffectRoughness#", "0")];
+
+        assert_eq!(GrainEffect::from_exif(&exif), Some(GrainEffect::Off));
+    }
 }
@@ -1,22 +1,70 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use super::legacy;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::report::Field;
 use crate::recipe::HighISONoiseReduction;
 use log::trace;
 
+const TAG: &str = "NoiseReduction";
+
 impl FromExifData for HighISONoiseReduction {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = data.find("NoiseReduction")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for HighISONoiseReduction {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(value) = data.find_number(TAG).and_then(from_number) {
+            trace!("HighISONoiseReduction::from_exif: raw {:?}", value);
 
-        trace!("HighISONoiseReduction::from_exif: {:?}", exif);
+            return Ok(HighISONoiseReduction { value });
+        }
 
-        let value: i64 = exif.try_into().ok()?;
+        if let Some(exif) = data.find(TAG) {
+            trace!("HighISONoiseReduction::from_exif: {:?}", exif);
+
+            if let Ok(value) = i64::try_from(exif.clone()) {
+                return Ok(HighISONoiseReduction { value });
+            }
+
+            if let Some(value) = legacy::from_word(
+                data,
+                Field::HighIsoNoiseReduction,
+                &exif.value().to_string(),
+            ) {
+                return Ok(HighISONoiseReduction { value });
+            }
+        }
+
+        Err(FieldError::not_read(data, TAG))
+    }
+}
 
-        Some(HighISONoiseReduction { value })
+fn from_number(number: i64) -> Option<i64> {
+    match number {
+        0x2e0 => Some(-4),
+        0x2c0 => Some(-3),
+        0x200 => Some(-2),
+        0x280 => Some(-1),
+        0x000 => Some(0),
+        0x180 => Some(1),
+        0x100 => Some(2),
+        0x1c0 => Some(3),
+        0x1e0 => Some(4),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_positive_numbers() {
@@ -44,4 +92,17 @@ mod tests {
 
         assert_eq!(HighISONoiseReduction::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("NoiseReduction", "0 (normal)"),
+            ExifData::new("NoiseReduction#", "736"),
+        ];
+
+        assert_eq!(
+            HighISONoiseReduction::from_exif(&exif),
+            Some(HighISONoiseReduction { value: -4 })
+        );
+    }
 }
@@ -0,0 +1,207 @@
+use crate::camera::CameraModel;
+use crate::exif::FindExifData;
+use crate::recipe::report::Field;
+use crate::recipe::TransSensor;
+use log::trace;
+
+/// Words printed instead of a number, e.g. `Medium Hard` for `+1 (medium
+/// hard)`, by the cameras of one sensor generation.
+struct Words {
+    sharpness: &'static [(&'static str, i64)],
+    tone: &'static [(&'static str, i64)],
+    color: &'static [(&'static str, i64)],
+    noise_reduction: &'static [(&'static str, i64)],
+}
+
+/// X-Trans I cameras go from -2 to +2 and name the noise reduction like the
+/// color.
+const TRANS_I: Words = Words {
+    sharpness: &[
+        ("soft", -2),
+        ("medium soft", -1),
+        ("normal", 0),
+        ("medium hard", 1),
+        ("hard", 2),
+    ],
+    tone: &[
+        ("soft", -2),
+        ("medium soft", -1),
+        ("normal", 0),
+        ("medium hard", 1),
+        ("hard", 2),
+    ],
+    color: &[
+        ("low", -2),
+        ("medium low", -1),
+        ("normal", 0),
+        ("medium high", 1),
+        ("high", 2),
+    ],
+    noise_reduction: &[
+        ("low", -2),
+        ("medium low", -1),
+        ("normal", 0),
+        ("medium high", 1),
+        ("high", 2),
+    ],
+};
+
+/// X-Trans II cameras go from -2 to +2 as well, but name the noise reduction
+/// by its strength.
+const TRANS_II: Words = Words {
+    sharpness: TRANS_I.sharpness,
+    tone: TRANS_I.tone,
+    color: TRANS_I.color,
+    noise_reduction: &[
+        ("weak", -2),
+        ("medium weak", -1),
+        ("normal", 0),
+        ("medium strong", 1),
+        ("strong", 2),
+    ],
+};
+
+/// Later cameras, and unknown models, from -4 to +4. Older exiftool versions
+/// print `standard` for `normal`.
+const LATER: Words = Words {
+    sharpness: &[
+        ("softest", -4),
+        ("very soft", -3),
+        ("soft", -2),
+        ("medium soft", -1),
+        ("normal", 0),
+        ("standard", 0),
+        ("medium hard", 1),
+        ("hard", 2),
+        ("very hard", 3),
+        ("hardest", 4),
+    ],
+    tone: &[
+        ("softest", -4),
+        ("very soft", -3),
+        ("soft", -2),
+        ("medium soft", -1),
+        ("normal", 0),
+        ("standard", 0),
+        ("medium hard", 1),
+        ("hard", 2),
+        ("very hard", 3),
+        ("hardest", 4),
+    ],
+    color: &[
+        ("lowest", -4),
+        ("very low", -3),
+        ("low", -2),
+        ("medium low", -1),
+        ("normal", 0),
+        ("standard", 0),
+        ("medium high", 1),
+        ("high", 2),
+        ("very high", 3),
+        ("highest", 4),
+    ],
+    noise_reduction: &[
+        ("weakest", -4),
+        ("very weak", -3),
+        ("weak", -2),
+        ("medium weak", -1),
+        ("normal", 0),
+        ("standard", 0),
+        ("medium strong", 1),
+        ("strong", 2),
+        ("very strong", 3),
+        ("strongest", 4),
+    ],
+};
+
+impl Words {
+    fn of(sensor: Option<&TransSensor>) -> &'static Words {
+        match sensor {
+            Some(TransSensor::TransI) => &TRANS_I,
+            Some(TransSensor::TransII) => &TRANS_II,
+            _ => &LATER,
+        }
+    }
+
+    fn table(&self, field: Field) -> Option<&'static [(&'static str, i64)]> {
+        match field {
+            Field::Sharpness => Some(self.sharpness),
+            Field::ToneCurve => Some(self.tone),
+            Field::Color => Some(self.color),
+            Field::HighIsoNoiseReduction => Some(self.noise_reduction),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a setting printed as a word, e.g. `Medium Hard`, with the words
+/// of the sensor generation of the camera. Words outside the range of the
+/// model in the camera database, e.g. `Very Hard` on an X-Pro1, are not known.
+pub(crate) fn from_word<D>(data: &D, field: Field, word: &str) -> Option<i64>
+where
+    D: FindExifData + ?Sized,
+{
+    let camera = data
+        .find("Model")
+        .and_then(|exif| CameraModel::lookup(&exif.value().to_string()));
+    let table = Words::of(camera.as_ref().map(|camera| &camera.sensor)).table(field)?;
+
+    let word = word.trim().to_lowercase();
+    let value = table
+        .iter()
+        .find(|(name, _)| *name == word)
+        .map(|(_, value)| *value)?;
+
+    let range = camera.and_then(|camera| camera.range(field));
+
+    trace!("legacy::from_word: {} {} {:?}", word, value, range);
+
+    match range {
+        Some(range) if !range.contains(value as f64) => None,
+        _ => Some(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifData;
+
+    #[test]
+    fn it_reads_words() {
+        let exif: Vec<ExifData> = vec![];
+
+        assert_eq!(from_word(&exif, Field::Sharpness, "Medium Hard"), Some(1));
+        assert_eq!(from_word(&exif, Field::Color, "Medium High"), Some(1));
+        assert_eq!(
+            from_word(&exif, Field::HighIsoNoiseReduction, "Weak"),
+            Some(-2)
+        );
+        assert_eq!(from_word(&exif, Field::ToneCurve, " soft "), Some(-2));
+        assert_eq!(from_word(&exif, Field::Sharpness, "Film Simulation"), None);
+    }
+
+    #[test]
+    fn it_uses_the_scale_of_the_camera() {
+        let x_pro1: Vec<ExifData> = vec![ExifData::new("Model", "X-Pro1")];
+        let x_pro2: Vec<ExifData> = vec![ExifData::new("Model", "X-Pro2")];
+
+        assert_eq!(from_word(&x_pro1, Field::Sharpness, "Hard"), Some(2));
+        assert_eq!(from_word(&x_pro1, Field::Sharpness, "Very Hard"), None);
+        assert_eq!(from_word(&x_pro2, Field::Sharpness, "Very Hard"), Some(3));
+    }
+
+    #[test]
+    fn it_uses_the_words_of_the_generation() {
+        let x_pro1: Vec<ExifData> = vec![ExifData::new("Model", "X-Pro1")];
+        let x100s: Vec<ExifData> = vec![ExifData::new("Model", "X100S")];
+        let nr = Field::HighIsoNoiseReduction;
+
+        assert_eq!(from_word(&x_pro1, nr, "Low"), Some(-2));
+        assert_eq!(from_word(&x_pro1, nr, "Weak"), None);
+        assert_eq!(from_word(&x100s, nr, "Weak"), Some(-2));
+        assert_eq!(from_word(&x100s, nr, "Low"), None);
+        assert_eq!(from_word(&x100s, Field::ToneCurve, "Medium Hard"), Some(1));
+        assert_eq!(from_word(&x100s, Field::ToneCurve, "Standard"), None);
+    }
+}
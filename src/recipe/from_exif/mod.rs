@@ -5,12 +5,33 @@ mod color_chrome_fx_blue;
 mod d_range_priority;
 mod dynamic_range;
 mod film_simulation;
-mod fujifilm_recipe_details;
+mod firmware_version;
+pub(crate) mod fujifilm_recipe_details;
+mod fujifilm_video_recipe;
 mod grain_effect;
 mod high_iso_noise_reduction;
-mod monochromatic_color;
+mod legacy;
+pub(crate) mod monochromatic_color;
+mod processing_mode;
 mod sharpness;
+mod shooting_mode;
 mod tone_curve;
 mod trans_sensor;
+mod video_gamma;
 mod white_balance;
 mod white_balance_shift;
+
+use crate::exif::FieldError;
+
+/// The value, or its default when it is not known. For the settings that are
+/// Off unless the camera wrote a known strength.
+pub(crate) fn or_default<T>(result: Result<T, FieldError>) -> Option<T>
+where
+    T: Default,
+{
+    match result {
+        Ok(value) => Some(value),
+        Err(FieldError::TagMissing { .. }) => None,
+        Err(_) => Some(T::default()),
+    }
+}
@@ -1,28 +1,143 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
-use crate::recipe::{MonochromaticColor, MonochromaticColorShift};
+use crate::camera::CameraModel;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::report::Field;
+use crate::recipe::{FilmSimulation, MonochromaticColor, MonochromaticColorShift};
 use log::trace;
 
+const WC_TAG: &str = "BWAdjustment";
+const MG_TAG: &str = "BWMagentaGreen";
+
 impl FromExifData for MonochromaticColor {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        // TODO: Adjust Toning for X-Trans III Cameras (X-T3, X-T30, etc.)
-        let exif_adjustment = data.find("BWAdjustment")?;
-        let exif_magenta = data.find("BWMagentaGreen")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for MonochromaticColor {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let (Some(wc), Some(mg)) = (data.find_number(WC_TAG), data.find_number(MG_TAG)) {
+            trace!("MonochromaticColor::from_exif: raw WC {:?} MG {:?}", wc, mg);
+
+            return color_shift(data, wc, mg);
+        }
+
+        let (Some(exif_adjustment), Some(exif_magenta)) = (data.find(WC_TAG), data.find(MG_TAG))
+        else {
+            // The tags are only written for monochrome film simulations
+            if FilmSimulation::from_exif(data).is_some_and(|film| !is_toned(&film)) {
+                return Ok(MonochromaticColor::default());
+            }
+
+            let tag = if data.find(WC_TAG).is_none() {
+                WC_TAG
+            } else {
+                MG_TAG
+            };
+
+            return Err(FieldError::not_read(data, tag));
+        };
 
         trace!("MonochromaticColor::from_exif: WC {:?}", exif_adjustment);
         trace!("MonochromaticColor::from_exif: MG {:?}", exif_magenta);
 
-        let wc: i64 = exif_adjustment.try_into().ok()?;
-        let mg: i64 = exif_magenta.try_into().ok()?;
+        let Ok(wc) = i64::try_from(exif_adjustment.clone()) else {
+            return Err(FieldError::unknown(WC_TAG, exif_adjustment.value()));
+        };
+        let Ok(mg) = i64::try_from(exif_magenta.clone()) else {
+            return Err(FieldError::unknown(MG_TAG, exif_magenta.value()));
+        };
+
+        color_shift(data, wc, mg)
+    }
+}
+
+fn color_shift<D>(data: &D, wc: i64, mg: i64) -> Result<MonochromaticColor, FieldError>
+where
+    D: FindExifData + ?Sized,
+{
+    if !in_range(data, wc) {
+        return Err(FieldError::unknown(WC_TAG, wc));
+    }
+    if !in_range(data, mg) {
+        return Err(FieldError::unknown(MG_TAG, mg));
+    }
+
+    Ok(MonochromaticColor::ColorShift {
+        shift: MonochromaticColorShift { wc, mg },
+    })
+}
+
+/// X-Trans III cameras (X-Pro2, X-T2, X-H1) only tone monochrome film
+/// simulations warm or cool, written in `BWAdjustment` without a
+/// `BWMagentaGreen`.
+pub(crate) fn strength_from_exif<D>(data: &D) -> Result<MonochromaticColor, FieldError>
+where
+    D: FindExifData + ?Sized,
+{
+    if let Some(value) = data.find_number(WC_TAG) {
+        trace!("MonochromaticColor::strength_from_exif: raw {:?}", value);
+
+        return strength(data, value);
+    }
+
+    let Some(exif) = data.find(WC_TAG) else {
+        if FilmSimulation::from_exif(data).is_some_and(|film| !is_toned(&film)) {
+            return Ok(MonochromaticColor::Strength { value: 0 });
+        }
+
+        return Err(FieldError::not_read(data, WC_TAG));
+    };
+
+    trace!("MonochromaticColor::strength_from_exif: {:?}", exif);
 
-        Some(MonochromaticColor::ColorShift {
-            shift: MonochromaticColorShift { wc, mg },
-        })
+    match i64::try_from(exif.clone()) {
+        Ok(value) => strength(data, value),
+        Err(_) => Err(FieldError::unknown(WC_TAG, exif.value())),
     }
 }
 
+fn strength<D>(data: &D, value: i64) -> Result<MonochromaticColor, FieldError>
+where
+    D: FindExifData + ?Sized,
+{
+    if !in_range(data, value) {
+        return Err(FieldError::unknown(WC_TAG, value));
+    }
+
+    Ok(MonochromaticColor::Strength { value })
+}
+
+/// Values outside the range of the model in the camera database cannot have
+/// been recorded by it. Models that are not in it can record any value.
+fn in_range<D>(data: &D, value: i64) -> bool
+where
+    D: FindExifData + ?Sized,
+{
+    let range = data
+        .find("Model")
+        .and_then(|exif| CameraModel::lookup(&exif.value().to_string()))
+        .and_then(|camera| camera.range(Field::MonochromaticColor));
+
+    range.is_none_or(|range| range.contains(value as f64))
+}
+
+fn is_toned(film_simulation: &FilmSimulation) -> bool {
+    matches!(
+        film_simulation,
+        FilmSimulation::Acros { .. } | FilmSimulation::Monochrome { .. }
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_monochromatic_color() {
@@ -62,4 +177,92 @@ mod tests {
 
         assert_eq!(MonochromaticColor::from_exif(&exif), None,);
     }
+
+    #[test]
+    fn it_has_no_toning_in_color() {
+        let exif: Vec<ExifData> = vec![ExifData::new("FilmMode", "Classic Chrome")];
+
+        assert_eq!(
+            MonochromaticColor::try_from_exif(&exif),
+            Ok(MonochromaticColor::default())
+        );
+
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Saturation", "B&W"),
+            ExifData::new("BWAdjustment", "3"),
+        ];
+
+        assert_eq!(
+            MonochromaticColor::try_from_exif(&exif),
+            Err(FieldError::missing("BWMagentaGreen"))
+        );
+    }
+
+    #[test]
+    fn it_parses_trans_iii_strength() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("FilmMode", "Acros"),
+            ExifData::new("BWAdjustment", "-4"),
+        ];
+
+        assert_eq!(
+            strength_from_exif(&exif),
+            Ok(MonochromaticColor::Strength { value: -4 })
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("FilmMode", "Velvia")];
+
+        assert_eq!(
+            strength_from_exif(&exif),
+            Ok(MonochromaticColor::Strength { value: 0 })
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("BWAdjustment", "warm")];
+
+        assert_eq!(
+            strength_from_exif(&exif),
+            Err(FieldError::unknown("BWAdjustment", "warm"))
+        );
+    }
+
+    #[test]
+    fn it_rejects_values_out_of_the_camera_range() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Model", "X-T2"),
+            ExifData::new("BWAdjustment", "12"),
+        ];
+
+        assert_eq!(
+            strength_from_exif(&exif),
+            Err(FieldError::unknown("BWAdjustment", 12))
+        );
+
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Model", "X-T3"),
+            ExifData::new("BWAdjustment", "3"),
+            ExifData::new("BWMagentaGreen", "-10"),
+        ];
+
+        assert_eq!(
+            MonochromaticColor::try_from_exif(&exif),
+            Err(FieldError::unknown("BWMagentaGreen", -10))
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("BWAdjustment", "0"),
+            ExifData::new("BWMagentaGreen", "0"),
+            ExifData::new("BWAdjustment#", "3"),
+            ExifData::new("BWMagentaGreen#", "-2"),
+        ];
+
+        assert_eq!(
+            MonochromaticColor::from_exif(&exif),
+            Some(MonochromaticColor::ColorShift {
+                shift: MonochromaticColorShift { wc: 3, mg: -2 }
+            })
+        );
+    }
 }
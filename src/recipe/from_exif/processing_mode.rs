@@ -0,0 +1,186 @@
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::{AdvancedFilter, FilmSimulation, PartialColorHue, ProcessingMode};
+use log::trace;
+
+const TAG: &str = "AdvancedFilter";
+
+impl FromExifData for ProcessingMode {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for ProcessingMode {
+    /// An Advanced Filter when one was used, the film simulation otherwise.
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        match AdvancedFilter::try_from_exif(data) {
+            Ok(filter) => Ok(ProcessingMode::AdvancedFilter(filter)),
+            Err(FieldError::TagMissing { .. }) => {
+                FilmSimulation::try_from_exif(data).map(ProcessingMode::FilmSimulation)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl FromExifData for AdvancedFilter {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for AdvancedFilter {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(number) = data.find_number(TAG) {
+            trace!("AdvancedFilter::from_exif: raw {:#x}", number);
+
+            // Written as 0 by some cameras when no filter is used
+            if number == 0 {
+                return Err(FieldError::missing(TAG));
+            }
+
+            return from_number(number).ok_or_else(|| FieldError::unknown(TAG, number));
+        }
+
+        let Some(exif) = data.find(TAG) else {
+            return Err(FieldError::missing(TAG));
+        };
+
+        trace!("AdvancedFilter::from_exif: {:?}", exif);
+
+        let value = exif.value().to_string().to_lowercase();
+        let filter = match value.as_str() {
+            "pop color" => AdvancedFilter::PopColor,
+            "hi key" => AdvancedFilter::HiKey,
+            "low key" => AdvancedFilter::LowKey,
+            "toy camera" => AdvancedFilter::ToyCamera,
+            "miniature" => AdvancedFilter::Miniature,
+            "dynamic tone" => AdvancedFilter::DynamicTone,
+            "soft focus" => AdvancedFilter::SoftFocus,
+            value => {
+                let hue = value
+                    .strip_prefix("partial color ")
+                    .and_then(partial_color_hue)
+                    .ok_or_else(|| FieldError::unknown(TAG, exif.value()))?;
+
+                AdvancedFilter::PartialColor { hue }
+            }
+        };
+
+        Ok(filter)
+    }
+}
+
+fn from_number(number: i64) -> Option<AdvancedFilter> {
+    let filter = match number {
+        0x10000 => AdvancedFilter::PopColor,
+        0x20000 => AdvancedFilter::HiKey,
+        0x30000 => AdvancedFilter::ToyCamera,
+        0x40000 => AdvancedFilter::Miniature,
+        0x50000 => AdvancedFilter::DynamicTone,
+        0x60001..=0x60006 => {
+            let hue = match number & 0xf {
+                1 => PartialColorHue::Red,
+                2 => PartialColorHue::Yellow,
+                3 => PartialColorHue::Green,
+                4 => PartialColorHue::Blue,
+                5 => PartialColorHue::Orange,
+                _ => PartialColorHue::Purple,
+            };
+
+            AdvancedFilter::PartialColor { hue }
+        }
+        0x70000 => AdvancedFilter::SoftFocus,
+        0x90000 => AdvancedFilter::LowKey,
+        _ => return None,
+    };
+
+    Some(filter)
+}
+
+fn partial_color_hue(name: &str) -> Option<PartialColorHue> {
+    match name {
+        "red" => Some(PartialColorHue::Red),
+        "yellow" => Some(PartialColorHue::Yellow),
+        "green" => Some(PartialColorHue::Green),
+        "blue" => Some(PartialColorHue::Blue),
+        "orange" => Some(PartialColorHue::Orange),
+        "purple" => Some(PartialColorHue::Purple),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifData;
+
+    #[test]
+    fn it_parses_advanced_filters() {
+        let exif: Vec<ExifData> = vec![ExifData::new("AdvancedFilter", "Toy Camera")];
+        assert_eq!(
+            ProcessingMode::from_exif(&exif),
+            Some(ProcessingMode::AdvancedFilter(AdvancedFilter::ToyCamera))
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("AdvancedFilter", "Partial Color Blue")];
+        assert_eq!(
+            AdvancedFilter::from_exif(&exif),
+            Some(AdvancedFilter::PartialColor {
+                hue: PartialColorHue::Blue
+            })
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("AdvancedFilter", "Unknown"),
+            ExifData::new("AdvancedFilter#", "393221"),
+        ];
+
+        assert_eq!(
+            AdvancedFilter::from_exif(&exif),
+            Some(AdvancedFilter::PartialColor {
+                hue: PartialColorHue::Orange
+            })
+        );
+    }
+
+    #[test]
+    fn it_uses_the_film_simulation_without_filter() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("FilmMode", "Classic Chrome"),
+            ExifData::new("AdvancedFilter#", "0"),
+        ];
+
+        assert_eq!(
+            ProcessingMode::from_exif(&exif),
+            Some(ProcessingMode::FilmSimulation(
+                FilmSimulation::ClassicChrome
+            ))
+        );
+    }
+
+    #[test]
+    fn it_reports_unknown_filters() {
+        let exif: Vec<ExifData> = vec![ExifData::new("AdvancedFilter#", "1048576")];
+
+        assert_eq!(
+            ProcessingMode::try_from_exif(&exif),
+            Err(FieldError::unknown("AdvancedFilter", 0x100000))
+        );
+    }
+}
@@ -1,22 +1,68 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use super::legacy;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::report::Field;
 use crate::recipe::Sharpness;
 use log::trace;
 
+const TAG: &str = "Sharpness";
+
 impl FromExifData for Sharpness {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = data.find("Sharpness")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for Sharpness {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(value) = data.find_number(TAG).and_then(from_number) {
+            trace!("Sharpness::from_exif: raw {:?}", value);
+
+            return Ok(Sharpness { value });
+        }
 
-        trace!("Sharpness::from_exif: {:?}", exif);
+        if let Some(exif) = data.find(TAG) {
+            trace!("Sharpness::from_exif: {:?}", exif);
 
-        let value: i64 = exif.try_into().ok()?;
+            if let Ok(value) = i64::try_from(exif.clone()) {
+                return Ok(Sharpness { value });
+            }
+
+            if let Some(value) =
+                legacy::from_word(data, Field::Sharpness, &exif.value().to_string())
+            {
+                return Ok(Sharpness { value });
+            }
+        }
+
+        Err(FieldError::not_read(data, TAG))
+    }
+}
 
-        Some(Sharpness { value })
+fn from_number(number: i64) -> Option<i64> {
+    match number {
+        0x00 => Some(-4),
+        0x01 => Some(-3),
+        0x02 => Some(-2),
+        0x82 => Some(-1),
+        0x03 => Some(0),
+        0x84 => Some(1),
+        0x04 => Some(2),
+        0x05 => Some(3),
+        0x06 => Some(4),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_positive_numbers() {
@@ -38,4 +84,24 @@ mod tests {
 
         assert_eq!(Sharpness::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_reads_the_print_of_unknown_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Sharpness", "Medium Hard"),
+            ExifData::new("Sharpness#", "7"),
+        ];
+
+        assert_eq!(Sharpness::try_from_exif(&exif), Ok(Sharpness { value: 1 }));
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("Sharpness", "+1 (moyennement dur)"),
+            ExifData::new("Sharpness#", "130"),
+        ];
+
+        assert_eq!(Sharpness::from_exif(&exif), Some(Sharpness { value: -1 }));
+    }
 }
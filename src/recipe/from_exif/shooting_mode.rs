@@ -0,0 +1,290 @@
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::{
+    CropMode, MultipleExposure, Panorama, PanoramaDirection, PixelShift, ProMode, ShootingMode,
+};
+use log::trace;
+
+const PICTURE_MODE_TAG: &str = "PictureMode";
+const EXPOSURE_COUNT_TAG: &str = "ExposureCount";
+const CROP_MODE_TAG: &str = "CropMode";
+const PANORAMA_ANGLE_TAG: &str = "PanoramaAngle";
+const PANORAMA_DIRECTION_TAG: &str = "PanoramaDirection";
+const PIXEL_SHIFT_SHOTS_TAG: &str = "PixelShiftShots";
+
+/// Picture modes merging several shots into the image.
+#[derive(Debug, PartialEq)]
+enum Composite {
+    Hdr,
+    Panorama,
+    Pro(ProMode),
+}
+
+impl FromExifData for ShootingMode {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for ShootingMode {
+    /// The modes of the MakerNote, none of them being a standard shot. Values
+    /// not known are read as the mode not being used.
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if [PICTURE_MODE_TAG, EXPOSURE_COUNT_TAG, CROP_MODE_TAG]
+            .iter()
+            .all(|tag| data.find(tag).is_none())
+        {
+            return Err(FieldError::missing(PICTURE_MODE_TAG));
+        }
+
+        let composite = composite(data);
+        trace!("ShootingMode::from_exif: composite {:?}", composite);
+
+        let hdr = composite == Some(Composite::Hdr);
+
+        let panorama = (composite == Some(Composite::Panorama)).then(|| Panorama {
+            angle: number(data, PANORAMA_ANGLE_TAG).and_then(|angle| u32::try_from(angle).ok()),
+            direction: panorama_direction(data),
+        });
+
+        let pixel_shift = number(data, PIXEL_SHIFT_SHOTS_TAG)
+            .and_then(|shots| u32::try_from(shots).ok())
+            .filter(|shots| *shots > 1)
+            .map(|shots| PixelShift { shots });
+
+        let pro_mode = match &composite {
+            Some(Composite::Pro(mode)) => Some(mode.clone()),
+            _ => None,
+        };
+
+        // HDR, panoramas, pro modes & pixel shift count their shots as well
+        let multiple_exposure = number(data, EXPOSURE_COUNT_TAG)
+            .and_then(|exposures| u32::try_from(exposures).ok())
+            .filter(|exposures| *exposures > 1)
+            .filter(|_| composite.is_none() && pixel_shift.is_none())
+            .map(|exposures| MultipleExposure { exposures });
+
+        Ok(ShootingMode {
+            hdr,
+            multiple_exposure,
+            panorama,
+            pixel_shift,
+            pro_mode,
+            crop: crop_mode(data),
+        })
+    }
+}
+
+/// Raw number of `tag`, or its value when it is printed as a number.
+fn number<D>(data: &D, tag: &str) -> Option<i64>
+where
+    D: FindExifData + ?Sized,
+{
+    data.find_number(tag)
+        .or_else(|| data.find(tag)?.value().numbers()?.first().copied())
+}
+
+fn composite<D>(data: &D) -> Option<Composite>
+where
+    D: FindExifData + ?Sized,
+{
+    if let Some(number) = data.find_number(PICTURE_MODE_TAG) {
+        return match number {
+            0x16 => Some(Composite::Panorama),
+            0x18 => Some(Composite::Pro(ProMode::ProLowLight)),
+            0x19 => Some(Composite::Pro(ProMode::ProFocus)),
+            0x30 => Some(Composite::Hdr),
+            _ => None,
+        };
+    }
+
+    let exif = data.find(PICTURE_MODE_TAG)?;
+
+    match exif.value().to_string().to_lowercase().as_str() {
+        "panorama" => Some(Composite::Panorama),
+        "pro low-light" => Some(Composite::Pro(ProMode::ProLowLight)),
+        "pro focus" => Some(Composite::Pro(ProMode::ProFocus)),
+        "hdr" => Some(Composite::Hdr),
+        _ => None,
+    }
+}
+
+fn panorama_direction<D>(data: &D) -> Option<PanoramaDirection>
+where
+    D: FindExifData + ?Sized,
+{
+    if let Some(number) = data.find_number(PANORAMA_DIRECTION_TAG) {
+        return match number {
+            1 => Some(PanoramaDirection::Right),
+            2 => Some(PanoramaDirection::Up),
+            3 => Some(PanoramaDirection::Left),
+            4 => Some(PanoramaDirection::Down),
+            _ => None,
+        };
+    }
+
+    let exif = data.find(PANORAMA_DIRECTION_TAG)?;
+
+    match exif.value().to_string().to_lowercase().as_str() {
+        "right" => Some(PanoramaDirection::Right),
+        "up" => Some(PanoramaDirection::Up),
+        "left" => Some(PanoramaDirection::Left),
+        "down" => Some(PanoramaDirection::Down),
+        _ => None,
+    }
+}
+
+fn crop_mode<D>(data: &D) -> Option<CropMode>
+where
+    D: FindExifData + ?Sized,
+{
+    if let Some(number) = data.find_number(CROP_MODE_TAG) {
+        trace!("CropMode::from_exif: raw {:?}", number);
+
+        return match number {
+            1 => Some(CropMode::FullFrame),
+            2 => Some(CropMode::SportsFinder),
+            4 => Some(CropMode::ElectronicShutter),
+            _ => None,
+        };
+    }
+
+    let exif = data.find(CROP_MODE_TAG)?;
+    trace!("CropMode::from_exif: {:?}", exif);
+
+    match exif.value().to_string().to_lowercase().as_str() {
+        "full-frame on gfx" => Some(CropMode::FullFrame),
+        "sports finder mode" => Some(CropMode::SportsFinder),
+        "electronic shutter 1.25x crop" => Some(CropMode::ElectronicShutter),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifData;
+
+    #[test]
+    fn it_parses_standard_shots() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("PictureMode", "Aperture-priority AE"),
+            ExifData::new("ExposureCount", "1"),
+            ExifData::new("CropMode", "n/a"),
+        ];
+
+        let mode = ShootingMode::from_exif(&exif).unwrap();
+
+        assert_eq!(mode, ShootingMode::default());
+        assert!(!mode.is_special());
+    }
+
+    #[test]
+    fn it_parses_hdr() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("PictureMode", "HDR"),
+            ExifData::new("ExposureCount", "3"),
+        ];
+
+        let mode = ShootingMode::from_exif(&exif).unwrap();
+
+        assert!(mode.hdr);
+        assert_eq!(mode.multiple_exposure, None);
+        assert!(mode.is_special());
+    }
+
+    #[test]
+    fn it_parses_pro_modes() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("PictureMode", "Pro Focus"),
+            ExifData::new("ExposureCount", "3"),
+        ];
+
+        let mode = ShootingMode::from_exif(&exif).unwrap();
+
+        assert_eq!(mode.pro_mode, Some(ProMode::ProFocus));
+        assert_eq!(mode.multiple_exposure, None);
+        assert!(mode.is_special());
+    }
+
+    #[test]
+    fn it_parses_multiple_exposures() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("PictureMode", "Manual"),
+            ExifData::new("ExposureCount", "2"),
+        ];
+
+        assert_eq!(
+            ShootingMode::from_exif(&exif).unwrap().multiple_exposure,
+            Some(MultipleExposure { exposures: 2 })
+        );
+    }
+
+    #[test]
+    fn it_parses_panoramas() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("PictureMode", "Panorama"),
+            ExifData::new("PanoramaAngle", "180"),
+            ExifData::new("PanoramaDirection", "Left"),
+        ];
+
+        assert_eq!(
+            ShootingMode::from_exif(&exif).unwrap().panorama,
+            Some(Panorama {
+                angle: Some(180),
+                direction: Some(PanoramaDirection::Left)
+            })
+        );
+    }
+
+    #[test]
+    fn it_parses_pixel_shift() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("ExposureCount", "16"),
+            ExifData::new("PixelShiftShots", "16"),
+        ];
+
+        let mode = ShootingMode::from_exif(&exif).unwrap();
+
+        assert_eq!(mode.pixel_shift, Some(PixelShift { shots: 16 }));
+        assert_eq!(mode.multiple_exposure, None);
+    }
+
+    #[test]
+    fn it_parses_crop_modes() {
+        let exif: Vec<ExifData> = vec![ExifData::new("CropMode", "Sports Finder Mode")];
+
+        assert_eq!(
+            ShootingMode::from_exif(&exif).unwrap().crop,
+            Some(CropMode::SportsFinder)
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("PictureMode", "Panoramique"),
+            ExifData::new("PictureMode#", "48"),
+            ExifData::new("CropMode", "Recadrage"),
+            ExifData::new("CropMode#", "4"),
+        ];
+
+        let mode = ShootingMode::from_exif(&exif).unwrap();
+
+        assert!(mode.hdr);
+        assert_eq!(mode.panorama, None);
+        assert_eq!(mode.crop, Some(CropMode::ElectronicShutter));
+    }
+
+    #[test]
+    fn it_does_not_parse_when_not_found() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Foo", "HDR")];
+
+        assert_eq!(ShootingMode::from_exif(&exif), None);
+    }
+}
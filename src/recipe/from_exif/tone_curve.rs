@@ -1,28 +1,87 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use super::legacy;
+use crate::exif::{ExifData, FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::report::Field;
 use crate::recipe::ToneCurve;
 use log::trace;
 
+const HIGHLIGHT_TAG: &str = "HighlightTone";
+const SHADOW_TAG: &str = "ShadowTone";
+
 impl FromExifData for ToneCurve {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let h_exif = data.find("HighlightTone")?;
-        let s_exif = data.find("ShadowTone")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for ToneCurve {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let (Some(highlights), Some(shadows)) = (
+            data.find_number(HIGHLIGHT_TAG).and_then(from_number),
+            data.find_number(SHADOW_TAG).and_then(from_number),
+        ) {
+            trace!(
+                "ToneCurve::from_exif: raw H {:?} S {:?}",
+                highlights,
+                shadows
+            );
+
+            return Ok(ToneCurve {
+                highlights,
+                shadows,
+            });
+        }
+
+        let Some(h_exif) = data.find(HIGHLIGHT_TAG) else {
+            return Err(FieldError::not_read(data, HIGHLIGHT_TAG));
+        };
+        let Some(s_exif) = data.find(SHADOW_TAG) else {
+            return Err(FieldError::not_read(data, SHADOW_TAG));
+        };
 
         trace!("ToneCurve::from_exif: H {:?}", h_exif);
         trace!("ToneCurve::from_exif: S {:?}", s_exif);
 
-        let highlights: f64 = h_exif.try_into().ok()?;
-        let shadows: f64 = s_exif.try_into().ok()?;
+        let Some(highlights) = tone(data, &h_exif) else {
+            return Err(FieldError::unknown(HIGHLIGHT_TAG, h_exif.value()));
+        };
+        let Some(shadows) = tone(data, &s_exif) else {
+            return Err(FieldError::unknown(SHADOW_TAG, s_exif.value()));
+        };
 
-        Some(ToneCurve {
+        Ok(ToneCurve {
             highlights,
             shadows,
         })
     }
 }
 
+/// Stored as multiples of -16, in half steps from -4 to +4.
+fn from_number(number: i64) -> Option<f64> {
+    match number {
+        -64..=64 if number % 8 == 0 => Some(-number as f64 / 16.0),
+        _ => None,
+    }
+}
+
+fn tone<D>(data: &D, exif: &ExifData) -> Option<f64>
+where
+    D: FindExifData + ?Sized,
+{
+    f64::try_from(exif.clone()).ok().or_else(|| {
+        legacy::from_word(data, Field::ToneCurve, &exif.value().to_string()).map(|v| v as f64)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_tone_curve() {
@@ -74,4 +133,40 @@ mod tests {
 
         assert_eq!(ToneCurve::from_exif(&exif), None,);
     }
+
+    #[test]
+    fn it_reads_the_print_of_unknown_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("HighlightTone", "+1 (medium hard)"),
+            ExifData::new("ShadowTone", "-1 (medium soft)"),
+            ExifData::new("HighlightTone#", "-12"),
+            ExifData::new("ShadowTone#", "16"),
+        ];
+
+        assert_eq!(
+            ToneCurve::try_from_exif(&exif),
+            Ok(ToneCurve {
+                highlights: 1.0,
+                shadows: -1.0,
+            })
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("HighlightTone", "0 (normal)"),
+            ExifData::new("ShadowTone", "0 (normal)"),
+            ExifData::new("HighlightTone#", "32"),
+            ExifData::new("ShadowTone#", "-8"),
+        ];
+
+        assert_eq!(
+            ToneCurve::from_exif(&exif),
+            Some(ToneCurve {
+                highlights: -2.0,
+                shadows: 0.5,
+            })
+        );
+    }
 }
@@ -1,57 +1,40 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use crate::camera::CameraModel;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::TransSensor;
 use log::trace;
 
 impl FromExifData for TransSensor {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
-        let exif = data.find("Model")?;
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for TransSensor {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
+        let exif = data
+            .find("Model")
+            .ok_or_else(|| FieldError::missing("Model"))?;
 
         trace!("TransSensor::from_exif: {:?}", exif);
 
-        match exif.value().to_lowercase().as_str() {
-            "x-pro1" => Some(TransSensor::TransI),
-            "x-e1" => Some(TransSensor::TransI),
-            "x-m1" => Some(TransSensor::TransI),
-            "x100s" => Some(TransSensor::TransII),
-            "x-e2" => Some(TransSensor::TransII),
-            "x-t1" => Some(TransSensor::TransII),
-            "x100t" => Some(TransSensor::TransII),
-            "x-t10" => Some(TransSensor::TransII),
-            "x-e2s" => Some(TransSensor::TransII),
-            "x70" => Some(TransSensor::TransII),
-            "x20" => Some(TransSensor::TransII),
-            "xq1" => Some(TransSensor::TransII),
-            "x30" => Some(TransSensor::TransII),
-            "xq2" => Some(TransSensor::TransII),
-            "x-pro2" => Some(TransSensor::TransIII),
-            "x-t2" => Some(TransSensor::TransIII),
-            "x100f" => Some(TransSensor::TransIII),
-            "x-t20" => Some(TransSensor::TransIII),
-            "x-e3" => Some(TransSensor::TransIII),
-            "x-h1" => Some(TransSensor::TransIII),
-            "x-t3" => Some(TransSensor::TransIV),
-            "x-t4" => Some(TransSensor::TransIV),
-            "x-t30" => Some(TransSensor::TransIV),
-            "x-t30 ii" => Some(TransSensor::TransIV),
-            "x-pro3" => Some(TransSensor::TransIV),
-            "x100v" => Some(TransSensor::TransIV),
-            "x-e4" => Some(TransSensor::TransIV),
-            "x-s10" => Some(TransSensor::TransIV),
-            "x-s20" => Some(TransSensor::TransIV),
-            "x-m5" => Some(TransSensor::TransIV),
-            "x-h2s" => Some(TransSensor::TransV),
-            "x-h2" => Some(TransSensor::TransV),
-            "x-t5" => Some(TransSensor::TransV),
-            "x-t50" => Some(TransSensor::TransV),
-            "x100vi" => Some(TransSensor::TransV),
-            _ => None,
-        }
+        let model = exif.value().to_string();
+
+        CameraModel::lookup(&model)
+            .map(|camera| camera.sensor)
+            .ok_or(FieldError::UnsupportedModel { model })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_trans_i_sensor() {
@@ -171,6 +154,33 @@ mod tests {
 
         let exif: Vec<ExifData> = vec![ExifData::new("Model", "X100VI")];
         assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::TransV));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "X-E5")];
+        assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::TransV));
+    }
+
+    #[test]
+    fn it_parses_gfx_sensors() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "GFX 50S")];
+        assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::Gfx50));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "GFX50S II")];
+        assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::Gfx50));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "GFX100S")];
+        assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::Gfx100));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "GFX100RF")];
+        assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::Gfx100));
+    }
+
+    #[test]
+    fn it_parses_bayer_sensor() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "X-A7")];
+        assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::Bayer));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "X-T200")];
+        assert_eq!(TransSensor::from_exif(&exif), Some(TransSensor::Bayer));
     }
 
     #[test]
@@ -178,4 +188,21 @@ mod tests {
         let exif: Vec<ExifData> = vec![ExifData::new("Foo", "X100VI")];
         assert_eq!(TransSensor::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_reports_unsupported_models() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Model", "FinePix S1")];
+        assert_eq!(
+            TransSensor::try_from_exif(&exif),
+            Err(FieldError::UnsupportedModel {
+                model: "FinePix S1".to_string()
+            })
+        );
+
+        let exif: Vec<ExifData> = vec![ExifData::new("Foo", "X100VI")];
+        assert_eq!(
+            TransSensor::try_from_exif(&exif),
+            Err(FieldError::missing("Model"))
+        );
+    }
 }
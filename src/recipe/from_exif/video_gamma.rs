@@ -0,0 +1,68 @@
+use crate::exif::{FindExifData, FromExifData};
+use crate::recipe::VideoGamma;
+use log::trace;
+
+impl FromExifData for VideoGamma {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(mode) = data.find_number("VideoRecordingMode") {
+            trace!("VideoGamma::from_exif: raw {:?}", mode);
+
+            match mode {
+                0x00 => return Some(VideoGamma::Standard),
+                0x10 => return Some(VideoGamma::FLog),
+                0x20 => return Some(VideoGamma::Hlg),
+                0x30 => return Some(VideoGamma::FLog2),
+                _ => {}
+            }
+        }
+
+        let exif = data.find("VideoRecordingMode")?;
+
+        trace!("VideoGamma::from_exif: {:?}", exif);
+
+        match exif.value().to_string().to_lowercase().as_str() {
+            "normal" => Some(VideoGamma::Standard),
+            "f-log" => Some(VideoGamma::FLog),
+            "f-log2" => Some(VideoGamma::FLog2),
+            "hlg" => Some(VideoGamma::Hlg),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exif::ExifData;
+
+    #[test]
+    fn it_parses_video_recording_mode() {
+        let exif: Vec<ExifData> = vec![ExifData::new("VideoRecordingMode", "F-Log2")];
+
+        assert_eq!(VideoGamma::from_exif(&exif), Some(VideoGamma::FLog2));
+
+        let exif: Vec<ExifData> = vec![ExifData::new("VideoRecordingMode", "HLG")];
+
+        assert_eq!(VideoGamma::from_exif(&exif), Some(VideoGamma::Hlg));
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("VideoRecordingMode", "Normal"),
+            ExifData::new("VideoRecordingMode#", "16"),
+        ];
+
+        assert_eq!(VideoGamma::from_exif(&exif), Some(VideoGamma::FLog));
+    }
+
+    #[test]
+    fn it_does_not_parse_when_not_found() {
+        let exif: Vec<ExifData> = vec![ExifData::new("Foo", "F-Log")];
+
+        assert_eq!(VideoGamma::from_exif(&exif), None);
+    }
+}
@@ -1,13 +1,35 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
 use crate::recipe::{WBShift, WhiteBalance};
 use log::trace;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+const TAG: &str = "WhiteBalance";
+const TEMPERATURE_TAG: &str = "ColorTemperature";
+
 impl FromExifData for WhiteBalance {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        Self::try_from_exif(data).ok()
+    }
+}
+
+impl TryFromExifData for WhiteBalance {
+    fn try_from_exif<D>(data: &D) -> Result<Self, FieldError>
+    where
+        D: FindExifData + ?Sized,
+    {
         let shift = WBShift::from_exif(data).unwrap_or_default();
-        let exif = data.find("WhiteBalance")?;
+
+        if let Some(white_balance) = from_number(data, shift.clone()) {
+            return Ok(white_balance);
+        }
+
+        let Some(exif) = data.find(TAG) else {
+            return Err(FieldError::not_read(data, TAG));
+        };
 
         trace!("WhiteBalance::from_exif: {:?}", exif);
 
@@ -17,45 +39,87 @@ impl FromExifData for WhiteBalance {
             ).unwrap()
         });
 
-        let captures = RE.captures(exif.value())?;
+        let Some(captures) = exif.value().as_str().and_then(|value| RE.captures(value)) else {
+            return Err(FieldError::unknown(TAG, exif.value()));
+        };
 
         trace!("WhiteBalance Captures: {:?}", captures);
 
-        match captures[1].to_lowercase().as_str() {
-            "auto" => Some(WhiteBalance::Auto { shift }),
-            "auto (white priority)" => Some(WhiteBalance::AutoWhitePriority { shift }),
-            "auto (ambiance priority)" => Some(WhiteBalance::AutoAmbiencePriority { shift }),
-            "custom" => Some(WhiteBalance::Custom1 { shift }),
-            "custom2" => Some(WhiteBalance::Custom2 { shift }),
-            "custom3" => Some(WhiteBalance::Custom3 { shift }),
-            "daylight" => Some(WhiteBalance::Daylight { shift }),
-            "cloudy" => Some(WhiteBalance::Cloudy { shift }),
-            "daylight fluorescent" => Some(WhiteBalance::FluorescentLight1 { shift }),
-            "day white fluorescent" => Some(WhiteBalance::FluorescentLight2 { shift }),
-            "white fluorescent" => Some(WhiteBalance::FluorescentLight3 { shift }),
-            "incandescent" => Some(WhiteBalance::Incandescent { shift }),
-            "underwater" => Some(WhiteBalance::Underwater { shift }),
+        let white_balance = match captures[1].to_lowercase().as_str() {
+            "auto" => WhiteBalance::Auto { shift },
+            "auto (white priority)" => WhiteBalance::AutoWhitePriority { shift },
+            "auto (ambiance priority)" => WhiteBalance::AutoAmbiencePriority { shift },
+            "custom" => WhiteBalance::Custom1 { shift },
+            "custom2" => WhiteBalance::Custom2 { shift },
+            "custom3" => WhiteBalance::Custom3 { shift },
+            "daylight" => WhiteBalance::Daylight { shift },
+            "cloudy" => WhiteBalance::Cloudy { shift },
+            "daylight fluorescent" => WhiteBalance::FluorescentLight1 { shift },
+            "day white fluorescent" => WhiteBalance::FluorescentLight2 { shift },
+            "white fluorescent" => WhiteBalance::FluorescentLight3 { shift },
+            "incandescent" => WhiteBalance::Incandescent { shift },
+            "underwater" => WhiteBalance::Underwater { shift },
             "kelvin" => {
-                if let Some(exif) = data.find("ColorTemperature") {
-                    trace!("WhiteBalance::Temperature {:?}", exif.value());
-
-                    if let Ok(temperature) = exif.value().parse::<i32>() {
-                        Some(WhiteBalance::Kelvin { temperature, shift })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
+                let Some(exif) = data.find(TEMPERATURE_TAG) else {
+                    return Err(FieldError::missing(TEMPERATURE_TAG));
+                };
+
+                trace!("WhiteBalance::Temperature {:?}", exif.value());
+
+                let Some(temperature) = exif.value().as_i64() else {
+                    return Err(FieldError::unknown(TEMPERATURE_TAG, exif.value()));
+                };
+
+                WhiteBalance::Kelvin {
+                    temperature: temperature as i32,
+                    shift,
                 }
             }
-            _ => None,
+            _ => return Err(FieldError::unknown(TAG, exif.value())),
+        };
+
+        Ok(white_balance)
+    }
+}
+
+fn from_number<D>(data: &D, shift: WBShift) -> Option<WhiteBalance>
+where
+    D: FindExifData + ?Sized,
+{
+    let number = data.find_number(TAG)?;
+
+    trace!("WhiteBalance::from_number: {:#x}", number);
+
+    match number {
+        0x000 => Some(WhiteBalance::Auto { shift }),
+        0x001 => Some(WhiteBalance::AutoWhitePriority { shift }),
+        0x002 => Some(WhiteBalance::AutoAmbiencePriority { shift }),
+        0x100 => Some(WhiteBalance::Daylight { shift }),
+        0x200 => Some(WhiteBalance::Cloudy { shift }),
+        0x300 => Some(WhiteBalance::FluorescentLight1 { shift }),
+        0x301 => Some(WhiteBalance::FluorescentLight2 { shift }),
+        0x302 => Some(WhiteBalance::FluorescentLight3 { shift }),
+        0x400 => Some(WhiteBalance::Incandescent { shift }),
+        0x600 => Some(WhiteBalance::Underwater { shift }),
+        0xf00 => Some(WhiteBalance::Custom1 { shift }),
+        0xf01 => Some(WhiteBalance::Custom2 { shift }),
+        0xf02 => Some(WhiteBalance::Custom3 { shift }),
+        0xff0 => {
+            let temperature = data.find_number(TEMPERATURE_TAG)?;
+
+            Some(WhiteBalance::Kelvin {
+                temperature: temperature as i32,
+                shift,
+            })
         }
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_parses_white_balance_auto() {
@@ -376,4 +440,31 @@ mod tests {
         let exif: Vec<ExifData> = vec![ExifData::new("Foo", "Auto")];
         assert_eq!(WhiteBalance::from_exif(&exif), None,);
     }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("WhiteBalance", "Lumière du jour"),
+            ExifData::new("WhiteBalance#", "256"),
+            ExifData::new("WhiteBalanceFineTune#", "40 -60"),
+        ];
+        assert_eq!(
+            WhiteBalance::from_exif(&exif),
+            Some(WhiteBalance::Daylight {
+                shift: WBShift { red: 2, blue: -3 }
+            })
+        );
+
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("WhiteBalance#", "4080"),
+            ExifData::new("ColorTemperature#", "4700"),
+        ];
+        assert_eq!(
+            WhiteBalance::from_exif(&exif),
+            Some(WhiteBalance::Kelvin {
+                shift: WBShift { red: 0, blue: 0 },
+                temperature: 4700,
+            })
+        );
+    }
 }
@@ -1,11 +1,27 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
+use crate::exif::{FindExifData, FromExifData};
 use crate::recipe::WBShift;
 use log::trace;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
 impl FromExifData for WBShift {
-    fn from_exif(data: &[ExifData]) -> Option<Self> {
+    fn from_exif<D>(data: &D) -> Option<Self>
+    where
+        D: FindExifData + ?Sized,
+    {
+        if let Some(numbers) = data.find_numbers("WhiteBalanceFineTune") {
+            trace!("WBShift::from_exif: raw {:?}", numbers);
+
+            let shift = match numbers[..] {
+                [red, blue] => from_number(red).zip(from_number(blue)),
+                _ => None,
+            };
+
+            if let Some((red, blue)) = shift {
+                return Some(WBShift { red, blue });
+            }
+        }
+
         let exif = data.find("WhiteBalanceFineTune")?;
 
         trace!("WBShift::from_exif: {:?}", exif);
@@ -13,7 +29,7 @@ impl FromExifData for WBShift {
         static RE: Lazy<Regex> =
             Lazy::new(|| Regex::new(r"(?i:(red ?[+\-0-9]+), ?(blue ?[+\-0-9]+))").unwrap());
 
-        let captures = RE.captures(exif.value())?;
+        let captures = RE.captures(exif.value().as_str()?)?;
 
         trace!("WBShift Captures: {:?}", captures);
 
@@ -45,9 +61,18 @@ impl FromExifData for WBShift {
     }
 }
 
+/// Stored in steps of 20, from -9 to +9.
+fn from_number(number: i64) -> Option<i32> {
+    match number {
+        -180..=180 if number % 20 == 0 => Some((number / 20) as i32),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::exif::ExifData;
 
     #[test]
     fn it_should_parse_white_balance_shift() {
@@ -78,4 +103,30 @@ mod tests {
         let exif: Vec<ExifData> = vec![ExifData::new("Foo", "red 40, blue -60")];
         assert_eq!(WBShift::from_exif(&exif), None);
     }
+
+    #[test]
+    fn it_reads_the_print_of_unknown_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("WhiteBalanceFineTune", "Red +40, Blue -20"),
+            ExifData::new("WhiteBalanceFineTune#", "45 -20"),
+        ];
+
+        assert_eq!(
+            WBShift::from_exif(&exif),
+            Some(WBShift { red: 2, blue: -1 })
+        );
+    }
+
+    #[test]
+    fn it_prefers_raw_numbers() {
+        let exif: Vec<ExifData> = vec![
+            ExifData::new("WhiteBalanceFineTune", "Rouge +80, Bleu -40"),
+            ExifData::new("WhiteBalanceFineTune#", "80 -40"),
+        ];
+
+        assert_eq!(
+            WBShift::from_exif(&exif),
+            Some(WBShift { red: 4, blue: -2 })
+        );
+    }
 }
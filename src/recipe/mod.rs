@@ -1,9 +1,11 @@
+use builder::SettingsBuilder;
 use serde::{Deserialize, Serialize, Serializer};
 use strum_macros::{Display, EnumString};
 
 pub mod builder;
 pub mod from_exif;
 pub mod read;
+pub mod report;
 pub mod str;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,11 +19,99 @@ impl FujifilmRecipe {
     }
 }
 
+/// The `settings` are deserialized for the `sensor` next to them, as several
+/// sensors record the same settings. Recipes serialized with a
+/// `film_simulation` instead of a `processing_mode` are read as well.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "RecipeDetailsFields")]
 pub struct FujifilmRecipeDetails {
-    pub film_simulation: FilmSimulation,
+    /// The film simulation, or the Advanced Filter used instead of one.
+    pub processing_mode: ProcessingMode,
+    /// HDR, multiple exposure, panorama, pixel shift, pro mode or crop the
+    /// image was shot with, if any.
+    #[serde(default)]
+    pub shooting_mode: ShootingMode,
     pub sensor: TransSensor,
     pub settings: Settings,
+    /// Read from a DNG or TIFF converted from the original raw (Adobe DNG
+    /// Converter, X RAW Studio) instead of a file written by the camera.
+    #[serde(default)]
+    pub converted: bool,
+}
+
+impl FujifilmRecipeDetails {
+    /// `None` when an Advanced Filter was used instead of a film simulation.
+    pub fn film_simulation(&self) -> Option<&FilmSimulation> {
+        match &self.processing_mode {
+            ProcessingMode::FilmSimulation(film_simulation) => Some(film_simulation),
+            ProcessingMode::AdvancedFilter(_) => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RecipeDetailsFields {
+    #[serde(default)]
+    film_simulation: Option<FilmSimulation>,
+    #[serde(default)]
+    processing_mode: Option<ProcessingMode>,
+    #[serde(default)]
+    shooting_mode: ShootingMode,
+    sensor: TransSensor,
+    settings: SettingsBuilder,
+    #[serde(default)]
+    converted: bool,
+}
+
+impl TryFrom<RecipeDetailsFields> for FujifilmRecipeDetails {
+    type Error = &'static str;
+
+    fn try_from(fields: RecipeDetailsFields) -> Result<Self, Self::Error> {
+        let processing_mode = match (fields.processing_mode, fields.film_simulation) {
+            (Some(processing_mode), _) => processing_mode,
+            (None, Some(film_simulation)) => ProcessingMode::FilmSimulation(film_simulation),
+            (None, None) => return Err("missing field `processing_mode`"),
+        };
+
+        Ok(FujifilmRecipeDetails {
+            settings: fields.sensor.settings(fields.settings),
+            processing_mode,
+            shooting_mode: fields.shooting_mode,
+            sensor: fields.sensor,
+            converted: fields.converted,
+        })
+    }
+}
+
+/// Recipe of a movie (MOV/MP4) recorded by a Fujifilm camera.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FujifilmVideoRecipe {
+    /// `None` when recording F-Log, which skips the film simulation.
+    pub film_simulation: Option<FilmSimulation>,
+    pub gamma: VideoGamma,
+    pub white_balance: WhiteBalance,
+    /// `None` when recording F-Log, which skips the tone curve.
+    pub tone_curve: Option<ToneCurve>,
+}
+
+/// Gamma the movie was recorded with, standard being the film simulation's.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Display, EnumString, Default)]
+pub enum VideoGamma {
+    #[default]
+    #[strum(serialize = "Standard")]
+    Standard,
+    #[strum(serialize = "F-Log")]
+    FLog,
+    #[strum(serialize = "F-Log2")]
+    FLog2,
+    #[strum(serialize = "HLG")]
+    Hlg,
+}
+
+impl VideoGamma {
+    pub fn is_log(&self) -> bool {
+        matches!(self, VideoGamma::FLog | VideoGamma::FLog2)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Display, PartialEq)]
@@ -32,8 +122,13 @@ pub enum Settings {
     TransIII(TransIIISettings),
     TransIV(TransIVSettings),
     TransV(TransVSettings),
+    Gfx50(Gfx50Settings),
+    Gfx100(Gfx100Settings),
+    Bayer(BayerSettings),
 }
 
+/// Sensor generation of the camera, which tells the settings it records. GFX
+/// and Bayer sensors are not X-Trans, but have their own settings as well.
 #[derive(Debug, Deserialize, PartialEq, Display, EnumString, Clone)]
 pub enum TransSensor {
     #[strum(serialize = "Trans Sensor I", to_string = "TransI")]
@@ -46,6 +141,15 @@ pub enum TransSensor {
     TransIV,
     #[strum(serialize = "Trans Sensor V", to_string = "TransV")]
     TransV,
+    /// 51MP medium format sensor of the GFX 50 cameras.
+    #[strum(serialize = "GFX 50 Sensor", to_string = "Gfx50")]
+    Gfx50,
+    /// 102MP medium format sensor of the GFX 100 cameras.
+    #[strum(serialize = "GFX 100 Sensor", to_string = "Gfx100")]
+    Gfx100,
+    /// Bayer sensor of the X-A, X-T100/X-T200 & XF10 cameras.
+    #[strum(serialize = "Bayer Sensor", to_string = "Bayer")]
+    Bayer,
 }
 
 impl Serialize for TransSensor {
@@ -98,6 +202,118 @@ impl Serialize for FilmSimulation {
     }
 }
 
+/// How the camera rendered the image, with a film simulation or an Advanced
+/// Filter replacing it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display)]
+pub enum ProcessingMode {
+    #[strum(to_string = "{0}")]
+    FilmSimulation(FilmSimulation),
+    #[strum(to_string = "{0}")]
+    AdvancedFilter(AdvancedFilter),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display)]
+pub enum AdvancedFilter {
+    #[strum(to_string = "Pop Color")]
+    PopColor,
+    #[strum(to_string = "Hi Key")]
+    HiKey,
+    #[strum(to_string = "Low Key")]
+    LowKey,
+    #[strum(to_string = "Toy Camera")]
+    ToyCamera,
+    #[strum(to_string = "Miniature")]
+    Miniature,
+    #[strum(to_string = "Dynamic Tone")]
+    DynamicTone,
+    /// Only keeps the colors of `hue`, everything else is monochrome.
+    #[strum(to_string = "Partial Color {hue}")]
+    PartialColor { hue: PartialColorHue },
+    #[strum(to_string = "Soft Focus")]
+    SoftFocus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display, EnumString)]
+pub enum PartialColorHue {
+    Red,
+    Yellow,
+    Green,
+    Blue,
+    Orange,
+    Purple,
+}
+
+/// Special capture modes, which merge several shots or crop the sensor. The
+/// film simulation alone does not describe these images, see
+/// [`ShootingMode::is_special`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ShootingMode {
+    /// Whether the shot merges several exposures into a high dynamic range.
+    pub hdr: bool,
+    pub multiple_exposure: Option<MultipleExposure>,
+    pub panorama: Option<Panorama>,
+    pub pixel_shift: Option<PixelShift>,
+    pub pro_mode: Option<ProMode>,
+    pub crop: Option<CropMode>,
+}
+
+impl ShootingMode {
+    /// Whether the image was shot with any of the special modes.
+    pub fn is_special(&self) -> bool {
+        *self != ShootingMode::default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultipleExposure {
+    pub exposures: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Panorama {
+    /// Angle swept, in degrees.
+    pub angle: Option<u32>,
+    pub direction: Option<PanoramaDirection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display)]
+pub enum PanoramaDirection {
+    Right,
+    Up,
+    Left,
+    Down,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PixelShift {
+    /// Shots merged into the image.
+    pub shots: u32,
+}
+
+/// Modes merging a burst of shots, to lower the noise or extend the depth of
+/// field.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display)]
+pub enum ProMode {
+    #[strum(to_string = "Pro Low-light")]
+    ProLowLight,
+    #[strum(to_string = "Pro Focus")]
+    ProFocus,
+}
+
+/// Part of the sensor used instead of all of it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display)]
+pub enum CropMode {
+    /// 35mm format of a GFX.
+    #[strum(to_string = "Full-frame")]
+    FullFrame,
+    /// 1.25x crop, framed in a wider viewfinder.
+    #[strum(to_string = "Sports Finder")]
+    SportsFinder,
+    /// 1.25x crop of the electronic shutter, for faster bursts.
+    #[strum(to_string = "Electronic Shutter 1.25x Crop")]
+    ElectronicShutter,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display, EnumString, Default)]
 pub enum MonochromaticFilter {
     #[strum(serialize = "Standard", to_string = "")]
@@ -340,6 +556,40 @@ pub struct TransVSettings {
     pub clarity: Clarity,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Gfx100Settings {
+    pub white_balance: WhiteBalance,
+    pub dynamic_range: DynamicRange,
+    pub d_range_priority: DRangePriority,
+    pub grain_effect: GrainEffect,
+    pub color_chrome_effect: ColorChromeEffect,
+    pub color_chrome_fx_blue: ColorChromeEffectFxBlue,
+    pub tone_curve: ToneCurve,
+    pub color: Color,
+    pub monochromatic_color: MonochromaticColor,
+    pub sharpness: Sharpness,
+    pub high_iso_noise_reduction: HighISONoiseReduction,
+    pub clarity: Clarity,
+}
+
+/// The GFX 50S & 50R have no D-Range Priority, Color Chrome FX Blue nor
+/// Clarity, which only the GFX50S II records.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Gfx50Settings {
+    pub white_balance: WhiteBalance,
+    pub dynamic_range: DynamicRange,
+    pub d_range_priority: Option<DRangePriority>,
+    pub grain_effect: GrainEffect,
+    pub color_chrome_effect: ColorChromeEffect,
+    pub color_chrome_fx_blue: Option<ColorChromeEffectFxBlue>,
+    pub tone_curve: ToneCurve,
+    pub color: Color,
+    pub monochromatic_color: MonochromaticColor,
+    pub sharpness: Sharpness,
+    pub high_iso_noise_reduction: HighISONoiseReduction,
+    pub clarity: Option<Clarity>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TransIVSettings {
     pub white_balance: WhiteBalance,
@@ -388,6 +638,16 @@ pub struct TransISettings {
     pub high_iso_noise_reduction: HighISONoiseReduction,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BayerSettings {
+    pub white_balance: WhiteBalance,
+    pub dynamic_range: DynamicRange,
+    pub tone_curve: ToneCurve,
+    pub color: Color,
+    pub sharpness: Sharpness,
+    pub high_iso_noise_reduction: HighISONoiseReduction,
+}
+
 impl Settings {
     pub fn get_values(&self) -> SettingsTuple {
         match self.clone() {
@@ -461,6 +721,48 @@ impl Settings {
                 Some(settings.color_chrome_fx_blue),
                 Some(settings.monochromatic_color),
             ),
+            Settings::Gfx50(settings) => (
+                settings.white_balance,
+                settings.dynamic_range,
+                settings.d_range_priority,
+                settings.tone_curve,
+                settings.color,
+                settings.sharpness,
+                settings.clarity,
+                settings.high_iso_noise_reduction,
+                Some(settings.grain_effect),
+                Some(settings.color_chrome_effect),
+                settings.color_chrome_fx_blue,
+                Some(settings.monochromatic_color),
+            ),
+            Settings::Gfx100(settings) => (
+                settings.white_balance,
+                settings.dynamic_range,
+                Some(settings.d_range_priority),
+                settings.tone_curve,
+                settings.color,
+                settings.sharpness,
+                Some(settings.clarity),
+                settings.high_iso_noise_reduction,
+                Some(settings.grain_effect),
+                Some(settings.color_chrome_effect),
+                Some(settings.color_chrome_fx_blue),
+                Some(settings.monochromatic_color),
+            ),
+            Settings::Bayer(settings) => (
+                settings.white_balance,
+                settings.dynamic_range,
+                None,
+                settings.tone_curve,
+                settings.color,
+                settings.sharpness,
+                None,
+                settings.high_iso_noise_reduction,
+                None,
+                None,
+                None,
+                None,
+            ),
         }
     }
 }
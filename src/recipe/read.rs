@@ -1,5 +1,7 @@
-use crate::exif::{ExifData, FindExifData, FromExifData};
-use crate::recipe::FujifilmRecipeDetails;
+use crate::exif::{FieldError, FindExifData, FromExifData, TryFromExifData};
+use crate::recipe::from_exif::fujifilm_recipe_details::read_with_report;
+use crate::recipe::report::{Field, FieldStatus, RecipeReport};
+use crate::recipe::{FujifilmRecipeDetails, FujifilmVideoRecipe};
 use snafu::prelude::*;
 
 /// Reads a Fujifilm Recipe from exif data.
@@ -20,18 +22,21 @@ use snafu::prelude::*;
 ///
 /// // Recipe will contain details like FilmSimulation, WhiteBalance, etc.
 /// if let Some(details) = recipe {
-///     println!("Film Simulation: {:?}", details.film_simulation);
+///     println!("Film Simulation: {:?}", details.film_simulation());
 ///     println!("White Balance: {:?}", details.settings);
 /// }
 /// # Ok(())
 /// # }
 /// ```
-pub fn from_exif(data: &[ExifData]) -> Result<Option<FujifilmRecipeDetails>, Error> {
+pub fn from_exif<D>(data: &D) -> Result<Option<FujifilmRecipeDetails>, Error>
+where
+    D: FindExifData + ?Sized,
+{
     let maker = data.find("Make").context(MakeNotFoundSnafu)?;
 
-    if maker.value().to_lowercase() == "fujifilm" {
+    if maker.value().to_string().to_lowercase() == "fujifilm" {
         let recipe_details =
-            FujifilmRecipeDetails::from_exif(data).context(FujifilmRecipeDetailsSnafu)?;
+            FujifilmRecipeDetails::try_from_exif(data).context(FujifilmRecipeDetailsSnafu)?;
 
         return Ok(Some(recipe_details));
     }
@@ -39,11 +44,109 @@ pub fn from_exif(data: &[ExifData]) -> Result<Option<FujifilmRecipeDetails>, Err
     Ok(None)
 }
 
+/// Same as [`from_exif`], also reporting how each setting was read: parsed,
+/// or given its default value because its tag is missing or has a value that
+/// is not known.
+///
+/// # Example
+///
+/// ```
+/// use fuji::native;
+/// use fuji::recipe::read;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::path::Path::new("tests/img/DSCF5230.JPG");
+/// let metadata = native::read_metadata(&path)?;
+///
+/// if let Some(report) = read::from_exif_with_report(&metadata)? {
+///     for field in report.defaulted() {
+///         println!("{}: {:?}", field.field, field.status);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_exif_with_report<D>(data: &D) -> Result<Option<RecipeReport>, Error>
+where
+    D: FindExifData + ?Sized,
+{
+    let maker = data.find("Make").context(MakeNotFoundSnafu)?;
+
+    if maker.value().to_string().to_lowercase() == "fujifilm" {
+        let report = read_with_report(data).context(FujifilmRecipeDetailsSnafu)?;
+
+        return Ok(Some(report));
+    }
+
+    Ok(None)
+}
+
+/// Same as [`from_exif`], failing instead of giving a setting its default
+/// value, see [`from_exif_with_report`].
+pub fn from_exif_strict<D>(data: &D) -> Result<Option<FujifilmRecipeDetails>, Error>
+where
+    D: FindExifData + ?Sized,
+{
+    let Some(report) = from_exif_with_report(data)? else {
+        return Ok(None);
+    };
+
+    if let Some(field) = report.defaulted().next() {
+        return DefaultedSnafu {
+            field: field.field,
+            status: field.status.clone(),
+        }
+        .fail();
+    }
+
+    Ok(Some(report.recipe))
+}
+
+/// Reads the recipe of a Fujifilm movie from exif data, see [`from_exif`].
+///
+/// # Example
+///
+/// ```no_run
+/// use fuji::native;
+/// use fuji::recipe::read;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let path = std::path::Path::new("DSCF0001.MOV");
+/// let metadata = native::read_metadata(&path)?;
+///
+/// if let Some(recipe) = read::video_from_exif(&metadata)? {
+///     println!("Gamma: {}", recipe.gamma);
+///     println!("Film Simulation: {:?}", recipe.film_simulation);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn video_from_exif<D>(data: &D) -> Result<Option<FujifilmVideoRecipe>, Error>
+where
+    D: FindExifData + ?Sized,
+{
+    let maker = data.find("Make").context(MakeNotFoundSnafu)?;
+
+    if maker.value().to_string().to_lowercase() == "fujifilm" {
+        let recipe = FujifilmVideoRecipe::from_exif(data).context(FujifilmVideoRecipeSnafu)?;
+
+        return Ok(Some(recipe));
+    }
+
+    Ok(None)
+}
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display("Failed to find Camera Maker"))]
     MakeNotFound,
 
-    #[snafu(display("Could not find Fujifilm Recipe details in EXIF"))]
-    FujifilmRecipeDetails,
+    #[snafu(display("Could not read Fujifilm Recipe details from EXIF: {}", source))]
+    FujifilmRecipeDetails { source: FieldError },
+
+    #[snafu(display("Could not find Fujifilm Video Recipe in EXIF"))]
+    FujifilmVideoRecipe,
+
+    #[snafu(display("{} was not read, {}", field, status))]
+    Defaulted { field: Field, status: FieldStatus },
 }
@@ -0,0 +1,217 @@
+use crate::camera::{CameraModel, FirmwareVersion};
+use crate::exif::FieldError;
+use crate::recipe::{FilmSimulation, FujifilmRecipeDetails};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use strum_macros::Display;
+
+/// A setting of [`crate::recipe::Settings`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Display)]
+#[serde(rename_all = "snake_case")]
+pub enum Field {
+    #[strum(to_string = "White Balance")]
+    WhiteBalance,
+    #[strum(to_string = "Dynamic Range")]
+    DynamicRange,
+    #[strum(to_string = "D-Range Priority")]
+    DRangePriority,
+    #[strum(to_string = "Grain Effect")]
+    GrainEffect,
+    #[strum(to_string = "Color Chrome Effect")]
+    ColorChromeEffect,
+    #[strum(to_string = "Color Chrome FX Blue")]
+    ColorChromeFxBlue,
+    #[strum(to_string = "Tone Curve")]
+    ToneCurve,
+    #[strum(to_string = "Color")]
+    Color,
+    #[strum(to_string = "Monochromatic Color")]
+    MonochromaticColor,
+    #[strum(to_string = "Sharpness")]
+    Sharpness,
+    #[strum(to_string = "High ISO Noise Reduction")]
+    HighIsoNoiseReduction,
+    #[strum(to_string = "Clarity")]
+    Clarity,
+}
+
+/// How a setting was read from the EXIF.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum FieldStatus {
+    /// The setting was read from its tags.
+    Parsed,
+    /// `tag` is not in the EXIF, the setting has its default value.
+    Missing { tag: String },
+    /// `tag` has a value that is not known, the setting has its default value.
+    Unrecognized { tag: String, value: String },
+}
+
+impl FieldStatus {
+    pub fn is_parsed(&self) -> bool {
+        matches!(self, FieldStatus::Parsed)
+    }
+
+    pub(crate) fn of<T>(result: &Result<T, FieldError>) -> Self {
+        match result {
+            Ok(_) => FieldStatus::Parsed,
+            Err(FieldError::TagMissing { tag }) => FieldStatus::Missing { tag: tag.clone() },
+            Err(FieldError::UnknownValue { tag, value }) => FieldStatus::Unrecognized {
+                tag: tag.clone(),
+                value: value.clone(),
+            },
+            Err(FieldError::UnsupportedModel { model }) => FieldStatus::Unrecognized {
+                tag: "Model".to_string(),
+                value: model.clone(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for FieldStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldStatus::Parsed => write!(f, "parsed"),
+            FieldStatus::Missing { tag } => write!(f, "{} is missing", tag),
+            FieldStatus::Unrecognized { tag, value } => {
+                write!(f, "{} has an unknown value {:?}", tag, value)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FieldReport {
+    pub field: Field,
+    #[serde(flatten)]
+    pub status: FieldStatus,
+}
+
+/// Something in a recipe the camera should not have been able to record.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecipeWarning {
+    UnsupportedSetting {
+        field: Field,
+        model: String,
+        firmware: Option<FirmwareVersion>,
+    },
+    UnsupportedFilmSimulation {
+        film_simulation: FilmSimulation,
+        model: String,
+        firmware: Option<FirmwareVersion>,
+    },
+}
+
+impl fmt::Display for RecipeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (name, model, firmware) = match self {
+            RecipeWarning::UnsupportedSetting {
+                field,
+                model,
+                firmware,
+            } => (field.to_string(), model, firmware),
+            RecipeWarning::UnsupportedFilmSimulation {
+                film_simulation,
+                model,
+                firmware,
+            } => (film_simulation.to_string(), model, firmware),
+        };
+
+        match firmware {
+            Some(firmware) => write!(
+                f,
+                "{} is not supported by the {} with firmware {}",
+                name, model, firmware
+            ),
+            None => write!(f, "{} is not supported by the {}", name, model),
+        }
+    }
+}
+
+/// A recipe and how each of its settings was read, see
+/// [`crate::recipe::read::from_exif_with_report`].
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RecipeReport {
+    pub recipe: FujifilmRecipeDetails,
+    pub fields: Vec<FieldReport>,
+    /// Firmware of the camera, from the `Software` tag.
+    pub firmware: Option<FirmwareVersion>,
+    /// What the camera supports with its firmware, `None` for models that
+    /// are not in the camera database.
+    pub camera: Option<CameraModel>,
+    pub warnings: Vec<RecipeWarning>,
+}
+
+impl RecipeReport {
+    /// Settings that have their default value instead of one read from the
+    /// EXIF.
+    pub fn defaulted(&self) -> impl Iterator<Item = &FieldReport> {
+        self.fields.iter().filter(|field| !field.status.is_parsed())
+    }
+
+    pub fn status(&self, field: Field) -> Option<&FieldStatus> {
+        self.fields
+            .iter()
+            .find(|report| report.field == field)
+            .map(|report| &report.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_reports_field_errors() {
+        let result: Result<i64, FieldError> = Err(FieldError::unknown("Sharpness", "Hard"));
+
+        assert_eq!(
+            FieldStatus::of(&result),
+            FieldStatus::Unrecognized {
+                tag: "Sharpness".to_string(),
+                value: "Hard".to_string()
+            }
+        );
+        assert_eq!(
+            FieldStatus::of::<i64>(&Err(FieldError::missing("Sharpness"))),
+            FieldStatus::Missing {
+                tag: "Sharpness".to_string()
+            }
+        );
+        assert_eq!(FieldStatus::of(&Ok(0)), FieldStatus::Parsed);
+    }
+
+    #[test]
+    fn it_displays_warnings() {
+        let warning = RecipeWarning::UnsupportedSetting {
+            field: Field::ColorChromeFxBlue,
+            model: "X-T3".to_string(),
+            firmware: Some(FirmwareVersion::new(2, 0)),
+        };
+
+        assert_eq!(
+            warning.to_string(),
+            "Color Chrome FX Blue is not supported by the X-T3 with firmware 2.00"
+        );
+    }
+
+    #[test]
+    fn it_serializes_statuses() {
+        let report = FieldReport {
+            field: Field::GrainEffect,
+            status: FieldStatus::Missing {
+                tag: "GrainEffectRoughness".to_string(),
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(report).unwrap(),
+            serde_json::json!({
+                "field": "grain_effect",
+                "status": "missing",
+                "tag": "GrainEffectRoughness"
+            })
+        );
+    }
+}
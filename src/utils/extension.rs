@@ -1,15 +1,28 @@
 use std::path::Path;
 
-const EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "raf"];
+const EXTENSIONS: [&str; 9] = [
+    "jpg", "jpeg", "raf", "hif", "heif", "heic", "dng", "tif", "tiff",
+];
+const VIDEO_EXTENSIONS: [&str; 2] = ["mov", "mp4"];
+
 pub fn is_valid_extension(path: &Path) -> bool {
     let extension = path.extension().unwrap_or("none".as_ref());
     let extension = extension.to_str().unwrap_or("none").to_lowercase();
 
-    let res = !path.is_dir() && EXTENSIONS.contains(&extension.as_str());
+    let res = !path.is_dir()
+        && (EXTENSIONS.contains(&extension.as_str())
+            || VIDEO_EXTENSIONS.contains(&extension.as_str()));
 
     res
 }
 
+pub fn is_video_extension(path: &Path) -> bool {
+    let extension = path.extension().unwrap_or("none".as_ref());
+    let extension = extension.to_str().unwrap_or("none").to_lowercase();
+
+    !path.is_dir() && VIDEO_EXTENSIONS.contains(&extension.as_str())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -31,4 +44,36 @@ mod tests {
         let path = Path::new("/example/path/my_image.JPEG");
         assert!(is_valid_extension(path));
     }
+
+    #[test]
+    fn it_should_be_truthy_for_heif() {
+        let path = Path::new("/example/path/my_image.HIF");
+        assert!(is_valid_extension(path));
+
+        let path = Path::new("/example/path/my_image.heif");
+        assert!(is_valid_extension(path));
+    }
+
+    #[test]
+    fn it_should_be_truthy_for_converted_raws() {
+        let path = Path::new("/example/path/my_image.DNG");
+        assert!(is_valid_extension(path));
+
+        let path = Path::new("/example/path/my_image.tif");
+        assert!(is_valid_extension(path));
+    }
+
+    #[test]
+    fn it_should_be_truthy_for_videos() {
+        let path = Path::new("/example/path/DSCF0001.MOV");
+        assert!(is_valid_extension(path));
+        assert!(is_video_extension(path));
+
+        let path = Path::new("/example/path/DSCF0001.mp4");
+        assert!(is_valid_extension(path));
+        assert!(is_video_extension(path));
+
+        let path = Path::new("/example/path/DSCF0001.JPG");
+        assert!(!is_video_extension(path));
+    }
 }
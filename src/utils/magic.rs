@@ -0,0 +1,119 @@
+const JPEG_SOI: &[u8] = &[0xff, 0xd8, 0xff];
+const RAF_MAGIC: &[u8] = b"FUJIFILMCCD-RAW";
+const TIFF_LE: &[u8] = b"II*\0";
+const TIFF_BE: &[u8] = b"MM\0*";
+const FTYP: &[u8] = b"ftyp";
+
+/// Boxes a QuickTime movie may start with when it has no `ftyp` box.
+const QUICKTIME_BOXES: &[&[u8]] = &[b"moov", b"mdat", b"wide", b"free", b"skip"];
+
+/// Brands of the HEIF still image formats, Fujifilm writes `heix` files
+/// compatible with `mif1`.
+const HEIF_BRANDS: &[&[u8]] = &[b"heic", b"heix", b"heim", b"heis", b"mif1", b"msf1"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    Jpeg,
+    Raf,
+    Heif,
+    /// TIFF or DNG, both share the TIFF header.
+    Tiff,
+    /// QuickTime (MOV) or MP4 movie.
+    QuickTime,
+}
+
+/// Detects the file type from its first bytes.
+pub fn detect_file_type(data: &[u8]) -> Option<FileType> {
+    if data.starts_with(JPEG_SOI) {
+        Some(FileType::Jpeg)
+    } else if data.starts_with(RAF_MAGIC) {
+        Some(FileType::Raf)
+    } else if is_heif(data) {
+        Some(FileType::Heif)
+    } else if data.starts_with(TIFF_LE) || data.starts_with(TIFF_BE) {
+        Some(FileType::Tiff)
+    } else if is_quicktime(data) {
+        Some(FileType::QuickTime)
+    } else {
+        None
+    }
+}
+
+/// HEIF files start with a `ftyp` box listing a HEIF brand, either as the
+/// major brand or as a compatible one.
+fn is_heif(data: &[u8]) -> bool {
+    if data.len() < 16 || &data[4..8] != FTYP {
+        return false;
+    }
+
+    let size = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+    let brands = &data[8..size.clamp(8, data.len())];
+
+    // The second field is the minor version
+    brands
+        .chunks_exact(4)
+        .enumerate()
+        .any(|(i, brand)| i != 1 && HEIF_BRANDS.contains(&brand))
+}
+
+/// Any other ISO-BMFF file, with or without a `ftyp` box, is read as a movie.
+fn is_quicktime(data: &[u8]) -> bool {
+    data.get(4..8)
+        .is_some_and(|kind| kind == FTYP || QUICKTIME_BOXES.contains(&kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_detects_jpeg() {
+        assert_eq!(
+            detect_file_type(&[0xff, 0xd8, 0xff, 0xe1]),
+            Some(FileType::Jpeg)
+        );
+    }
+
+    #[test]
+    fn it_detects_raf() {
+        assert_eq!(
+            detect_file_type(b"FUJIFILMCCD-RAW 0201"),
+            Some(FileType::Raf)
+        );
+    }
+
+    #[test]
+    fn it_detects_heif() {
+        let ftyp = b"\0\0\0\x18ftypheix\0\0\0\0mif1heic";
+        assert_eq!(detect_file_type(ftyp), Some(FileType::Heif));
+
+        let ftyp = b"\0\0\0\x14ftypisom\0\0\0\0mp41";
+        assert_ne!(detect_file_type(ftyp), Some(FileType::Heif));
+    }
+
+    #[test]
+    fn it_detects_quicktime() {
+        let ftyp = b"\0\0\0\x14ftypqt  \0\0\0\0qt  ";
+        assert_eq!(detect_file_type(ftyp), Some(FileType::QuickTime));
+
+        let ftyp = b"\0\0\0\x14ftypisom\0\0\0\0mp41";
+        assert_eq!(detect_file_type(ftyp), Some(FileType::QuickTime));
+
+        assert_eq!(
+            detect_file_type(b"\0\0\0\x08wide"),
+            Some(FileType::QuickTime)
+        );
+    }
+
+    #[test]
+    fn it_detects_tiff() {
+        assert_eq!(detect_file_type(b"II*\0\x08\0\0\0"), Some(FileType::Tiff));
+        assert_eq!(detect_file_type(b"MM\0*\0\0\0\x08"), Some(FileType::Tiff));
+    }
+
+    #[test]
+    fn it_rejects_unknown_data() {
+        assert_eq!(detect_file_type(b"GIF89a"), None);
+        assert_eq!(detect_file_type(&[]), None);
+    }
+}
@@ -1,3 +1,13 @@
+use fuji::{
+    exif::{ExifData, ExifValue},
+    native,
+    recipe::{
+        Clarity, Color, ColorChromeEffect, ColorChromeEffectFxBlue, DRangePriority, DynamicRange,
+        FilmSimulation, FujifilmRecipeDetails, GrainEffect, GrainSize, GrainStrength,
+        HighISONoiseReduction, MonochromaticColor, ProcessingMode, SettingStrength, Settings,
+        Sharpness, ShootingMode, ToneCurve, TransSensor, TransVSettings, WBShift, WhiteBalance,
+    },
+};
 use std::path::PathBuf;
 
 pub fn get_manifest_dir() -> PathBuf {
@@ -5,3 +15,99 @@ pub fn get_manifest_dir() -> PathBuf {
         .expect("Failed to get CARGO_MANIFEST_DIR")
         .into()
 }
+
+/// Fields of an X100VI JPEG with `changes` made, for bodies & modes there are
+/// no images of. A change replaces both the printed and the raw (`Tag#`)
+/// value of its tag, and [`ExifValue::Null`] removes them.
+pub fn read_with_changes(changes: &[(&str, ExifValue)]) -> Vec<ExifData> {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let mut fields = native::read_metadata(&img_path).unwrap();
+
+    for (tag, value) in changes {
+        let name = tag.trim_end_matches('#');
+        let group = fields
+            .iter()
+            .find(|exif| exif.tag().trim_end_matches('#') == name)
+            .and_then(|exif| exif.group().map(|g| g.to_string()));
+
+        fields.retain(|exif| exif.tag().trim_end_matches('#') != name);
+
+        match (value, group) {
+            (ExifValue::Null, _) => {}
+            (value, Some(group)) => fields.push(ExifData::grouped(&group, tag, value.clone())),
+            (value, None) => fields.push(ExifData::with_value(tag, value.clone())),
+        }
+    }
+
+    fields
+}
+
+/// Recipe of `tests/img/DSCF5230.JPG`, whichever way its metadata is read.
+pub fn classic_neg_recipe() -> FujifilmRecipeDetails {
+    FujifilmRecipeDetails {
+        processing_mode: ProcessingMode::FilmSimulation(FilmSimulation::ClassicNeg),
+        shooting_mode: ShootingMode::default(),
+        sensor: TransSensor::TransV,
+        settings: Settings::TransV(TransVSettings {
+            white_balance: WhiteBalance::Kelvin {
+                temperature: 4700,
+                shift: WBShift { red: 4, blue: -2 },
+            },
+            dynamic_range: DynamicRange::DR200,
+            d_range_priority: DRangePriority::default(),
+            grain_effect: GrainEffect::StrengthAndSize {
+                strength: GrainStrength::Strong,
+                size: GrainSize::Small,
+            },
+            color_chrome_effect: ColorChromeEffect {
+                strength: SettingStrength::Strong,
+            },
+            color_chrome_fx_blue: ColorChromeEffectFxBlue::default(),
+            tone_curve: ToneCurve {
+                highlights: -2.,
+                shadows: 0.,
+            },
+            high_iso_noise_reduction: HighISONoiseReduction { value: -4 },
+            color: Color { value: 4 },
+            sharpness: Sharpness::default(),
+            clarity: Clarity::default(),
+            monochromatic_color: MonochromaticColor::default(),
+        }),
+        converted: false,
+    }
+}
+
+/// Recipe of `tests/img/DSCF5358.JPG`, whichever way its metadata is read.
+pub fn classic_chrome_recipe() -> FujifilmRecipeDetails {
+    FujifilmRecipeDetails {
+        processing_mode: ProcessingMode::FilmSimulation(FilmSimulation::ClassicChrome),
+        shooting_mode: ShootingMode::default(),
+        sensor: TransSensor::TransV,
+        settings: Settings::TransV(TransVSettings {
+            white_balance: WhiteBalance::Kelvin {
+                temperature: 5600,
+                shift: WBShift { red: 1, blue: 1 },
+            },
+            dynamic_range: DynamicRange::DR200,
+            d_range_priority: DRangePriority::default(),
+            grain_effect: GrainEffect::StrengthAndSize {
+                strength: GrainStrength::Weak,
+                size: GrainSize::Large,
+            },
+            color_chrome_effect: ColorChromeEffect {
+                strength: SettingStrength::Strong,
+            },
+            color_chrome_fx_blue: ColorChromeEffectFxBlue::default(),
+            tone_curve: ToneCurve {
+                highlights: -2.,
+                shadows: 1.,
+            },
+            high_iso_noise_reduction: HighISONoiseReduction { value: -4 },
+            color: Color { value: 3 },
+            sharpness: Sharpness::default(),
+            clarity: Clarity { value: 2 },
+            monochromatic_color: MonochromaticColor::default(),
+        }),
+        converted: false,
+    }
+}
@@ -0,0 +1,32 @@
+extern crate test_utilities;
+
+use fuji::{
+    exiftool::spawn,
+    native,
+    recipe::{read, FilmSimulation},
+};
+use std::fs::File;
+use test_utilities::get_manifest_dir;
+
+#[test]
+fn test_native_from_bytes() {
+    let data = std::fs::read(get_manifest_dir().join("tests/img/DSCF5230.JPG")).unwrap();
+
+    let result = native::read_metadata_from_bytes(&data).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(recipe.film_simulation(), Some(&FilmSimulation::ClassicNeg));
+}
+
+#[test]
+fn test_exiftool_from_reader() {
+    let file = File::open(get_manifest_dir().join("tests/img/DSCF5358.JPG")).unwrap();
+
+    let result = spawn::read_metadata_from_reader(file, None).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(
+        recipe.film_simulation(),
+        Some(&FilmSimulation::ClassicChrome)
+    );
+}
@@ -0,0 +1,64 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::{json::JsonValue, ExifData, ExifMap, FindExifData},
+    native,
+    recipe::read,
+};
+use pretty_assertions::assert_eq;
+use test_utilities::get_manifest_dir;
+
+fn read_map(name: &str) -> (Vec<ExifData>, ExifMap) {
+    let img_path = get_manifest_dir().join("tests/img").join(name);
+    let fields = native::read_metadata(&img_path).unwrap();
+
+    (fields.clone(), ExifMap::from(fields))
+}
+
+#[test]
+fn test_recipe_from_map() {
+    for name in ["DSCF5230.JPG", "DSCF5358.JPG"] {
+        let (fields, map) = read_map(name);
+
+        assert_eq!(
+            read::from_exif(&map).unwrap(),
+            read::from_exif(&fields).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_duplicate_white_balance() {
+    let (_, map) = read_map("DSCF5230.JPG");
+
+    let groups: Vec<Option<&str>> = map.get_all("WhiteBalance").map(|e| e.group()).collect();
+    assert_eq!(groups, vec![Some("FujiFilm"), Some("ExifIFD")]);
+
+    assert_eq!(map.get("WhiteBalance"), map.get("MakerNotes:WhiteBalance"));
+    assert_eq!(
+        map.get("EXIF:WhiteBalance").and_then(|e| e.group()),
+        Some("ExifIFD")
+    );
+}
+
+#[test]
+fn test_map_from_grouped_json() {
+    let json = serde_json::json!({
+        "SourceFile": "DSCF5230.JPG",
+        "IFD0:Make": "FUJIFILM",
+        "ExifIFD:WhiteBalance": "Manual",
+        "FujiFilm:WhiteBalance": "Kelvin",
+        "FujiFilm:WhiteBalance#": 4080,
+        "FujiFilm:ColorTemperature": 5500,
+    });
+    let fields: Vec<ExifData> = JsonValue(json).try_into().unwrap();
+    let map = ExifMap::from(fields);
+
+    assert_eq!(map.get("SourceFile").and_then(|e| e.group()), None);
+    assert_eq!(
+        map.find("WhiteBalance").map(|e| e.value().to_string()),
+        Some("Kelvin".to_string())
+    );
+    assert_eq!(map.find_number("WhiteBalance"), Some(4080));
+    assert_eq!(map.get_all("WhiteBalance").count(), 2);
+}
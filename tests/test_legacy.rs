@@ -0,0 +1,115 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::{
+        source::{JsonFixture, MetadataSource},
+        ExifData,
+    },
+    recipe::{
+        read, Color, DynamicRange, FilmSimulation, FujifilmRecipeDetails, HighISONoiseReduction,
+        ProcessingMode, Settings, Sharpness, ShootingMode, ToneCurve, TransIISettings,
+        TransISettings, TransSensor, WBShift, WhiteBalance,
+    },
+};
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use test_utilities::get_manifest_dir;
+
+fn read_fixture(name: &str, image: &str) -> Vec<ExifData> {
+    let fixture_path = get_manifest_dir().join("tests/fixtures").join(name);
+    let mut fixture = JsonFixture::open(&fixture_path).unwrap();
+
+    fixture.read_metadata(Path::new(image)).unwrap()
+}
+
+#[test]
+fn test_x_pro1() {
+    let fields = read_fixture("x-pro1.json", "DSCF1021.JPG");
+
+    let recipe = read::from_exif_strict(&fields).unwrap();
+
+    assert_eq!(
+        recipe,
+        Some(FujifilmRecipeDetails {
+            processing_mode: ProcessingMode::FilmSimulation(FilmSimulation::ProviaStandard),
+            shooting_mode: ShootingMode::default(),
+            sensor: TransSensor::TransI,
+            settings: Settings::TransI(TransISettings {
+                white_balance: WhiteBalance::Auto {
+                    shift: WBShift { red: 0, blue: 0 }
+                },
+                dynamic_range: DynamicRange::Auto,
+                tone_curve: ToneCurve {
+                    highlights: 1.0,
+                    shadows: -1.0
+                },
+                color: Color { value: 1 },
+                sharpness: Sharpness { value: 2 },
+                high_iso_noise_reduction: HighISONoiseReduction { value: -2 },
+            }),
+            converted: false,
+        })
+    );
+}
+
+#[test]
+fn test_x100s() {
+    let fields = read_fixture("x100s.json", "DSCF4410.JPG");
+
+    let recipe = read::from_exif_strict(&fields).unwrap();
+
+    assert_eq!(
+        recipe,
+        Some(FujifilmRecipeDetails {
+            processing_mode: ProcessingMode::FilmSimulation(FilmSimulation::VelviaVivid),
+            shooting_mode: ShootingMode::default(),
+            sensor: TransSensor::TransII,
+            settings: Settings::TransII(TransIISettings {
+                white_balance: WhiteBalance::Daylight {
+                    shift: WBShift { red: 1, blue: -2 }
+                },
+                dynamic_range: DynamicRange::DR200,
+                tone_curve: ToneCurve {
+                    highlights: 2.0,
+                    shadows: 0.0
+                },
+                color: Color { value: 2 },
+                sharpness: Sharpness { value: -1 },
+                high_iso_noise_reduction: HighISONoiseReduction { value: 1 },
+            }),
+            converted: false,
+        })
+    );
+}
+
+#[test]
+fn test_words_outside_the_scale() {
+    let fields: Vec<_> = read_fixture("x-pro1.json", "DSCF1021.JPG")
+        .into_iter()
+        .map(|exif| match exif.tag() {
+            "Sharpness" => ExifData::new("Sharpness", "Very Hard"),
+            _ => exif,
+        })
+        .collect();
+
+    let report = read::from_exif_with_report(&fields).unwrap().unwrap();
+
+    assert_eq!(report.defaulted().count(), 1);
+    assert!(read::from_exif_strict(&fields).is_err());
+}
+
+#[test]
+fn test_words_of_another_generation() {
+    let fields: Vec<_> = read_fixture("x100s.json", "DSCF4410.JPG")
+        .into_iter()
+        .map(|exif| match exif.tag() {
+            "NoiseReduction" => ExifData::new("NoiseReduction", "Medium High"),
+            _ => exif,
+        })
+        .collect();
+
+    let report = read::from_exif_with_report(&fields).unwrap().unwrap();
+
+    assert_eq!(report.defaulted().count(), 1);
+    assert!(read::from_exif_strict(&fields).is_err());
+}
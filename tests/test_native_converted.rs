@@ -0,0 +1,126 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::FindExifData,
+    native::{self, tiff::Tiff},
+    recipe::{read, FujifilmRecipeDetails},
+};
+use pretty_assertions::assert_eq;
+use test_utilities::get_manifest_dir;
+
+fn read_jpeg(name: &str) -> Vec<u8> {
+    std::fs::read(get_manifest_dir().join("tests/img").join(name)).unwrap()
+}
+
+fn recipe(data: &[u8]) -> FujifilmRecipeDetails {
+    let result = native::read_metadata_from_bytes(data).unwrap();
+
+    read::from_exif(result.as_slice()).unwrap().unwrap()
+}
+
+fn entry(tag: u16, format: u16, count: u32, value: [u8; 4]) -> Vec<u8> {
+    let mut data = vec![];
+    data.extend_from_slice(&tag.to_le_bytes());
+    data.extend_from_slice(&format.to_le_bytes());
+    data.extend_from_slice(&count.to_le_bytes());
+    data.extend_from_slice(&value);
+
+    data
+}
+
+/// Builds a DNG the way Adobe DNG Converter does it: Make & Model in IFD0 and
+/// the original MakerNote in the `DNGPrivateData`.
+fn dng_from_jpeg(jpeg: &[u8]) -> Vec<u8> {
+    let exif = native::jpeg::find_exif(jpeg).unwrap();
+    let tiff = Tiff::parse(exif).unwrap();
+    let ifd0 = tiff.ifd(tiff.first_ifd).unwrap();
+    let exif_ifd = tiff
+        .ifd(ifd0.get(0x8769).unwrap().offset().unwrap())
+        .unwrap();
+    let maker_note = &exif_ifd.get(0x927c).unwrap().data;
+
+    let mut private_data = b"Adobe\0MakN".to_vec();
+    private_data.extend_from_slice(&(maker_note.len() as u32 + 6).to_be_bytes());
+    private_data.extend_from_slice(b"II");
+    private_data.extend_from_slice(&0u32.to_be_bytes());
+    private_data.extend_from_slice(maker_note);
+
+    let make = b"FUJIFILM\0";
+    let model = b"X100VI\0";
+
+    // IFD0 at 8: 4 entries, ends at 8 + 2 + 48 + 4 = 62
+    let values = 62u32;
+    let mut data = b"II*\0".to_vec();
+    data.extend_from_slice(&8u32.to_le_bytes());
+    data.extend_from_slice(&4u16.to_le_bytes());
+    data.extend(entry(0x010f, 2, make.len() as u32, values.to_le_bytes()));
+    let model_offset = values + make.len() as u32;
+    data.extend(entry(
+        0x0110,
+        2,
+        model.len() as u32,
+        model_offset.to_le_bytes(),
+    ));
+    data.extend(entry(0xc612, 1, 4, [1, 4, 0, 0]));
+    let private_offset = model_offset + model.len() as u32;
+    data.extend(entry(
+        0xc634,
+        1,
+        private_data.len() as u32,
+        private_offset.to_le_bytes(),
+    ));
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(make);
+    data.extend_from_slice(model);
+    data.extend(private_data);
+
+    data
+}
+
+#[test]
+fn test_tiff_export() {
+    let jpeg = read_jpeg("DSCF5230.JPG");
+    // X RAW Studio keeps the EXIF & MakerNote as-is, the TIFF structure of the
+    // JPEG is a valid TIFF file on its own.
+    let tiff = native::jpeg::find_exif(&jpeg).unwrap();
+
+    let from_jpeg = recipe(&jpeg);
+    let from_tiff = recipe(tiff);
+
+    assert!(!from_jpeg.converted);
+    assert!(from_tiff.converted);
+    assert_eq!(
+        from_tiff,
+        FujifilmRecipeDetails {
+            converted: true,
+            ..from_jpeg
+        }
+    );
+}
+
+#[test]
+fn test_dng_private_data() {
+    let jpeg = read_jpeg("DSCF5358.JPG");
+    let dng = dng_from_jpeg(&jpeg);
+
+    let result = native::read_metadata_from_bytes(&dng).unwrap();
+    assert_eq!(
+        result
+            .as_slice()
+            .find("FileType")
+            .map(|e| e.value().to_string()),
+        Some("DNG".to_string())
+    );
+
+    let from_jpeg = recipe(&jpeg);
+    let from_dng = recipe(&dng);
+
+    assert!(from_dng.converted);
+    assert_eq!(
+        from_dng,
+        FujifilmRecipeDetails {
+            converted: true,
+            ..from_jpeg
+        }
+    );
+}
@@ -0,0 +1,106 @@
+extern crate test_utilities;
+
+use fuji::{
+    native,
+    recipe::{read, FilmSimulation, TransSensor},
+};
+use pretty_assertions::assert_eq;
+use test_utilities::get_manifest_dir;
+
+fn iso_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+    data.extend_from_slice(kind);
+    data.extend_from_slice(body);
+
+    data
+}
+
+fn full_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut data = vec![0, 0, 0, 0];
+    data.extend_from_slice(body);
+
+    iso_box(kind, &data)
+}
+
+/// Moves the EXIF of a JPEG into a minimal HEIF container, the way the camera
+/// stores it as an `Exif` item in `mdat`.
+fn heif_from_jpeg(jpeg: &[u8]) -> Vec<u8> {
+    let exif = native::jpeg::find_exif(jpeg).unwrap();
+
+    let mut item = 6u32.to_be_bytes().to_vec();
+    item.extend_from_slice(b"Exif\0\0");
+    item.extend_from_slice(exif);
+
+    let mut infe = 1u16.to_be_bytes().to_vec();
+    infe.extend_from_slice(&0u16.to_be_bytes());
+    infe.extend_from_slice(b"Exif\0");
+    let mut infe = full_box(b"infe", &infe);
+    infe[8] = 2;
+
+    let mut iinf = 1u16.to_be_bytes().to_vec();
+    iinf.extend(infe);
+
+    let meta = |item_offset: u32| {
+        // Version 0, 4-byte offsets & lengths, no base offset
+        let mut iloc = vec![0x44, 0x00];
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&0u16.to_be_bytes());
+        iloc.extend_from_slice(&1u16.to_be_bytes());
+        iloc.extend_from_slice(&item_offset.to_be_bytes());
+        iloc.extend_from_slice(&(item.len() as u32).to_be_bytes());
+
+        let mut meta = full_box(b"iinf", &iinf);
+        meta.extend(full_box(b"iloc", &iloc));
+
+        full_box(b"meta", &meta)
+    };
+
+    let ftyp = iso_box(b"ftyp", b"heix\0\0\0\0mif1heix");
+    // The item starts right after the mdat header
+    let item_offset = ftyp.len() + meta(0).len() + 8;
+    let meta = meta(item_offset as u32);
+
+    let mut data = ftyp;
+    data.extend(meta);
+    data.extend(iso_box(b"mdat", &item));
+
+    data
+}
+
+#[test]
+fn test_classic_neg() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let jpeg = std::fs::read(img_path).unwrap();
+    let heif = heif_from_jpeg(&jpeg);
+
+    let result = native::read_metadata_from_bytes(&heif).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(recipe.film_simulation(), Some(&FilmSimulation::ClassicNeg));
+    assert_eq!(recipe.sensor, TransSensor::TransV);
+
+    let expected = read::from_exif(native::read_jpeg(&jpeg).unwrap().as_slice()).unwrap();
+    assert_eq!(Some(recipe), expected);
+}
+
+#[test]
+fn test_hif_extension() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5358.JPG");
+    let jpeg = std::fs::read(img_path).unwrap();
+
+    let heif_path = std::env::temp_dir().join(format!("fuji-{}.HIF", std::process::id()));
+    std::fs::write(&heif_path, heif_from_jpeg(&jpeg)).unwrap();
+
+    let result = native::read_metadata(&heif_path);
+    std::fs::remove_file(&heif_path).unwrap();
+
+    let recipe = read::from_exif(result.unwrap().as_slice())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        recipe.film_simulation(),
+        Some(&FilmSimulation::ClassicChrome)
+    );
+}
@@ -0,0 +1,25 @@
+extern crate test_utilities;
+
+use fuji::{native, recipe::read};
+use pretty_assertions::assert_eq;
+use test_utilities::{classic_chrome_recipe, classic_neg_recipe, get_manifest_dir};
+
+#[test]
+fn test_classic_neg() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+
+    let result = native::read_metadata(&img_path).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap();
+
+    assert_eq!(recipe, Some(classic_neg_recipe()));
+}
+
+#[test]
+fn test_classic_chrome() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5358.JPG");
+
+    let result = native::read_metadata(&img_path).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap();
+
+    assert_eq!(recipe, Some(classic_chrome_recipe()));
+}
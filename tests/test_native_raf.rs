@@ -0,0 +1,48 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::FindExifData,
+    native,
+    recipe::{read, FilmSimulation, TransSensor},
+};
+use pretty_assertions::assert_eq;
+use test_utilities::get_manifest_dir;
+
+/// Wraps a JPEG into a minimal RAF container, the way the camera stores the
+/// preview next to the raw data.
+fn raf_from_jpeg(jpeg: &[u8]) -> Vec<u8> {
+    let mut data = b"FUJIFILMCCD-RAW 0201FF129502".to_vec();
+    let mut model = b"X100VI".to_vec();
+    model.resize(32, 0);
+    data.extend(model);
+    data.extend_from_slice(b"0100");
+    data.resize(0x54, 0);
+
+    let jpeg_offset = 0x6c_u32;
+    data.extend_from_slice(&jpeg_offset.to_be_bytes());
+    data.extend_from_slice(&(jpeg.len() as u32).to_be_bytes());
+    data.resize(0x6c, 0);
+    data.extend_from_slice(jpeg);
+
+    data
+}
+
+#[test]
+fn test_classic_neg() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let jpeg = std::fs::read(img_path).unwrap();
+    let raf = raf_from_jpeg(&jpeg);
+
+    let result = native::read_raf(&raf).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(recipe.film_simulation(), Some(&FilmSimulation::ClassicNeg));
+    assert_eq!(recipe.sensor, TransSensor::TransV);
+    assert_eq!(
+        result
+            .as_slice()
+            .find("RAFVersion")
+            .map(|e| e.value().to_string()),
+        Some("0100".to_string())
+    );
+}
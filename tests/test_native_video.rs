@@ -0,0 +1,148 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::FromExifData,
+    native::{self, tiff::Tiff},
+    recipe::{read, FilmSimulation, FujifilmVideoRecipe, ToneCurve, VideoGamma, WhiteBalance},
+};
+use pretty_assertions::assert_eq;
+use test_utilities::get_manifest_dir;
+
+fn iso_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut data = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+    data.extend_from_slice(kind);
+    data.extend_from_slice(body);
+
+    data
+}
+
+fn entry(tag: u16, format: u16, count: u32, value: [u8; 4]) -> Vec<u8> {
+    let mut data = vec![];
+    data.extend_from_slice(&tag.to_le_bytes());
+    data.extend_from_slice(&format.to_le_bytes());
+    data.extend_from_slice(&count.to_le_bytes());
+    data.extend_from_slice(&value);
+
+    data
+}
+
+fn jpeg_maker_note(name: &str) -> Vec<u8> {
+    let jpeg = std::fs::read(get_manifest_dir().join("tests/img").join(name)).unwrap();
+    let exif = native::jpeg::find_exif(&jpeg).unwrap();
+    let tiff = Tiff::parse(exif).unwrap();
+    let ifd0 = tiff.ifd(tiff.first_ifd).unwrap();
+    let exif_ifd = tiff
+        .ifd(ifd0.get(0x8769).unwrap().offset().unwrap())
+        .unwrap();
+
+    exif_ifd.get(0x927c).unwrap().data.clone()
+}
+
+/// MakerNote with only the White Balance (Daylight) and Video Recording Mode.
+fn video_maker_note(mode: u16) -> Vec<u8> {
+    let mut data = b"FUJIFILM".to_vec();
+    data.extend_from_slice(&12u32.to_le_bytes());
+    data.extend_from_slice(&2u16.to_le_bytes());
+    data.extend(entry(0x1002, 3, 1, [0x00, 0x01, 0, 0]));
+    let mode = mode.to_le_bytes();
+    data.extend(entry(0x3803, 3, 1, [mode[0], mode[1], 0, 0]));
+    data.extend_from_slice(&0u32.to_le_bytes());
+
+    data
+}
+
+/// Builds a movie the way Fujifilm cameras write it: the `mdat` first, then
+/// `moov` with an EXIF IFD (Make & MakerNote) in the `MVTG` atom of `udta`.
+fn mov_from_maker_note(maker_note: &[u8]) -> Vec<u8> {
+    let make = b"FUJIFILM\0";
+
+    // IFD at 0: 2 entries, ends at 2 + 24 + 4 = 30
+    let values = 30u32;
+    let mut ifd = 2u16.to_le_bytes().to_vec();
+    ifd.extend(entry(0x010f, 2, make.len() as u32, values.to_le_bytes()));
+    let maker_note_offset = values + make.len() as u32;
+    ifd.extend(entry(
+        0x927c,
+        7,
+        maker_note.len() as u32,
+        maker_note_offset.to_le_bytes(),
+    ));
+    ifd.extend_from_slice(&0u32.to_le_bytes());
+    ifd.extend_from_slice(make);
+    ifd.extend_from_slice(maker_note);
+
+    let mut mvtg = vec![0; 16];
+    mvtg.extend(ifd);
+
+    let mut udta = iso_box(b"FFMV", b"FUJIFILM DIGITAL CAMERA X100VI");
+    udta.extend(iso_box(b"MVTG", &mvtg));
+
+    let mut moov = iso_box(b"mvhd", &[0; 100]);
+    moov.extend(iso_box(b"udta", &udta));
+
+    let mut data = iso_box(b"ftyp", b"qt  \0\0\0\0qt  ");
+    data.extend(iso_box(b"wide", b""));
+    data.extend(iso_box(b"mdat", &[0; 4096]));
+    data.extend(iso_box(b"moov", &moov));
+
+    data
+}
+
+#[test]
+fn test_classic_neg() {
+    let mov = mov_from_maker_note(&jpeg_maker_note("DSCF5230.JPG"));
+
+    let result = native::read_metadata_from_bytes(&mov).unwrap();
+    let recipe = read::video_from_exif(result.as_slice()).unwrap().unwrap();
+
+    let jpeg = std::fs::read(get_manifest_dir().join("tests/img/DSCF5230.JPG")).unwrap();
+    let photo = native::read_jpeg(&jpeg).unwrap();
+
+    assert_eq!(
+        recipe,
+        FujifilmVideoRecipe {
+            film_simulation: Some(FilmSimulation::ClassicNeg),
+            gamma: VideoGamma::Standard,
+            white_balance: WhiteBalance::from_exif(&photo).unwrap(),
+            tone_curve: ToneCurve::from_exif(&photo),
+        }
+    );
+}
+
+#[test]
+fn test_f_log() {
+    let mov = mov_from_maker_note(&video_maker_note(0x10));
+
+    let result = native::read_metadata_from_bytes(&mov).unwrap();
+    let recipe = read::video_from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(
+        recipe,
+        FujifilmVideoRecipe {
+            film_simulation: None,
+            gamma: VideoGamma::FLog,
+            white_balance: WhiteBalance::Daylight {
+                shift: Default::default()
+            },
+            tone_curve: None,
+        }
+    );
+}
+
+#[test]
+fn test_mov_extension() {
+    let mov = mov_from_maker_note(&jpeg_maker_note("DSCF5358.JPG"));
+
+    let mov_path = std::env::temp_dir().join(format!("fuji-{}.MOV", std::process::id()));
+    std::fs::write(&mov_path, mov).unwrap();
+
+    let result = native::read_metadata(&mov_path);
+    std::fs::remove_file(&mov_path).unwrap();
+
+    let recipe = read::video_from_exif(result.unwrap().as_slice())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(recipe.film_simulation, Some(FilmSimulation::ClassicChrome));
+    assert_eq!(recipe.gamma, VideoGamma::Standard);
+}
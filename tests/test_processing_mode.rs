@@ -0,0 +1,86 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::{ExifData, ExifValue},
+    native,
+    recipe::{
+        read, AdvancedFilter, FilmSimulation, FujifilmRecipeDetails, PartialColorHue,
+        ProcessingMode,
+    },
+};
+use pretty_assertions::assert_eq;
+use test_utilities::{get_manifest_dir, read_with_changes};
+
+/// Fields of a JPEG shot with `filter` instead of a film simulation.
+fn read_with_filter(filter: i64) -> Vec<ExifData> {
+    read_with_changes(&[
+        ("FilmMode", ExifValue::Null),
+        ("AdvancedFilter#", ExifValue::Int(filter)),
+    ])
+}
+
+#[test]
+fn test_film_simulation() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let fields = native::read_metadata(&img_path).unwrap();
+
+    let recipe = read::from_exif(&fields).unwrap().unwrap();
+
+    assert_eq!(
+        recipe.processing_mode,
+        ProcessingMode::FilmSimulation(FilmSimulation::ClassicNeg)
+    );
+}
+
+#[test]
+fn test_advanced_filter() {
+    let recipe = read::from_exif(&read_with_filter(0x30000))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        recipe.processing_mode,
+        ProcessingMode::AdvancedFilter(AdvancedFilter::ToyCamera)
+    );
+    assert_eq!(recipe.film_simulation(), None);
+    assert_eq!(recipe.processing_mode.to_string(), "Toy Camera");
+}
+
+#[test]
+fn test_deserialize_film_simulation() {
+    let json = r#"{
+        "film_simulation": "ClassicChrome",
+        "sensor": "TransV",
+        "settings": {}
+    }"#;
+
+    let recipe: FujifilmRecipeDetails = serde_json::from_str(json).unwrap();
+
+    assert_eq!(
+        recipe.processing_mode,
+        ProcessingMode::FilmSimulation(FilmSimulation::ClassicChrome)
+    );
+    assert_eq!(
+        recipe.film_simulation(),
+        Some(&FilmSimulation::ClassicChrome)
+    );
+}
+
+#[test]
+fn test_partial_color() {
+    let report = read::from_exif_with_report(&read_with_filter(0x60001))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        report.recipe.processing_mode,
+        ProcessingMode::AdvancedFilter(AdvancedFilter::PartialColor {
+            hue: PartialColorHue::Red
+        })
+    );
+    assert_eq!(
+        report.recipe.processing_mode.to_string(),
+        "Partial Color Red"
+    );
+    assert_eq!(report.warnings, vec![]);
+}
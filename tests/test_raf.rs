@@ -5,8 +5,8 @@ use fuji::{
     recipe::{
         read, Clarity, Color, ColorChromeEffect, ColorChromeEffectFxBlue, DRangePriority,
         DynamicRange, FilmSimulation, FujifilmRecipeDetails, GrainEffect, GrainSize, GrainStrength,
-        HighISONoiseReduction, MonochromaticColor, SettingStrength, Settings, Sharpness, ToneCurve,
-        TransSensor, TransVSettings, WBShift, WhiteBalance,
+        HighISONoiseReduction, MonochromaticColor, ProcessingMode, SettingStrength, Settings,
+        Sharpness, ShootingMode, ToneCurve, TransSensor, TransVSettings, WBShift, WhiteBalance,
     },
 };
 use pretty_assertions::assert_eq;
@@ -20,7 +20,8 @@ fn test_classic_neg() {
     let recipe = read::from_exif(result.as_slice()).unwrap();
 
     let expected = Some(FujifilmRecipeDetails {
-        film_simulation: FilmSimulation::ClassicNeg,
+        processing_mode: ProcessingMode::FilmSimulation(FilmSimulation::ClassicNeg),
+        shooting_mode: ShootingMode::default(),
         sensor: TransSensor::TransV,
         settings: Settings::TransV(TransVSettings {
             white_balance: WhiteBalance::Kelvin {
@@ -47,6 +48,7 @@ fn test_classic_neg() {
             clarity: Clarity::default(),
             monochromatic_color: MonochromaticColor::default(),
         }),
+        converted: false,
     });
     assert_eq!(recipe, expected);
 }
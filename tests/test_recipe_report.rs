@@ -0,0 +1,179 @@
+extern crate test_utilities;
+
+use fuji::{
+    camera::FirmwareVersion,
+    exif::{ExifData, ExifValue, FieldError},
+    native,
+    recipe::{
+        read,
+        report::{Field, FieldStatus, RecipeWarning},
+        ColorChromeEffect, FilmSimulation, GrainEffect, Settings,
+    },
+};
+use pretty_assertions::assert_eq;
+use test_utilities::get_manifest_dir;
+
+fn read_jpeg(name: &str) -> Vec<ExifData> {
+    let img_path = get_manifest_dir().join("tests/img").join(name);
+
+    native::read_metadata(&img_path).unwrap()
+}
+
+#[test]
+fn test_every_setting_is_parsed() {
+    for name in ["DSCF5230.JPG", "DSCF5358.JPG"] {
+        let fields = read_jpeg(name);
+
+        let report = read::from_exif_with_report(&fields).unwrap().unwrap();
+
+        assert_eq!(report.fields.len(), 12);
+        assert_eq!(report.defaulted().count(), 0);
+        assert_eq!(
+            Some(report.recipe.clone()),
+            read::from_exif(&fields).unwrap()
+        );
+        assert_eq!(
+            read::from_exif_strict(&fields).unwrap(),
+            Some(report.recipe)
+        );
+    }
+}
+
+#[test]
+fn test_missing_grain_effect() {
+    let fields: Vec<ExifData> = read_jpeg("DSCF5230.JPG")
+        .into_iter()
+        .filter(|exif| !exif.tag().starts_with("GrainEffectRoughness"))
+        .collect();
+
+    let report = read::from_exif_with_report(&fields).unwrap().unwrap();
+
+    assert_eq!(
+        report.status(Field::GrainEffect),
+        Some(&FieldStatus::Missing {
+            tag: "GrainEffectRoughness".to_string()
+        })
+    );
+    assert_eq!(report.defaulted().count(), 1);
+
+    let Settings::TransV(settings) = report.recipe.settings else {
+        panic!("Expected X-Trans V settings");
+    };
+    assert_eq!(settings.grain_effect, GrainEffect::Off);
+
+    let error = read::from_exif_strict(&fields).unwrap_err();
+    assert!(matches!(
+        error,
+        read::Error::Defaulted {
+            field: Field::GrainEffect,
+            ..
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Grain Effect was not read, GrainEffectRoughness is missing"
+    );
+}
+
+#[test]
+fn test_unrecognized_color_chrome_effect() {
+    let fields: Vec<ExifData> = read_jpeg("DSCF5230.JPG")
+        .into_iter()
+        .map(|exif| match exif.tag() {
+            "ColorChromeEffect#" => ExifData::grouped("FujiFilm", exif.tag(), ExifValue::Int(48)),
+            "ColorChromeEffect" => ExifData::grouped("FujiFilm", exif.tag(), "Medium".into()),
+            _ => exif,
+        })
+        .collect();
+
+    let report = read::from_exif_with_report(&fields).unwrap().unwrap();
+
+    assert_eq!(
+        report.status(Field::ColorChromeEffect),
+        Some(&FieldStatus::Unrecognized {
+            tag: "ColorChromeEffect".to_string(),
+            value: "Medium".to_string()
+        })
+    );
+
+    let Settings::TransV(settings) = report.recipe.settings else {
+        panic!("Expected X-Trans V settings");
+    };
+    assert_eq!(settings.color_chrome_effect, ColorChromeEffect::default());
+
+    assert!(read::from_exif_strict(&fields).is_err());
+}
+
+#[test]
+fn test_unsupported_model() {
+    let fields: Vec<ExifData> = read_jpeg("DSCF5230.JPG")
+        .into_iter()
+        .map(|exif| match exif.tag() {
+            "Model" => ExifData::grouped("IFD0", "Model", "FinePix S1".into()),
+            _ => exif,
+        })
+        .collect();
+
+    let error = read::from_exif(&fields).unwrap_err();
+
+    assert!(matches!(
+        &error,
+        read::Error::FujifilmRecipeDetails {
+            source: FieldError::UnsupportedModel { model }
+        } if model == "FinePix S1"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Could not read Fujifilm Recipe details from EXIF: unsupported camera model FinePix S1"
+    );
+}
+
+#[test]
+fn test_firmware_version() {
+    let report = read::from_exif_with_report(&read_jpeg("DSCF5230.JPG"))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(report.firmware, Some(FirmwareVersion::new(1, 20)));
+    assert_eq!(
+        report.camera.map(|camera| camera.name),
+        Some("X100VI".to_string())
+    );
+    assert_eq!(report.warnings, vec![]);
+}
+
+#[test]
+fn test_unsupported_before_firmware_update() {
+    let fields: Vec<ExifData> = read_jpeg("DSCF5230.JPG")
+        .into_iter()
+        .map(|exif| match exif.tag() {
+            "Model" => ExifData::grouped("IFD0", "Model", "X-T3".into()),
+            "Software" => {
+                ExifData::grouped("IFD0", "Software", "Digital Camera X-T3 Ver2.00".into())
+            }
+            "ColorChromeFXBlue#" => ExifData::grouped("FujiFilm", exif.tag(), ExifValue::Int(64)),
+            _ => exif,
+        })
+        .collect();
+
+    let report = read::from_exif_with_report(&fields).unwrap().unwrap();
+
+    assert_eq!(
+        report
+            .warnings
+            .iter()
+            .map(|warning| warning.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "Classic Negative is not supported by the X-T3 with firmware 2.00",
+            "Color Chrome FX Blue is not supported by the X-T3 with firmware 2.00",
+        ]
+    );
+    assert!(matches!(
+        report.warnings[0],
+        RecipeWarning::UnsupportedFilmSimulation {
+            film_simulation: FilmSimulation::ClassicNeg,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,155 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::{ExifData, ExifValue},
+    recipe::{
+        read, Clarity, DynamicRange, FujifilmRecipeDetails, MonochromaticColor,
+        MonochromaticColorShift, Settings, TransSensor,
+    },
+};
+use pretty_assertions::assert_eq;
+use test_utilities::read_with_changes;
+
+/// Fields of an X100VI JPEG, as if they were written by `model`.
+fn read_as(model: &str) -> Vec<ExifData> {
+    read_with_changes(&[("Model", ExifValue::from(model))])
+}
+
+#[test]
+fn test_gfx_100_recipe() {
+    let original = read::from_exif(&read_as("X100VI")).unwrap().unwrap();
+    let recipe = read::from_exif(&read_as("GFX100S")).unwrap().unwrap();
+
+    assert_eq!(recipe.sensor, TransSensor::Gfx100);
+    assert!(matches!(recipe.settings, Settings::Gfx100(_)));
+    assert_eq!(recipe.settings.get_values(), original.settings.get_values());
+}
+
+#[test]
+fn test_gfx_50_recipe() {
+    let recipe = read::from_exif_with_report(&read_as("GFX50S II"))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(recipe.recipe.sensor, TransSensor::Gfx50);
+    assert!(matches!(recipe.recipe.settings, Settings::Gfx50(_)));
+    assert_eq!(recipe.fields.len(), 12);
+}
+
+#[test]
+fn test_gfx_50_without_later_settings() {
+    let recipe = read::from_exif(&read_with_changes(&[
+        ("Model", ExifValue::from("GFX 50S")),
+        ("DRangePriority", ExifValue::Null),
+        ("ColorChromeFXBlue", ExifValue::Null),
+        ("Clarity", ExifValue::Null),
+    ]))
+    .unwrap()
+    .unwrap();
+
+    let Settings::Gfx50(settings) = recipe.settings else {
+        panic!("Expected GFX 50 settings");
+    };
+    assert_eq!(settings.d_range_priority, None);
+    assert_eq!(settings.color_chrome_fx_blue, None);
+    assert_eq!(settings.clarity, None);
+}
+
+#[test]
+fn test_bayer_recipe() {
+    let original = read::from_exif(&read_as("X100VI")).unwrap().unwrap();
+    let report = read::from_exif_with_report(&read_as("X-T200"))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(report.recipe.sensor, TransSensor::Bayer);
+    assert_eq!(report.fields.len(), 6);
+
+    let Settings::Bayer(settings) = report.recipe.settings else {
+        panic!("Expected Bayer settings");
+    };
+    let Settings::TransV(original) = original.settings else {
+        panic!("Expected X-Trans V settings");
+    };
+    assert_eq!(settings.white_balance, original.white_balance);
+    assert_eq!(settings.tone_curve, original.tone_curve);
+    assert_eq!(settings.color, original.color);
+}
+
+#[test]
+fn test_deserialize_settings_of_the_sensor() {
+    let json = r#"{
+        "processing_mode": { "FilmSimulation": "ProviaStandard" },
+        "sensor": "Gfx50",
+        "settings": {
+            "dynamic_range": "DR200",
+            "clarity": { "value": 2 }
+        }
+    }"#;
+
+    let recipe: FujifilmRecipeDetails = serde_json::from_str(json).unwrap();
+
+    let Settings::Gfx50(settings) = recipe.settings else {
+        panic!("Expected GFX 50 settings");
+    };
+    assert_eq!(settings.dynamic_range, DynamicRange::DR200);
+    assert_eq!(settings.clarity, Some(Clarity { value: 2 }));
+    assert_eq!(settings.d_range_priority, None);
+}
+
+#[test]
+fn test_deserialize_bayer_settings() {
+    let json = r#"{
+        "film_simulation": "ProviaStandard",
+        "sensor": "Bayer",
+        "settings": { "dynamic_range": "DR400" }
+    }"#;
+
+    let recipe: FujifilmRecipeDetails = serde_json::from_str(json).unwrap();
+
+    let Settings::Bayer(settings) = recipe.settings else {
+        panic!("Expected Bayer settings");
+    };
+    assert_eq!(settings.dynamic_range, DynamicRange::DR400);
+}
+
+#[test]
+fn test_trans_iii_toning() {
+    let fields = read_with_changes(&[
+        ("Model", ExifValue::from("X-T2")),
+        ("FilmMode", ExifValue::from("Acros")),
+        ("BWAdjustment", ExifValue::from("3")),
+    ]);
+
+    let recipe = read::from_exif(&fields).unwrap().unwrap();
+
+    let Settings::TransIII(settings) = recipe.settings else {
+        panic!("Expected X-Trans III settings");
+    };
+    assert_eq!(
+        settings.monochromatic_color,
+        MonochromaticColor::Strength { value: 3 }
+    );
+}
+
+#[test]
+fn test_trans_iv_toning() {
+    let fields = read_with_changes(&[
+        ("Model", ExifValue::from("X-T3")),
+        ("FilmMode", ExifValue::from("Acros")),
+        ("BWAdjustment", ExifValue::from("3")),
+        ("BWMagentaGreen", ExifValue::from("-2")),
+    ]);
+
+    let recipe = read::from_exif(&fields).unwrap().unwrap();
+
+    let Settings::TransIV(settings) = recipe.settings else {
+        panic!("Expected X-Trans IV settings");
+    };
+    assert_eq!(
+        settings.monochromatic_color,
+        MonochromaticColor::ColorShift {
+            shift: MonochromaticColorShift { wc: 3, mg: -2 }
+        }
+    );
+}
@@ -0,0 +1,84 @@
+extern crate test_utilities;
+
+use fuji::{
+    exiftool::{session::ExiftoolSession, spawn},
+    recipe::{read, FilmSimulation},
+};
+use pretty_assertions::assert_eq;
+use test_utilities::get_manifest_dir;
+
+#[test]
+fn test_reads_many_files_with_one_session() {
+    let mut session = ExiftoolSession::new(None).unwrap();
+
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let result = session.read_metadata(&img_path).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+    assert_eq!(recipe.film_simulation(), Some(&FilmSimulation::ClassicNeg));
+
+    let img_path = get_manifest_dir().join("tests/img/DSCF5358.JPG");
+    let result = session.read_metadata(&img_path).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+    assert_eq!(
+        recipe.film_simulation(),
+        Some(&FilmSimulation::ClassicChrome)
+    );
+}
+
+#[test]
+fn test_session_matches_one_shot_read() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5358.JPG");
+
+    let mut session = ExiftoolSession::new(None).unwrap();
+    let from_session = session.read_metadata(&img_path).unwrap();
+    let from_spawn = spawn::read_metadata(&img_path, None).unwrap();
+
+    assert_eq!(from_session, from_spawn);
+}
+
+#[test]
+fn test_batch_reads_images_apart_from_movies() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let mov_path = get_manifest_dir().join("tests/img/DSCF0001.MOV");
+
+    let results = spawn::read_metadata_batch(&[&img_path, &mov_path], None);
+    let single = spawn::read_metadata(&img_path, None).unwrap();
+
+    assert_eq!(results[0].1.as_ref().unwrap(), &single);
+    assert!(results[1].1.is_err());
+}
+
+#[test]
+fn test_reports_missing_files() {
+    let mut session = ExiftoolSession::new(None).unwrap();
+
+    let img_path = get_manifest_dir().join("tests/img/MISSING.JPG");
+    assert!(session.read_metadata(&img_path).is_err());
+
+    // The session is still usable afterwards.
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    assert!(session.read_metadata(&img_path).is_ok());
+}
+
+#[test]
+fn test_reads_a_batch() {
+    let paths = vec![
+        get_manifest_dir().join("tests/img/DSCF5230.JPG"),
+        get_manifest_dir().join("tests/img/MISSING.JPG"),
+        get_manifest_dir().join("tests/img/DSCF5358.JPG"),
+    ];
+
+    let results = spawn::read_metadata_batch(&paths, None);
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].0, paths[0]);
+    assert!(results[1].1.is_err());
+
+    let recipe = read::from_exif(results[2].1.as_ref().unwrap())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        recipe.film_simulation(),
+        Some(&FilmSimulation::ClassicChrome)
+    );
+}
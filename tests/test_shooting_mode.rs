@@ -0,0 +1,71 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::ExifValue,
+    native,
+    recipe::{read, CropMode, MultipleExposure, ProMode, ShootingMode},
+};
+use pretty_assertions::assert_eq;
+use test_utilities::{get_manifest_dir, read_with_changes};
+
+#[test]
+fn test_standard_shot() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let fields = native::read_metadata(&img_path).unwrap();
+
+    let recipe = read::from_exif(&fields).unwrap().unwrap();
+
+    assert_eq!(recipe.shooting_mode, ShootingMode::default());
+    assert!(!recipe.shooting_mode.is_special());
+}
+
+#[test]
+fn test_hdr() {
+    let recipe = read::from_exif(&read_with_changes(&[(
+        "PictureMode#",
+        ExifValue::Int(0x30),
+    )]))
+    .unwrap()
+    .unwrap();
+
+    assert!(recipe.shooting_mode.hdr);
+    assert!(recipe.shooting_mode.is_special());
+}
+
+#[test]
+fn test_pro_low_light() {
+    let recipe = read::from_exif(&read_with_changes(&[
+        ("PictureMode#", ExifValue::Int(0x18)),
+        ("ExposureCount", ExifValue::Int(4)),
+    ]))
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+        recipe.shooting_mode,
+        ShootingMode {
+            pro_mode: Some(ProMode::ProLowLight),
+            ..ShootingMode::default()
+        }
+    );
+    assert!(recipe.shooting_mode.is_special());
+}
+
+#[test]
+fn test_multiple_exposure_with_crop() {
+    let recipe = read::from_exif(&read_with_changes(&[
+        ("ExposureCount", ExifValue::Int(2)),
+        ("CropMode#", ExifValue::Int(2)),
+    ]))
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(
+        recipe.shooting_mode,
+        ShootingMode {
+            multiple_exposure: Some(MultipleExposure { exposures: 2 }),
+            crop: Some(CropMode::SportsFinder),
+            ..ShootingMode::default()
+        }
+    );
+}
@@ -0,0 +1,34 @@
+extern crate test_utilities;
+
+use fuji::{
+    exif::source::{Fallback, MetadataSource},
+    exiftool::spawn::ExiftoolSpawn,
+    native::NativeReader,
+    recipe::{read, FilmSimulation},
+};
+use test_utilities::get_manifest_dir;
+
+#[test]
+fn test_native_source() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+    let mut source: Box<dyn MetadataSource> = Box::new(NativeReader);
+
+    let result = source.read_metadata(&img_path).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(recipe.film_simulation(), Some(&FilmSimulation::ClassicNeg));
+}
+
+#[test]
+fn test_native_source_with_fallback() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5358.JPG");
+    let mut source = Fallback::new(NativeReader, ExiftoolSpawn::new(None));
+
+    let result = source.read_metadata(&img_path).unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(
+        recipe.film_simulation(),
+        Some(&FilmSimulation::ClassicChrome)
+    );
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "tokio")]
+
+extern crate test_utilities;
+
+use fuji::{
+    exiftool::{
+        options::ExiftoolOptions,
+        spawn::Error,
+        tokio::{read_metadata, AsyncExiftoolSession},
+    },
+    recipe::{read, FilmSimulation},
+};
+use std::time::Duration;
+use test_utilities::get_manifest_dir;
+
+#[tokio::test]
+async fn test_async_read_metadata() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+
+    let result = read_metadata(&img_path, None).await.unwrap();
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(recipe.film_simulation(), Some(&FilmSimulation::ClassicNeg));
+}
+
+#[tokio::test]
+async fn test_async_session_batch() {
+    let paths = vec![
+        get_manifest_dir().join("tests/img/DSCF5230.JPG"),
+        get_manifest_dir().join("tests/img/DSCF5358.JPG"),
+    ];
+
+    let mut session = AsyncExiftoolSession::new(None).unwrap();
+    let results = session.read_metadata_batch(&paths).await;
+    session.close().await.unwrap();
+
+    let recipe = read::from_exif(results[1].1.as_ref().unwrap())
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        recipe.film_simulation(),
+        Some(&FilmSimulation::ClassicChrome)
+    );
+}
+
+#[tokio::test]
+async fn test_async_session_after_dropped_request() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+
+    let mut session = AsyncExiftoolSession::new(None).unwrap();
+
+    // Dropped before exiftool could answer, its output is never read
+    let dropped = tokio::time::timeout(Duration::ZERO, session.read_metadata(&img_path)).await;
+    assert!(dropped.is_err());
+
+    let result = session.read_metadata(&img_path).await.unwrap();
+    session.close().await.unwrap();
+
+    let recipe = read::from_exif(result.as_slice()).unwrap().unwrap();
+
+    assert_eq!(recipe.film_simulation(), Some(&FilmSimulation::ClassicNeg));
+}
+
+#[tokio::test]
+async fn test_async_session_timeout() {
+    let img_path = get_manifest_dir().join("tests/img/DSCF5230.JPG");
+
+    let options = ExiftoolOptions::new().timeout(Duration::ZERO);
+    let mut session = AsyncExiftoolSession::with_options(options).unwrap();
+
+    assert!(matches!(
+        session.read_metadata(&img_path).await,
+        Err(Error::Timeout { .. })
+    ));
+}